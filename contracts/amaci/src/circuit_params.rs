@@ -1,4 +1,4 @@
-use crate::groth16_parser::parse_groth16_vkey;
+use crate::groth16_parser::{decode_groth16_vkey, parse_groth16_vkey};
 
 use crate::ContractError;
 use crate::{
@@ -17,20 +17,8 @@ pub struct VkeyParams {
 
 pub fn format_vkey(groth16_vkey: &Groth16VKeyType) -> Result<Groth16VkeyStr, ContractError> {
     // Create a process_vkeys struct from the process_vkey in the message
-    let groth16_vkey_formatted = Groth16VkeyStr {
-        alpha_1: hex::decode(&groth16_vkey.vk_alpha1)
-            .map_err(|_| ContractError::HexDecodingError {})?,
-        beta_2: hex::decode(&groth16_vkey.vk_beta_2)
-            .map_err(|_| ContractError::HexDecodingError {})?,
-        gamma_2: hex::decode(&groth16_vkey.vk_gamma_2)
-            .map_err(|_| ContractError::HexDecodingError {})?,
-        delta_2: hex::decode(&groth16_vkey.vk_delta_2)
-            .map_err(|_| ContractError::HexDecodingError {})?,
-        ic0: hex::decode(&groth16_vkey.vk_ic0).map_err(|_| ContractError::HexDecodingError {})?,
-        ic1: hex::decode(&groth16_vkey.vk_ic1).map_err(|_| ContractError::HexDecodingError {})?,
-    };
-    parse_groth16_vkey::<Bn256>(groth16_vkey_formatted.clone())
-        .map_err(|_| ContractError::InvalidVKeyError {})?;
+    let groth16_vkey_formatted = decode_groth16_vkey(groth16_vkey)?;
+    parse_groth16_vkey::<Bn256>(groth16_vkey_formatted.clone())?;
 
     Ok(groth16_vkey_formatted)
 }
@@ -2,9 +2,11 @@ use crate::circuit_params::match_vkeys;
 use crate::error::ContractError;
 use crate::groth16_parser::{parse_groth16_proof, parse_groth16_vkey};
 use crate::msg::{
-    DelayConfigResponse, ExecuteMsg, FeeConfigResponse, Groth16ProofType, InstantiateMsg,
-    InstantiationData, QueryMsg, RegistrationConfigInfo, RegistrationConfigUpdate,
-    RegistrationModeConfig, RegistrationStatus, TallyDelayInfo, VkeysResponse, WhitelistBaseConfig,
+    BatchRange, CanStartResponse, Commitments, ConfigResponse, DelayConfigResponse, ExecuteMsg,
+    FeeConfigResponse, Groth16ProofType, InstantiateMsg, InstantiationData, OperatorPerformance,
+    QueryMsg, RegistrationConfigInfo, RegistrationConfigUpdate, RegistrationModeConfig,
+    RegistrationStatus, TallyBatch, TallyDelayInfo, TallyWork, TreeZeros, VkeysResponse,
+    WhitelistBaseConfig,
 };
 use crate::state::{
     Admin, DelayConfig, DelayRecord, DelayRecords, DelayType, FeeConfig, Groth16ProofStr,
@@ -12,17 +14,18 @@ use crate::state::{
     QuinaryTreeRoot, RegistrationMode, RoundInfo, StateLeaf, VoiceCreditMode, VotingTime,
     Whitelist, WhitelistConfig, ADMIN, CERTSYSTEM, CIRCUITTYPE, COORDINATORHASH,
     CREATE_ROUND_WINDOW, CURRENT_DEACTIVATE_COMMITMENT, CURRENT_STATE_COMMITMENT,
-    CURRENT_TALLY_COMMITMENT, DEACTIVATE_ENABLED, DELAY_CONFIG, DELAY_RECORDS,
-    DMSG_CHAIN_LENGTH, DMSG_HASHES, DNODES, FEE_CONFIG, FEE_DENOM, FEE_RECIPIENT,
+    CURRENT_TALLY_COMMITMENT, DEACTIVATE_ENABLED, DELAY_CONFIG, DELAY_RECORDS, DMSG_CHAIN_LENGTH,
+    DMSG_HASHES, DNODES, FEE_CONFIG, FEE_DENOM, FEE_DENOM_CONFIG, FEE_RATE, FEE_RECIPIENT,
     FIRST_DMSG_TIMESTAMP, GROTH16_DEACTIVATE_VKEYS, GROTH16_NEWKEY_VKEYS, GROTH16_PROCESS_VKEYS,
-    GROTH16_TALLY_VKEYS, LEAF_IDX_0, MACIPARAMETERS, MACI_OPERATOR,
-    MAX_LEAVES_COUNT, MAX_VOTE_OPTIONS, MSG_CHAIN_LENGTH, MSG_HASHES, NODES, NULLIFIERS,
-    NUMSIGNUPS, ORACLE_WHITELIST, PENALTY_RATE, PERIOD, POLL_ID, PRE_DEACTIVATE_COORDINATOR_HASH,
+    GROTH16_TALLY_VKEYS, INSTANTIATION_DATA, LEAF_IDX_0, MACIPARAMETERS, MACI_OPERATOR,
+    MAX_LEAVES_COUNT, MAX_STATE_TREE_DEPTH, MAX_VOTE_OPTIONS, MIN_STATE_TREE_DEPTH,
+    MSG_CHAIN_LENGTH, MSG_HASHES, NODES, NULLIFIERS, NUMSIGNUPS, ORACLE_SIGNUP_COUNT,
+    ORACLE_WHITELIST, PENALTY_RATE, PERIOD, POLL_ID, PRE_DEACTIVATE_COORDINATOR_HASH,
     PRE_DEACTIVATE_ROOT, PROCESSED_DMSG_COUNT, PROCESSED_MSG_COUNT, PROCESSED_USER_COUNT, QTR_LIB,
-    REGISTRATION_MODE, RESULT, ROUNDINFO, SIGNUPED, STATE_ROOT_BY_DMSG,
-    TALLY_DELAY_MAX_HOURS, TALLY_DELAY_MULTIPLIER, TALLY_TIMEOUT, TALLY_TIMEOUT_EXTRA_SECONDS,
-    TOTAL_RESULT, USED_ENC_PUB_KEYS, VOICECREDITBALANCE, VOICE_CREDIT_AMOUNT, VOICE_CREDIT_MODE,
-    VOTEOPTIONMAP, VOTINGTIME, WHITELIST, ZEROS, ZEROS_H10,
+    REGISTRATION_MODE, RESULT, ROUNDINFO, SIGNUPED, SIGNUPED_BY_X, STATE_ROOT_BY_DMSG,
+    TALLY_DELAY_MAX_HOURS, TALLY_DELAY_MIN_HOURS, TALLY_DELAY_MULTIPLIER, TALLY_TIMEOUT,
+    TALLY_TIMEOUT_EXTRA_SECONDS, TOTAL_RESULT, USED_ENC_PUB_KEYS, VOICECREDITBALANCE,
+    VOICE_CREDIT_AMOUNT, VOICE_CREDIT_MODE, VOTEOPTIONMAP, VOTINGTIME, WHITELIST, ZEROS, ZEROS_H10,
 };
 use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
@@ -32,11 +35,13 @@ use cw2::set_contract_version;
 use pairing_ce::bn256::Bn256;
 
 use cosmwasm_std::{
-    attr, coins, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdResult, Timestamp, Uint128, Uint256,
+    attr, coin, coins, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Decimal,
+    Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult, Storage, Timestamp,
+    Uint128, Uint256,
 };
 use maci_utils::{
-    hash2, hash5, hash_256_uint256_list, is_on_babyjubjub_curve, uint256_from_hex_string,
+    address_to_uint256, hash2, hash5, hash_256_uint256_list, is_on_babyjubjub_curve, pack_vals,
+    uint256_from_hex_string,
 };
 
 use sha2::{Digest, Sha256};
@@ -66,6 +71,9 @@ const SNARK_SCALAR_FIELD_HEX: &str =
 /// This helper centralizes the conversion logic
 #[inline]
 fn uint256_to_field<F: Fr>(input: &Uint256) -> Result<F, ContractError> {
+    if *input >= uint256_from_hex_string(SNARK_SCALAR_FIELD_HEX) {
+        return Err(ContractError::FieldElementOutOfRange { value: *input });
+    }
     F::from_str(&input.to_string()).ok_or_else(|| ContractError::FieldConversionError {
         value: input.to_string(),
     })
@@ -102,32 +110,17 @@ fn run_groth16_verify(
     Ok(())
 }
 
-/// Convert a contract address to Uint256 format
-/// This function takes the address bytes and converts them to a Uint256
-fn address_to_uint256(address: &Addr) -> Uint256 {
-    let address_bytes = address.as_bytes();
-
-    // Use SHA256 hash to convert the address to a fixed-length 32-byte format
-    let mut hasher = Sha256::new();
-    hasher.update(address_bytes);
-    let hash_result = hasher.finalize();
-
-    // Convert the hash bytes to Uint256
-    let mut bytes = [0u8; 32];
-    bytes.copy_from_slice(&hash_result[..]);
-
-    // Convert bytes to Uint256 (big-endian)
-    let mut uint256_bytes = [0u8; 32];
-    for (i, &byte) in bytes.iter().enumerate() {
-        uint256_bytes[31 - i] = byte; // Reverse for little-endian to big-endian conversion
-    }
-
-    Uint256::from_be_bytes(uint256_bytes)
-}
-
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw-amaci";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+// Upper bound on entries returned by a single GetAllVoiceCreditBalances query
+const MAX_VOICE_CREDIT_BALANCES_QUERY: u128 = 100;
+// Upper bound on records returned by a single GetDelayRecordsPaginated query
+const MAX_DELAY_RECORDS_QUERY: u32 = 100;
+// Default minimum gap between voting start and end time
+const DEFAULT_CREATE_ROUND_WINDOW_SECS: u64 = 10 * 60;
+// Default share of the claimed balance sent to fee_recipient, in percent
+const DEFAULT_FEE_RATE: u64 = 10;
 
 /// Validate and process whitelist users into WhitelistConfig
 ///
@@ -226,6 +219,23 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    // Every proof is bound to the coordinator key, so reject an invalid one up front
+    if !is_on_babyjubjub_curve(msg.coordinator.x, msg.coordinator.y) {
+        return Err(ContractError::InvalidCoordinatorKey {});
+    }
+
+    // Every depth-derived index below relies on this range, so check it before any parsing
+    let state_tree_depth = msg.parameters.state_tree_depth;
+    if state_tree_depth < Uint256::from_u128(MIN_STATE_TREE_DEPTH)
+        || state_tree_depth > Uint256::from_u128(MAX_STATE_TREE_DEPTH)
+    {
+        return Err(ContractError::UnsupportedStateTreeDepth {
+            depth: state_tree_depth,
+            min: MIN_STATE_TREE_DEPTH,
+            max: MAX_STATE_TREE_DEPTH,
+        });
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // Create an admin with the sender address
     let admin = Admin {
@@ -261,7 +271,13 @@ pub fn instantiate(
         return Err(ContractError::WrongTimeSet {});
     }
 
-    let create_round_window = Timestamp::from_seconds(10 * 60); // 10 minutes
+    let create_round_window_secs = msg
+        .create_round_window
+        .unwrap_or(DEFAULT_CREATE_ROUND_WINDOW_SECS);
+    if create_round_window_secs == 0 {
+        return Err(ContractError::InvalidCreateRoundWindow {});
+    }
+    let create_round_window = Timestamp::from_seconds(create_round_window_secs);
     CREATE_ROUND_WINDOW.save(deps.storage, &create_round_window)?;
 
     // Ensure start and end are at least the create-round window apart.
@@ -448,10 +464,6 @@ pub fn instantiate(
     GROTH16_DEACTIVATE_VKEYS.save(deps.storage, &vkey.deactivate_vkey)?;
     GROTH16_NEWKEY_VKEYS.save(deps.storage, &vkey.add_key_vkey)?;
 
-    if !is_on_babyjubjub_curve(msg.coordinator.x, msg.coordinator.y) {
-        return Err(ContractError::InvalidPubKey {});
-    }
-
     // Compute the coordinator hash from the coordinator values in the message
     let coordinator_hash = hash2([msg.coordinator.x, msg.coordinator.y]);
     COORDINATORHASH.save(deps.storage, &coordinator_hash)?;
@@ -611,6 +623,7 @@ pub fn instantiate(
         &Uint256::from_u128(0u128),
     )?;
 
+    validate_vote_option_labels(&msg.vote_option_map)?;
     VOTEOPTIONMAP.save(deps.storage, &msg.vote_option_map)?;
     ROUNDINFO.save(deps.storage, &msg.round_info)?;
 
@@ -626,6 +639,7 @@ pub fn instantiate(
     MACI_OPERATOR.save(deps.storage, &msg.operator)?;
 
     FEE_RECIPIENT.save(deps.storage, &msg.fee_recipient)?;
+    FEE_RATE.save(deps.storage, &DEFAULT_FEE_RATE)?;
 
     // Deactivate is only compatible with Unified VC mode (see AddNewKey balance handling).
     validate_deactivate_vc_compatibility(msg.deactivate_enabled, &msg.voice_credit_mode)?;
@@ -633,6 +647,8 @@ pub fn instantiate(
     // Save deactivate_enabled flag (default: false)
     DEACTIVATE_ENABLED.save(deps.storage, &msg.deactivate_enabled)?;
 
+    // Only 1p1v and qv circuits exist; anything else (including 2) is rejected
+    // with the same error as the maci contract
     let circuit_type = if msg.circuit_type == Uint256::from_u128(0u128) {
         "0" // 1p1v
     } else if msg.circuit_type == Uint256::from_u128(1u128) {
@@ -658,6 +674,8 @@ pub fn instantiate(
 
     let deactivate_delay = Timestamp::from_seconds(msg.deactivate_delay);
 
+    let tally_delay_min_hours = 0; // no floor
+    TALLY_DELAY_MIN_HOURS.save(deps.storage, &tally_delay_min_hours)?;
     let tally_delay_max_hours = 48; // 48 hours
     TALLY_DELAY_MAX_HOURS.save(deps.storage, &tally_delay_max_hours)?;
 
@@ -673,6 +691,10 @@ pub fn instantiate(
             signup_fee: msg.signup_fee,
         },
     )?;
+    FEE_DENOM_CONFIG.save(
+        deps.storage,
+        &msg.fee_denom.unwrap_or_else(|| FEE_DENOM.to_string()),
+    )?;
     DELAY_CONFIG.save(
         deps.storage,
         &DelayConfig {
@@ -720,6 +742,10 @@ pub fn instantiate(
         attr("coordinator_pubkey_x", &msg.coordinator.x.to_string()),
         attr("coordinator_pubkey_y", &msg.coordinator.y.to_string()),
         attr("max_vote_options", &msg.vote_option_map.len().to_string()),
+        attr(
+            "vote_option_map_hash",
+            vote_option_map_hash(&msg.vote_option_map),
+        ),
         attr(
             "state_tree_depth",
             &msg.parameters.state_tree_depth.to_string(),
@@ -767,6 +793,8 @@ pub fn instantiate(
         attributes.push(attr("round_link", msg.round_info.link))
     }
 
+    INSTANTIATION_DATA.save(deps.storage, &data)?;
+
     Ok(Response::new()
         .add_attributes(attributes)
         .set_data(to_json_binary(&data)?))
@@ -786,9 +814,23 @@ pub fn execute(
         ExecuteMsg::UpdateRegistrationConfig { config } => {
             execute_update_registration_config(deps, env, info, config)
         }
+        ExecuteMsg::ExtendVotingTime { new_end_time } => {
+            execute_extend_voting_time(deps, env, info, new_end_time)
+        }
+        ExecuteMsg::SetFeeRate { fee_rate } => execute_set_fee_rate(deps, env, info, fee_rate),
+        ExecuteMsg::SetTallyDelayHours {
+            min_hours,
+            max_hours,
+        } => execute_set_tally_delay_hours(deps, env, info, min_hours, max_hours),
         ExecuteMsg::SetVoteOptionsMap { vote_option_map } => {
             execute_set_vote_options_map(deps, env, info, vote_option_map)
         }
+        ExecuteMsg::RemoveFromWhitelist { addr } => {
+            execute_remove_from_whitelist(deps, env, info, addr)
+        }
+        ExecuteMsg::SetOracleWhitelistPubkey { pubkey } => {
+            execute_set_oracle_whitelist_pubkey(deps, env, info, pubkey)
+        }
         // ExecuteMsg::StartVotingPeriod {} => execute_start_voting_period(deps, env, info),
         ExecuteMsg::SignUp {
             pubkey,
@@ -846,7 +888,7 @@ pub fn execute(
         ExecuteMsg::StopTallyingPeriod { results, salt } => {
             execute_stop_tallying_period(deps, env, info, results, salt)
         }
-        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::Claim { reward_recipient } => execute_claim(deps, env, info, reward_recipient),
     }
 }
 
@@ -885,6 +927,107 @@ pub fn execute_set_round_info(
     }
 }
 
+// Move the voting end time before the round starts, keeping the same
+// start/end gap that instantiate requires
+pub fn execute_extend_voting_time(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_end_time: Timestamp,
+) -> Result<Response, ContractError> {
+    let mut voting_time = VOTINGTIME.load(deps.storage)?;
+    if env.block.time >= voting_time.start_time {
+        return Err(ContractError::PeriodError {});
+    }
+
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Extending only: the round can never be shortened this way
+    if new_end_time <= voting_time.end_time {
+        return Err(ContractError::WrongTimeSet {});
+    }
+
+    let create_round_window = CREATE_ROUND_WINDOW.load(deps.storage)?;
+    if voting_time
+        .start_time
+        .plus_seconds(create_round_window.seconds())
+        >= new_end_time
+    {
+        return Err(ContractError::WrongTimeSet {});
+    }
+
+    let old_end_time = voting_time.end_time;
+    voting_time.end_time = new_end_time;
+    VOTINGTIME.save(deps.storage, &voting_time)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "extend_voting_time")
+        .add_attribute("old_end_time", old_end_time.nanos().to_string())
+        .add_attribute("new_end_time", new_end_time.nanos().to_string()))
+}
+
+// Change the fee_recipient share of the claim; locked once voting starts so the
+// operator knows the split they signed up for
+pub fn execute_set_fee_rate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    fee_rate: u64,
+) -> Result<Response, ContractError> {
+    let voting_time = VOTINGTIME.load(deps.storage)?;
+    if env.block.time >= voting_time.start_time {
+        return Err(ContractError::PeriodError {});
+    }
+
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if fee_rate > 100 {
+        return Err(ContractError::InvalidFeeRate { fee_rate });
+    }
+
+    FEE_RATE.save(deps.storage, &fee_rate)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_fee_rate")
+        .add_attribute("fee_rate", fee_rate.to_string()))
+}
+
+pub fn execute_set_tally_delay_hours(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    min_hours: u64,
+    max_hours: u64,
+) -> Result<Response, ContractError> {
+    let voting_time = VOTINGTIME.load(deps.storage)?;
+    if env.block.time >= voting_time.start_time {
+        return Err(ContractError::PeriodError {});
+    }
+
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if min_hours > max_hours {
+        return Err(ContractError::InvalidTallyDelayHours {
+            min_hours,
+            max_hours,
+        });
+    }
+
+    TALLY_DELAY_MIN_HOURS.save(deps.storage, &min_hours)?;
+    TALLY_DELAY_MAX_HOURS.save(deps.storage, &max_hours)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_tally_delay_hours")
+        .add_attribute("min_hours", min_hours.to_string())
+        .add_attribute("max_hours", max_hours.to_string()))
+}
+
 // Helper function to validate registration config update
 fn validate_registration_config_update(
     deps: &DepsMut,
@@ -1156,6 +1299,110 @@ pub fn execute_update_registration_config(
     Ok(Response::new().add_attributes(attributes))
 }
 
+/// Hex SHA256 of the vote option labels, emitted so clients can detect option
+/// map changes without diffing the full list. Each label is prefixed with its
+/// byte length (u32 big-endian) so `["ab", "c"]` and `["a", "bc"]` differ.
+pub fn vote_option_map_hash(vote_option_map: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for label in vote_option_map {
+        hasher.update((label.len() as u32).to_be_bytes());
+        hasher.update(label.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+// Labels are shown next to the tally results by index, so an empty or
+// repeated label makes the results ambiguous.
+fn validate_vote_option_labels(vote_option_map: &[String]) -> Result<(), ContractError> {
+    for (i, label) in vote_option_map.iter().enumerate() {
+        if label.trim().is_empty() {
+            return Err(ContractError::InvalidVoteOptions {
+                reason: format!("option {} is empty", i),
+            });
+        }
+        if vote_option_map[..i].contains(label) {
+            return Err(ContractError::InvalidVoteOptions {
+                reason: format!("option {} duplicates \"{}\"", i, label),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_remove_from_whitelist(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    addr: Addr,
+) -> Result<Response, ContractError> {
+    let voting_time = VOTINGTIME.load(deps.storage)?;
+    if env.block.time >= voting_time.start_time {
+        return Err(ContractError::PeriodError {});
+    }
+
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut whitelist = WHITELIST
+        .may_load(deps.storage)?
+        .unwrap_or(Whitelist { users: vec![] });
+    if !whitelist.remove(&addr) {
+        return Err(ContractError::NotInWhitelist {
+            addr: addr.to_string(),
+        });
+    }
+    WHITELIST.save(deps.storage, &whitelist)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_from_whitelist")
+        .add_attribute("addr", addr.to_string()))
+}
+
+pub fn execute_set_oracle_whitelist_pubkey(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pubkey: String,
+) -> Result<Response, ContractError> {
+    let voting_time = VOTINGTIME.load(deps.storage)?;
+    if env.block.time >= voting_time.start_time {
+        return Err(ContractError::PeriodError {});
+    }
+
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !matches!(
+        REGISTRATION_MODE.load(deps.storage)?,
+        RegistrationMode::SignUpWithOracle { .. }
+    ) {
+        return Err(ContractError::OracleWhitelistNotConfigured {});
+    }
+
+    validate_oracle_pubkey(&pubkey)?;
+    REGISTRATION_MODE.save(
+        deps.storage,
+        &RegistrationMode::SignUpWithOracle {
+            oracle_pubkey: pubkey.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_oracle_whitelist_pubkey")
+        .add_attribute("oracle_pubkey", pubkey))
+}
+
+/// Accepts a base64 SEC1 secp256k1 key, compressed (33 bytes) or uncompressed (65 bytes)
+fn validate_oracle_pubkey(pubkey: &str) -> Result<(), ContractError> {
+    let bytes = Binary::from_base64(pubkey).map_err(|_| ContractError::InvalidBase64 {})?;
+    match (bytes.len(), bytes.first()) {
+        (33, Some(0x02 | 0x03)) | (65, Some(0x04)) => Ok(()),
+        _ => Err(ContractError::InvalidOraclePubkey {}),
+    }
+}
+
 // in pending
 pub fn execute_set_vote_options_map(
     deps: DepsMut,
@@ -1194,12 +1441,18 @@ pub fn execute_set_vote_options_map(
             });
         }
 
+        validate_vote_option_labels(&vote_option_map)?;
+
         VOTEOPTIONMAP.save(deps.storage, &vote_option_map)?;
         // Save the maximum vote options
         MAX_VOTE_OPTIONS.save(deps.storage, &Uint256::from_u128(max_vote_options))?;
         let res = Response::new()
             .add_attribute("action", "set_vote_option")
             .add_attribute("vote_option_map", to_json_or(&vote_option_map, "[]"))
+            .add_attribute(
+                "vote_option_map_hash",
+                vote_option_map_hash(&vote_option_map),
+            )
             .add_attribute("max_vote_options", max_vote_options.to_string());
         Ok(res)
     }
@@ -1314,7 +1567,7 @@ pub fn execute_sign_up(
     // Fee stays in contract balance and is distributed at Claim time.
     // ============================================
     let signup_fee = FEE_CONFIG.load(deps.storage)?.signup_fee;
-    let signup_payment = check_fee_payment(&info, signup_fee)?;
+    let signup_payment = check_fee_payment(deps.storage, &info, signup_fee)?;
 
     // ============================================
     // Step 2: Calculate Voice Credit Balance
@@ -1388,6 +1641,7 @@ pub fn execute_sign_up(
     )?;
     NUMSIGNUPS.save(deps.storage, &num_sign_ups)?;
     SIGNUPED.save(deps.storage, &pubkey_key(&pubkey), &state_index)?;
+    SIGNUPED_BY_X.save(deps.storage, pubkey.x.to_be_bytes().to_vec(), &state_index)?;
 
     // ============================================
     // Step 4: Update Registration State
@@ -1406,6 +1660,10 @@ pub fn execute_sign_up(
                 is_register: true,
             };
             ORACLE_WHITELIST.save(deps.storage, &pubkey_key(&pubkey), &oracle_user)?;
+            let oracle_signup_count = ORACLE_SIGNUP_COUNT
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+            ORACLE_SIGNUP_COUNT.save(deps.storage, &(oracle_signup_count + Uint256::one()))?;
         }
         RegistrationMode::PrePopulated { .. } => {
             // Already handled above, this branch should not be reached
@@ -1415,12 +1673,10 @@ pub fn execute_sign_up(
 
     Ok(Response::new()
         .add_attribute("action", "sign_up")
-        .add_attribute("fee_paid", format!("{}{}", signup_payment, FEE_DENOM))
+        .add_attribute("action_detail", registration_mode.variant_name())
+        .add_attribute("fee_paid", signup_payment.to_string())
         .add_attribute("state_idx", state_index.to_string())
-        .add_attribute(
-            "pubkey",
-            format!("{:?},{:?}", pubkey.x.to_string(), pubkey.y.to_string()),
-        )
+        .add_attributes(pubkey_attributes("pubkey", &pubkey))
         .add_attribute("balance", voice_credit_balance.to_string())
         .add_attribute("registration_mode", format!("{:?}", registration_mode))
         .add_attribute("vc_mode", format!("{:?}", vc_mode)))
@@ -1450,7 +1706,7 @@ pub fn execute_publish_message(
         .checked_mul(Uint128::from(batch_size as u128))
         .map_err(|_| ContractError::ValueTooLarge {})?;
 
-    let payment = check_fee_payment(&info, required_fee)?;
+    let payment = check_fee_payment(deps.storage, &info, required_fee)?;
 
     let start_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
 
@@ -1458,7 +1714,7 @@ pub fn execute_publish_message(
         attr("action", "publish_message"),
         attr("batch_size", batch_size.to_string()),
         attr("start_chain_length", start_chain_length.to_string()),
-        attr("fee_paid", format!("{}{}", payment, FEE_DENOM)),
+        attr("fee_paid", payment.to_string()),
     ];
 
     let mut msg_chain_length = start_chain_length;
@@ -1492,13 +1748,9 @@ pub fn execute_publish_message(
             format!("msg_{}_data", i),
             to_json_or(&message.data, "[]"),
         ));
-        attributes.push(attr(
-            format!("msg_{}_enc_pub_key", i),
-            format!(
-                "{:?},{:?}",
-                enc_pub_key.x.to_string(),
-                enc_pub_key.y.to_string()
-            ),
+        attributes.extend(pubkey_attributes(
+            &format!("msg_{}_enc_pub_key", i),
+            enc_pub_key,
         ));
 
         msg_chain_length += Uint256::from_u128(1u128);
@@ -1529,9 +1781,9 @@ pub fn execute_publish_deactivate_message(
         return Err(ContractError::InvalidEncPubKey {});
     }
 
-    // Check payment: require DEACTIVATE_FEE in FEE_DENOM
+    // Check payment: require DEACTIVATE_FEE in the round's fee denom
     let deactivate_fee = FEE_CONFIG.load(deps.storage)?.deactivate_fee;
-    let payment = check_fee_payment(&info, deactivate_fee)?;
+    let payment = check_fee_payment(deps.storage, &info, deactivate_fee)?;
 
     let mut dmsg_chain_length = DMSG_CHAIN_LENGTH.load(deps.storage)?;
 
@@ -1596,15 +1848,8 @@ pub fn execute_publish_deactivate_message(
         .add_attribute("dmsg_chain_length", old_chain_length.to_string())
         .add_attribute("num_sign_ups", num_sign_ups.to_string())
         .add_attribute("message", to_json_or(&message.data, "[]"))
-        .add_attribute(
-            "enc_pub_key",
-            format!(
-                "{:?},{:?}",
-                enc_pub_key.x.to_string(),
-                enc_pub_key.y.to_string()
-            ),
-        )
-        .add_attribute("fee_paid", format!("{}{}", payment, FEE_DENOM)))
+        .add_attributes(pubkey_attributes("enc_pub_key", &enc_pub_key))
+        .add_attribute("fee_paid", payment.to_string()))
 }
 
 pub fn execute_upload_deactivate_message(
@@ -1857,6 +2102,7 @@ fn add_key_internal(
     num_sign_ups += Uint256::from_u128(1u128);
     NUMSIGNUPS.save(deps.storage, &num_sign_ups)?;
     SIGNUPED.save(deps.storage, &pubkey_key(&pubkey), &state_index)?;
+    SIGNUPED_BY_X.save(deps.storage, pubkey.x.to_be_bytes().to_vec(), &state_index)?;
     // Keep the GetVoiceCreditBalance query view consistent with the state leaf.
     VOICECREDITBALANCE.save(
         deps.storage,
@@ -1872,13 +2118,12 @@ fn add_key_internal(
     let mut resp = Response::new()
         .add_attribute("action", action)
         .add_attribute("state_idx", state_index.to_string())
-        .add_attribute(
-            "pubkey",
-            format!("{:?},{:?}", pubkey.x.to_string(), pubkey.y.to_string()),
-        )
+        .add_attributes(pubkey_attributes("pubkey", &pubkey))
         .add_attribute("balance", voice_credit_amount.to_string());
     if !is_pre_populated {
+        // input[0] is DNODES[0], the deactivate tree root the proof was verified against
         resp = resp
+            .add_attribute("new_deactivate_root", input[0].to_string())
             .add_attribute("d0", d[0].to_string())
             .add_attribute("d1", d[1].to_string())
             .add_attribute("d2", d[2].to_string())
@@ -1899,9 +2144,9 @@ pub fn execute_add_new_key(
 ) -> Result<Response, ContractError> {
     // Fee stays in contract balance and is distributed at Claim time.
     let signup_fee = FEE_CONFIG.load(deps.storage)?.signup_fee;
-    let payment = check_fee_payment(&info, signup_fee)?;
+    let payment = check_fee_payment(deps.storage, &info, signup_fee)?;
     let resp = add_key_internal(deps, env, pubkey, nullifier, d, groth16_proof, false)?;
-    Ok(resp.add_attribute("fee_paid", format!("{}{}", payment, FEE_DENOM)))
+    Ok(resp.add_attribute("fee_paid", payment.to_string()))
 }
 
 // in voting — only allowed in PrePopulated registration mode
@@ -1920,9 +2165,9 @@ pub fn execute_pre_add_new_key(
     }
     // Fee stays in contract balance and is distributed at Claim time.
     let signup_fee = FEE_CONFIG.load(deps.storage)?.signup_fee;
-    let payment = check_fee_payment(&info, signup_fee)?;
+    let payment = check_fee_payment(deps.storage, &info, signup_fee)?;
     let resp = add_key_internal(deps, env, pubkey, nullifier, d, groth16_proof, true)?;
-    Ok(resp.add_attribute("fee_paid", format!("{}{}", payment, FEE_DENOM)))
+    Ok(resp.add_attribute("fee_paid", payment.to_string()))
 }
 
 pub fn execute_start_process_period(
@@ -1969,6 +2214,24 @@ pub fn execute_start_process_period(
     Ok(Response::new().add_attribute("action", "start_process_period"))
 }
 
+/// Message range the next ProcessMessage covers. Messages are processed
+/// newest-first, so the batch is the aligned one holding the last unprocessed
+/// message.
+pub fn next_process_batch(deps: Deps) -> Result<BatchRange, ContractError> {
+    let processed_msg_count = PROCESSED_MSG_COUNT.load(deps.storage)?;
+    let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
+    // Check that all messages have not been processed yet
+    if processed_msg_count >= msg_chain_length {
+        return Err(ContractError::AllMessagesProcessed {});
+    }
+
+    let batch_size = MACIPARAMETERS.load(deps.storage)?.message_batch_size;
+    let start = (msg_chain_length - processed_msg_count - Uint256::from_u128(1u128)) / batch_size
+        * batch_size;
+    let end = (start + batch_size).min(msg_chain_length);
+    Ok(BatchRange { start, end })
+}
+
 pub fn execute_process_message(
     deps: DepsMut,
     _env: Env,
@@ -1978,11 +2241,11 @@ pub fn execute_process_message(
 ) -> Result<Response, ContractError> {
     require_period_status(deps.as_ref(), PeriodStatus::Processing)?;
     let mut processed_msg_count = PROCESSED_MSG_COUNT.load(deps.storage)?;
-    let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
-    // Check that all messages have not been processed yet
-    if processed_msg_count >= msg_chain_length {
-        return Err(ContractError::AllMessagesProcessed {});
-    }
+    // Compute the start and end indices of the current batch
+    let BatchRange {
+        start: batch_start_index,
+        end: batch_end_index,
+    } = next_process_batch(deps.as_ref())?;
 
     // Create an array to store the input values for the SNARK proof
     let mut input: [Uint256; 8] = [Uint256::zero(); 8];
@@ -1990,35 +2253,14 @@ pub fn execute_process_message(
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
     let max_vote_options = MAX_VOTE_OPTIONS.load(deps.storage)?;
 
+    // circuit_type is 0 (1p1v) or 1 (qv), enforced at instantiation
     let circuit_type = CIRCUITTYPE.load(deps.storage)?;
-    if circuit_type == Uint256::from_u128(0u128) {
-        // 1p1v
-        input[0] = (num_sign_ups << 32) + max_vote_options; // packedVals
-    } else if circuit_type == Uint256::from_u128(1u128) {
-        // qv
-        input[0] = (num_sign_ups << 32) + (circuit_type << 64) + max_vote_options;
-        // packedVals
-    }
-
-    // input[0] = (num_sign_ups << 32) + max_vote_options; // packedVals
+    input[0] = pack_vals(num_sign_ups, circuit_type, max_vote_options); // packedVals
 
     // Load the coordinator's public key hash
     let coordinator_hash = COORDINATORHASH.load(deps.storage)?;
     input[1] = coordinator_hash; // coordPubKeyHash
 
-    // Load the MACI parameters
-    let parameters = MACIPARAMETERS.load(deps.storage)?;
-    let batch_size = parameters.message_batch_size;
-
-    // Compute the start and end indices of the current batch
-    let batch_start_index = (msg_chain_length - processed_msg_count - Uint256::from_u128(1u128))
-        / batch_size
-        * batch_size;
-    let mut batch_end_index = batch_start_index.clone() + batch_size;
-    if batch_end_index > msg_chain_length {
-        batch_end_index = msg_chain_length;
-    }
-
     // Load the hash of the message at the batch start index
     input[2] = MSG_HASHES.load(
         deps.storage,
@@ -2098,18 +2340,13 @@ pub fn execute_stop_processing_period(
         .add_attribute("period", "Tallying"))
 }
 
-pub fn execute_process_tally(
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    new_tally_commitment: Uint256,
-    groth16_proof: Groth16ProofType,
-) -> Result<Response, ContractError> {
-    require_period_status(deps.as_ref(), PeriodStatus::Tallying)?;
-
-    let mut processed_user_count = PROCESSED_USER_COUNT.load(deps.storage)?;
+/// Batch the next ProcessTally covers
+pub fn next_tally_batch(deps: Deps) -> Result<TallyBatch, ContractError> {
+    let processed_user_count = PROCESSED_USER_COUNT.load(deps.storage)?;
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
-    // Check that all users have not been processed yet
+    // Check that all users have not been processed yet. A round with no signups
+    // lands here too (0 >= 0): there is nothing to tally, so ProcessTally is
+    // skipped and StopTallying finalizes with all-zero results.
     if processed_user_count >= num_sign_ups {
         return Err(ContractError::AllUsersProcessed {});
     }
@@ -2129,6 +2366,36 @@ pub fn execute_process_tally(
     // Calculate the batch number
     let batch_num = processed_user_count / batch_size;
 
+    Ok(TallyBatch {
+        batch_num,
+        batch_size,
+    })
+}
+
+pub fn execute_process_tally(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    new_tally_commitment: Uint256,
+    groth16_proof: Groth16ProofType,
+) -> Result<Response, ContractError> {
+    require_period_status(deps.as_ref(), PeriodStatus::Tallying)?;
+
+    let mut processed_user_count = PROCESSED_USER_COUNT.load(deps.storage)?;
+    let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
+    // StopProcessingPeriod already enforces this before Tallying; re-check so a
+    // tally can never chain from a state commitment that skipped messages
+    if num_sign_ups != Uint256::zero()
+        && PROCESSED_MSG_COUNT.load(deps.storage)? != MSG_CHAIN_LENGTH.load(deps.storage)?
+    {
+        return Err(ContractError::MessagesNotFullyProcessed {});
+    }
+
+    let TallyBatch {
+        batch_num,
+        batch_size,
+    } = next_tally_batch(deps.as_ref())?;
+
     // Create an array to store the input values for the SNARK proof
     let mut input: [Uint256; 4] = [Uint256::zero(); 4];
 
@@ -2179,20 +2446,10 @@ fn execute_stop_tallying_period(
 ) -> Result<Response, ContractError> {
     require_period_status(deps.as_ref(), PeriodStatus::Tallying)?;
 
-    // Get the final signup count and message count
-    let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
-    let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
-
-    // Calculate total workload (signup and message have same weight)
-    let total_work = num_sign_ups + msg_chain_length;
-
-    let total_work_u128 = total_work
-        .try_into() // Uint256 -> Uint128
-        .map(|x: Uint128| x.u128()) // Uint128 -> u128
-        .map_err(|_| ContractError::ValueTooLarge {})?;
-
-    // Calculate actual delay timeout (linear change between min hours to max hours)
+    // Calculate actual delay timeout (linear change between min hours to max hours) and the
+    // total workload it is based on (signup and message have same weight)
     let actual_delay: TallyDelayInfo = calculate_tally_delay(deps.as_ref())?;
+    let total_work_u128 = actual_delay.total_work;
     let voting_time = VOTINGTIME.load(deps.storage)?;
     let current_time = env.block.time;
     let different_time = current_time
@@ -2238,27 +2495,14 @@ fn execute_stop_tallying_period(
 
     let processed_user_count = PROCESSED_USER_COUNT.load(deps.storage)?;
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
-    let max_vote_options = MAX_VOTE_OPTIONS.load(deps.storage)?;
 
     // Check that all users have been processed
     if processed_user_count < num_sign_ups {
         return Err(ContractError::NotAllUsersProcessed {});
     }
 
-    // Check that the number of results is not greater than the maximum vote options
-    if Uint256::from_u128(results.len() as u128) > max_vote_options {
-        return Err(ContractError::MaxVoteOptionsExceeded {
-            current: Uint256::from_u128(results.len() as u128),
-            max_allowed: max_vote_options,
-        });
-    }
-
-    // Load the QTR library and MACI parameters
-    let qtr_lib = QTR_LIB.load(deps.storage)?;
-    let parameters = MACIPARAMETERS.load(deps.storage)?;
-
     // Calculate the results root
-    let results_root = qtr_lib.root_of(parameters.vote_option_tree_depth, results.clone());
+    let results_root = compute_results_root(deps.as_ref(), &results)?;
 
     // Calculate the tally commitment
     let tally_commitment = hash2([results_root, salt]);
@@ -2314,15 +2558,29 @@ fn execute_stop_tallying_period(
         .add_attributes(attributes))
 }
 
-fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response, ContractError> {
+fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
     let period = PERIOD.load(deps.storage)?;
-    let voting_time: VotingTime = VOTINGTIME.load(deps.storage)?;
     let current_time = env.block.time;
     let admin = ADMIN.load(deps.storage)?.admin;
     let operator = MACI_OPERATOR.load(deps.storage)?;
+    // Anyone may trigger Claim, but only the operator can redirect its own reward
+    let reward_recipient = match reward_recipient {
+        Some(recipient) => {
+            if info.sender != operator {
+                return Err(ContractError::Unauthorized {});
+            }
+            deps.api.addr_validate(recipient.as_str())?
+        }
+        None => operator,
+    };
     let fee_recipient = FEE_RECIPIENT.load(deps.storage)?;
 
-    let denom = "peaka".to_string();
+    let denom = load_fee_denom(deps.storage)?;
     let contract_address = env.contract.address.clone();
     let contract_balance = deps.querier.query_balance(contract_address, &denom)?;
     let contract_balance_amount = contract_balance.amount.u128();
@@ -2331,11 +2589,8 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
         return Err(ContractError::AllFundsClaimed {});
     }
 
-    // Compute dynamic timeout: delay_allowed + 2 days
-    let actual_delay = calculate_tally_delay(deps.as_ref())?;
-    let tally_timeout_secs = actual_delay.delay_seconds + TALLY_TIMEOUT_EXTRA_SECONDS;
     // If exceeding the timeout, return all funds to admin
-    if current_time > voting_time.end_time.plus_seconds(tally_timeout_secs) {
+    if is_tally_timed_out(deps.as_ref(), current_time)? {
         let message = BankMsg::Send {
             to_address: admin.to_string(),
             amount: coins(contract_balance_amount, denom),
@@ -2359,8 +2614,9 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
         return Err(ContractError::PeriodError {});
     }
 
-    // First allocate 10% to fee_recipient
-    let fee_rate = Decimal::from_ratio(1u128, 10u128); // 10%
+    // First allocate the fee rate (10% unless changed) to fee_recipient
+    let fee_rate = FEE_RATE.may_load(deps.storage)?.unwrap_or(DEFAULT_FEE_RATE);
+    let fee_rate = Decimal::percent(fee_rate);
     let fee_amount = Uint128::from(contract_balance_amount) * fee_rate;
     let remaining_amount = Uint128::from(contract_balance_amount) - fee_amount;
 
@@ -2376,7 +2632,7 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
 
     let mut messages: Vec<CosmosMsg> = vec![];
 
-    // Send 10% to fee_recipient
+    // Send the fee share to fee_recipient
     if !fee_amount.is_zero() {
         messages.push(CosmosMsg::Bank(BankMsg::Send {
             to_address: fee_recipient.to_string(),
@@ -2397,7 +2653,7 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
         }));
     }
 
-    // Send remaining reward to operator
+    // Send remaining reward to operator (or its chosen reward recipient)
     let operator_reward_u128_amount = operator_reward
         .try_into()
         .map(|x: Uint128| x.u128())
@@ -2405,7 +2661,7 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
 
     if !operator_reward.is_zero() {
         messages.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: operator.to_string(),
+            to_address: reward_recipient.to_string(),
             amount: coins(operator_reward_u128_amount, denom.clone()),
         }));
     }
@@ -2416,6 +2672,7 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
         .add_attribute("is_ended", "true")
         .add_attribute("fee_to_recipient", fee_amount.to_string())
         .add_attribute("operator_reward", operator_reward_u128_amount.to_string())
+        .add_attribute("reward_recipient", reward_recipient.to_string())
         .add_attribute("penalty_amount", penalty_u128_amount.to_string())
         .add_attribute("miss_rate", performance.miss_rate.to_string())
         .add_attribute("is_tally_timeout", "false"))
@@ -2582,7 +2839,7 @@ fn is_operator(deps: Deps, sender: &str) -> StdResult<bool> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Admin {} => to_json_binary(&ADMIN.load(deps.storage)?.admin),
         QueryMsg::Operator {} => to_json_binary(&MACI_OPERATOR.load(deps.storage)?),
@@ -2605,11 +2862,24 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 .may_load(deps.storage)?
                 .unwrap_or_default(),
         ),
+        QueryMsg::GetFirstDmsgTimestamp {} => {
+            to_json_binary::<Option<Timestamp>>(&FIRST_DMSG_TIMESTAMP.may_load(deps.storage)?)
+        }
         QueryMsg::GetProcessedMsgCount {} => to_json_binary::<Uint256>(
             &PROCESSED_MSG_COUNT
                 .may_load(deps.storage)?
                 .unwrap_or_default(),
         ),
+        QueryMsg::GetNextProcessBatch {} => {
+            let batch = next_process_batch(deps)
+                .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+            to_json_binary(&batch)
+        }
+        QueryMsg::GetNextTallyBatch {} => {
+            let batch = next_tally_batch(deps)
+                .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+            to_json_binary(&batch)
+        }
         QueryMsg::GetProcessedUserCount {} => to_json_binary::<Uint256>(
             &PROCESSED_USER_COUNT
                 .may_load(deps.storage)?
@@ -2624,6 +2894,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 .unwrap_or_default();
             to_json_binary::<Uint256>(&node)
         }
+        QueryMsg::GetDeactivateNode { index } => {
+            let node = DNODES
+                .may_load(deps.storage, index.to_be_bytes().to_vec())?
+                .unwrap_or_default();
+            to_json_binary::<Uint256>(&node)
+        }
         QueryMsg::GetResult { index } => to_json_binary::<Uint256>(
             &RESULT
                 .may_load(deps.storage, index.to_be_bytes().to_vec())?
@@ -2656,6 +2932,26 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 .may_load(deps.storage, index.to_be_bytes().to_vec())?
                 .unwrap_or_default(),
         ),
+        QueryMsg::GetAllVoiceCreditBalances { start, end } => {
+            let num_signups = NUMSIGNUPS.may_load(deps.storage)?.unwrap_or_default();
+
+            // Convert Uint256 -> Uint128 -> u128 safely
+            let to_u128 = |x: Uint256| x.try_into().map(|x: Uint128| x.u128()).unwrap_or(u128::MAX);
+            let start = to_u128(start);
+            let end = to_u128(end)
+                .min(to_u128(num_signups))
+                .min(start.saturating_add(MAX_VOICE_CREDIT_BALANCES_QUERY));
+
+            let mut balances: Vec<Uint256> = Vec::new();
+            for i in start..end {
+                let balance = VOICECREDITBALANCE
+                    .may_load(deps.storage, Uint256::from_u128(i).to_be_bytes().to_vec())?
+                    .unwrap_or_default();
+                balances.push(balance);
+            }
+
+            to_json_binary::<Vec<Uint256>>(&balances)
+        }
         QueryMsg::GetVoiceCreditAmount {} => to_json_binary::<Uint256>(
             &VOICE_CREDIT_AMOUNT
                 .may_load(deps.storage)?
@@ -2665,9 +2961,23 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let state_idx = SIGNUPED.may_load(deps.storage, &pubkey_key(&pubkey))?;
             to_json_binary(&state_idx)
         }
+        QueryMsg::SignupedByX { pubkey_x } => {
+            let state_idx =
+                SIGNUPED_BY_X.may_load(deps.storage, pubkey_x.to_be_bytes().to_vec())?;
+            to_json_binary(&state_idx)
+        }
         QueryMsg::VoteOptionMap {} => {
             to_json_binary::<Vec<String>>(&VOTEOPTIONMAP.load(deps.storage)?)
         }
+        QueryMsg::GetVoteOptionsIndexed {} => {
+            let vote_options: Vec<(u32, String)> = VOTEOPTIONMAP
+                .load(deps.storage)?
+                .into_iter()
+                .enumerate()
+                .map(|(index, label)| (index as u32, label))
+                .collect();
+            to_json_binary(&vote_options)
+        }
         QueryMsg::MaxVoteOptions {} => {
             to_json_binary::<Uint256>(&MAX_VOTE_OPTIONS.may_load(deps.storage)?.unwrap_or_default())
         }
@@ -2692,11 +3002,43 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 .unwrap_or(DelayRecords { records: vec![] });
             to_json_binary(&records)
         }
+        QueryMsg::GetDelayRecordsPaginated { start, limit } => {
+            // Records are still stored as a single item, so this bounds the response
+            // size but not the cost of loading it
+            let limit = limit.min(MAX_DELAY_RECORDS_QUERY);
+            let records = DELAY_RECORDS
+                .may_load(deps.storage)?
+                .map(|delay_records| delay_records.records)
+                .unwrap_or_default()
+                .into_iter()
+                .skip(start as usize)
+                .take(limit as usize)
+                .collect();
+            to_json_binary(&DelayRecords { records })
+        }
+        QueryMsg::IsTallyTimedOut {} => {
+            let timed_out = is_tally_timed_out(deps, env.block.time)
+                .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+            to_json_binary(&timed_out)
+        }
         QueryMsg::GetTallyDelay {} => {
             let delay_info = calculate_tally_delay(deps)
                 .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
             to_json_binary(&delay_info)
         }
+        QueryMsg::GetEstimatedTallyWork {} => {
+            let delay_info = calculate_tally_delay(deps)
+                .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+            to_json_binary(&TallyWork {
+                total_work: delay_info.total_work,
+                allowed_delay_seconds: delay_info.delay_seconds,
+            })
+        }
+        QueryMsg::ComputeResultsRoot { results } => {
+            let results_root = compute_results_root(deps, &results)
+                .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+            to_json_binary(&results_root)
+        }
         QueryMsg::QueryOracleWhitelistConfig {} => {
             // Compatible: return oracle pubkey from registration mode (same Option<String> as before)
             let pubkey = get_oracle_pubkey(deps)?;
@@ -2742,6 +3084,28 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
             to_json_binary(&config_info)
         }
+        QueryMsg::WhitelistStatus { addrs } => {
+            let whitelist = WHITELIST.may_load(deps.storage)?;
+            let statuses: Vec<(Addr, bool, bool)> = addrs
+                .into_iter()
+                .map(|addr| {
+                    let (is_whitelist, is_register) =
+                        whitelist.as_ref().map_or((false, false), |w| {
+                            (w.is_whitelist(&addr), w.is_register(&addr))
+                        });
+                    (addr, is_whitelist, is_register)
+                })
+                .collect();
+            to_json_binary(&statuses)
+        }
+        QueryMsg::GetInstantiationData {} => {
+            to_json_binary(&INSTANTIATION_DATA.load(deps.storage)?)
+        }
+        QueryMsg::GetOracleSignupCount {} => to_json_binary(
+            &ORACLE_SIGNUP_COUNT
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        ),
         QueryMsg::QueryRegistrationStatus {
             sender,
             pubkey,
@@ -2827,6 +3191,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 message_fee: fee_cfg.message_fee,
                 deactivate_fee: fee_cfg.deactivate_fee,
                 signup_fee: fee_cfg.signup_fee,
+                fee_denom: load_fee_denom(deps.storage)?,
             };
             to_json_binary(&config)
         }
@@ -2849,11 +3214,103 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             };
             to_json_binary(&vkeys)
         }
+        QueryMsg::GetTreeZeros {} => {
+            let tree_zeros = TreeZeros {
+                zeros: ZEROS.load(deps.storage)?.to_vec(),
+                zeros_h10: ZEROS_H10.load(deps.storage)?.to_vec(),
+            };
+            to_json_binary(&tree_zeros)
+        }
+        QueryMsg::GetConfig {} => {
+            let config = ConfigResponse {
+                parameters: MACIPARAMETERS.load(deps.storage)?,
+                circuit_type: CIRCUITTYPE.may_load(deps.storage)?.unwrap_or_default(),
+                certification_system: CERTSYSTEM.may_load(deps.storage)?.unwrap_or_default(),
+                voice_credit_amount: VOICE_CREDIT_AMOUNT
+                    .may_load(deps.storage)?
+                    .unwrap_or_default(),
+                voting_time: VOTINGTIME.load(deps.storage)?,
+                max_vote_options: MAX_VOTE_OPTIONS.may_load(deps.storage)?.unwrap_or_default(),
+                coordinator_hash: COORDINATORHASH.load(deps.storage)?,
+                admin: ADMIN.load(deps.storage)?.admin,
+                operator: MACI_OPERATOR.load(deps.storage)?,
+                fee_recipient: FEE_RECIPIENT.load(deps.storage)?,
+            };
+            to_json_binary(&config)
+        }
+        QueryMsg::GetOperatorPerformance {} => {
+            let performance = calculate_operator_performance(deps)
+                .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+            to_json_binary(&performance)
+        }
+        QueryMsg::CanStartProcessing {} => {
+            let reason = start_process_blocker(deps, &env)?;
+            to_json_binary(&CanStartResponse {
+                can_start: reason.is_none(),
+                reason,
+            })
+        }
+        QueryMsg::GetCommitments {} => to_json_binary(&Commitments {
+            state: CURRENT_STATE_COMMITMENT
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+            tally: CURRENT_TALLY_COMMITMENT
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+            deactivate: CURRENT_DEACTIVATE_COMMITMENT
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        }),
     }
 }
 
+// amaci dispatches no reply-tracked submessages yet, so every id is unknown here;
+// the entry point lets wrappers register `with_reply` and gives hooks a home.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    Err(ContractError::UnRecognizedReplyIdErr { id: reply.id })
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use pairing_ce::bn256::Fr as Bn256Fr;
+
+    #[test]
+    fn test_pubkey_attributes_keep_legacy_combined_form() {
+        let pubkey = PubKey {
+            x: Uint256::from_u128(12),
+            y: Uint256::from_u128(345),
+        };
+
+        // The same helper backs sign_up, AddNewKey/PreAddNewKey and both publish paths
+        assert_eq!(
+            pubkey_attributes("pubkey", &pubkey),
+            [
+                attr("pubkey", "\"12\",\"345\""),
+                attr("pubkey_x", "12"),
+                attr("pubkey_y", "345"),
+            ]
+        );
+        assert_eq!(
+            pubkey_attributes("msg_3_enc_pub_key", &pubkey)[1],
+            attr("msg_3_enc_pub_key_x", "12")
+        );
+    }
+
+    #[test]
+    fn test_uint256_to_field_rejects_values_outside_the_field() {
+        let modulus = uint256_from_hex_string(SNARK_SCALAR_FIELD_HEX);
+
+        assert!(uint256_to_field::<Bn256Fr>(&(modulus - Uint256::one())).is_ok());
+        for value in [modulus, modulus + Uint256::one(), Uint256::MAX] {
+            assert_eq!(
+                uint256_to_field::<Bn256Fr>(&value).unwrap_err(),
+                ContractError::FieldElementOutOfRange { value }
+            );
+        }
+    }
+}
 
 // Check if the operator has processed all deactivate messages within 15 minutes
 pub fn check_operator_process_time(deps: Deps, env: Env) -> Result<bool, ContractError> {
@@ -2884,13 +3341,6 @@ pub fn check_operator_process_time(deps: Deps, env: Env) -> Result<bool, Contrac
     Ok(true)
 }
 
-#[cw_serde]
-pub struct OperatorPerformance {
-    pub delay_deactivate_count: Uint256,
-    pub delay_tally_count: Uint256,
-    pub miss_rate: Uint256, // Miss rate, range 0-100, represents percentage of operator's deserved reward
-}
-
 pub fn calculate_operator_performance(deps: Deps) -> Result<OperatorPerformance, ContractError> {
     let delay_records = DELAY_RECORDS.load(deps.storage)?;
 
@@ -2930,6 +3380,37 @@ pub fn calculate_operator_performance(deps: Deps) -> Result<OperatorPerformance,
     })
 }
 
+/// Root of the vote option tree built from `results`, as committed to by the
+/// tally circuit. Shared by StopTallyingPeriod and the ComputeResultsRoot query.
+pub fn compute_results_root(deps: Deps, results: &[Uint256]) -> Result<Uint256, ContractError> {
+    if results.is_empty() {
+        return Err(ContractError::EmptyResults {});
+    }
+
+    // Check that the number of results is not greater than the maximum vote options
+    let max_vote_options = MAX_VOTE_OPTIONS.load(deps.storage)?;
+    if Uint256::from_u128(results.len() as u128) > max_vote_options {
+        return Err(ContractError::MaxVoteOptionsExceeded {
+            current: Uint256::from_u128(results.len() as u128),
+            max_allowed: max_vote_options,
+        });
+    }
+
+    let qtr_lib = QTR_LIB.load(deps.storage)?;
+    let parameters = MACIPARAMETERS.load(deps.storage)?;
+    qtr_lib.root_of(parameters.vote_option_tree_depth, results.to_vec())
+}
+
+/// Whether the operator missed the tally deadline at `now`, i.e. now is past
+/// end_time + the dynamic tally delay + TALLY_TIMEOUT_EXTRA_SECONDS. Claim then
+/// returns all funds to the admin.
+pub fn is_tally_timed_out(deps: Deps, now: Timestamp) -> Result<bool, ContractError> {
+    let voting_time = VOTINGTIME.load(deps.storage)?;
+    let actual_delay = calculate_tally_delay(deps)?;
+    let tally_timeout_secs = actual_delay.delay_seconds + TALLY_TIMEOUT_EXTRA_SECONDS;
+    Ok(now > voting_time.end_time.plus_seconds(tally_timeout_secs))
+}
+
 pub fn calculate_tally_delay(deps: Deps) -> Result<TallyDelayInfo, ContractError> {
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
     let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
@@ -2966,6 +3447,17 @@ pub fn calculate_tally_delay(deps: Deps) -> Result<TallyDelayInfo, ContractError
         .saturating_add(num_sign_ups_u64.saturating_mul(signup_delay))
         .saturating_add(msg_count_u64.saturating_mul(message_delay))
         .saturating_mul(TALLY_DELAY_MULTIPLIER);
+
+    // Keep the workload-based window within the configured [min, max] hours.
+    // Rounds instantiated before the min was stored have no floor.
+    let min_hours = TALLY_DELAY_MIN_HOURS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let max_hours = TALLY_DELAY_MAX_HOURS.load(deps.storage)?;
+    let delay_seconds = delay_seconds.clamp(
+        min_hours.saturating_mul(3600),
+        max_hours.saturating_mul(3600),
+    );
     let calculated_hours = delay_seconds / 3600;
 
     Ok(TallyDelayInfo {
@@ -3015,14 +3507,18 @@ fn verify_oracle_certificate(
             .unwrap_or_default()
             .as_bytes(),
     );
-    let certificate_binary = Binary::from_base64(certificate)?;
-    let oracle_pubkey_binary = Binary::from_base64(oracle_pubkey_str)?;
-
-    Ok(deps.api.secp256k1_verify(
-        hash.as_ref(),
-        certificate_binary.as_slice(),
-        oracle_pubkey_binary.as_slice(),
-    )?)
+    // Surface the same errors as SignUp instead of the raw decode/verify ones
+    let invalid_base64 = |_| StdError::generic_err(ContractError::InvalidBase64 {}.to_string());
+    let certificate_binary = Binary::from_base64(certificate).map_err(invalid_base64)?;
+    let oracle_pubkey_binary = Binary::from_base64(oracle_pubkey_str).map_err(invalid_base64)?;
+
+    deps.api
+        .secp256k1_verify(
+            hash.as_ref(),
+            certificate_binary.as_slice(),
+            oracle_pubkey_binary.as_slice(),
+        )
+        .map_err(|_| StdError::generic_err(ContractError::VerificationFailed {}.to_string()))
 }
 
 // Resolve the amount to use in the oracle verification payload based on VoiceCreditMode.
@@ -3036,18 +3532,30 @@ fn pubkey_key(pubkey: &PubKey) -> (Vec<u8>, Vec<u8>) {
     )
 }
 
-// Verify that enough fee was paid and return the actual payment amount.
-fn check_fee_payment(info: &MessageInfo, required: Uint128) -> Result<Uint128, ContractError> {
+// Fee denom of this round, falling back to FEE_DENOM for rounds instantiated without one.
+fn load_fee_denom(storage: &dyn Storage) -> StdResult<String> {
+    Ok(FEE_DENOM_CONFIG
+        .may_load(storage)?
+        .unwrap_or_else(|| FEE_DENOM.to_string()))
+}
+
+// Verify that exactly the required fee was paid in the round's denom and return the payment.
+fn check_fee_payment(
+    storage: &dyn Storage,
+    info: &MessageInfo,
+    required: Uint128,
+) -> Result<Coin, ContractError> {
+    let denom = load_fee_denom(storage)?;
     let payment = info
         .funds
         .iter()
-        .find(|coin| coin.denom == FEE_DENOM)
+        .find(|coin| coin.denom == denom)
         .map(|coin| coin.amount)
         .unwrap_or(Uint128::zero());
     if payment != required {
         return Err(ContractError::InsufficientFundsSend {});
     }
-    Ok(payment)
+    Ok(coin(payment.u128(), denom))
 }
 
 // Guard: return DeactivateDisabled if the feature is turned off.
@@ -3058,6 +3566,34 @@ fn require_deactivate_enabled(deps: Deps) -> Result<(), ContractError> {
     Ok(())
 }
 
+// Mirrors the preconditions of execute_start_process_period; returns why
+// processing cannot start yet, or None if it can.
+fn start_process_blocker(deps: Deps, env: &Env) -> StdResult<Option<String>> {
+    let voting_time = VOTINGTIME.load(deps.storage)?;
+    if env.block.time <= voting_time.end_time {
+        return Ok(Some("Voting period has not ended".to_string()));
+    }
+
+    let period = PERIOD.load(deps.storage)?;
+    if period.status != PeriodStatus::Pending {
+        return Ok(Some(format!(
+            "Round is already in {:?} status",
+            period.status
+        )));
+    }
+
+    let processed_dmsg_count = PROCESSED_DMSG_COUNT.load(deps.storage)?;
+    let dmsg_chain_length = DMSG_CHAIN_LENGTH.load(deps.storage)?;
+    if processed_dmsg_count != dmsg_chain_length {
+        return Ok(Some(format!(
+            "{} deactivate messages are left to process",
+            dmsg_chain_length - processed_dmsg_count
+        )));
+    }
+
+    Ok(None)
+}
+
 // Guard: return PeriodError if the current period status is not the expected one.
 fn require_period_status(deps: Deps, expected: PeriodStatus) -> Result<(), ContractError> {
     let period = PERIOD.load(deps.storage)?;
@@ -3078,6 +3614,20 @@ fn to_json_or<T: serde::Serialize>(value: &T, fallback: &'static str) -> String
     serde_json::to_string(value).unwrap_or_else(|_| fallback.to_string())
 }
 
+// `{key}_x`/`{key}_y` as plain decimals, plus the deprecated combined `{key}`
+// ("x","y" with Debug quotes) that existing indexers still parse. Drop the
+// combined form once they have moved to the split keys.
+fn pubkey_attributes(key: &str, pubkey: &PubKey) -> [Attribute; 3] {
+    [
+        attr(
+            key,
+            format!("{:?},{:?}", pubkey.x.to_string(), pubkey.y.to_string()),
+        ),
+        attr(format!("{key}_x"), pubkey.x.to_string()),
+        attr(format!("{key}_y"), pubkey.y.to_string()),
+    ]
+}
+
 // Combined oracle registration status for QueryRegistrationStatus.
 // Performs a single signature verification and returns (can_sign_up, is_register, balance)
 // together, avoiding the double verification that would occur when calling the two functions
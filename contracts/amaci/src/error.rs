@@ -1,6 +1,33 @@
 use cosmwasm_std::{StdError, Uint256};
+use maci_utils::SnarkjsError;
 use thiserror::Error;
 
+/// Why a Groth16 verification key or proof failed to parse, naming the offending field
+#[derive(Error, Debug, PartialEq)]
+pub enum Groth16ParseError {
+    #[error("{field}: expected {expected} bytes, got {actual}")]
+    BadPointLength {
+        field: String,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("{field}: invalid hex encoding")]
+    HexDecode { field: String },
+
+    #[error("{field}: point is not on the curve")]
+    NotOnCurve { field: String },
+
+    #[error("{field}: point is not in the prime-order subgroup")]
+    NotInSubgroup { field: String },
+
+    #[error("{field}: invalid point encoding ({reason})")]
+    InvalidPoint { field: String, reason: String },
+
+    #[error("expected {expected} IC points, got {actual}")]
+    WrongNumberOfIC { expected: usize, actual: usize },
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
@@ -21,12 +48,6 @@ pub enum ContractError {
     #[error("Data error")]
     DataError {},
 
-    #[error("Error verification")]
-    ErrorVerificationKey {},
-
-    #[error("Error proof")]
-    ErrorProof {},
-
     #[error("Error public signal")]
     ErrorPublicSignal {},
 
@@ -42,8 +63,8 @@ pub enum ContractError {
     #[error("invalid hex format")]
     HexDecodingError {},
 
-    #[error("Invalid vkey")]
-    InvalidVKeyError {},
+    #[error("Groth16 parse error: {0}")]
+    Groth16Parse(#[from] Groth16ParseError),
 
     #[error("Invalid proof, step {step} verify failed")]
     InvalidProof { step: String },
@@ -208,6 +229,12 @@ pub enum ContractError {
     #[error("Invalid pubkey: values must be less than the snark scalar field")]
     InvalidPubKey {},
 
+    #[error("Invalid create round window: must be greater than zero")]
+    InvalidCreateRoundWindow {},
+
+    #[error("Invalid coordinator pubkey: not a point on the Baby Jubjub curve")]
+    InvalidCoordinatorKey {},
+
     #[error("Invalid encrypted public key")]
     InvalidEncPubKey {},
 
@@ -226,6 +253,9 @@ pub enum ContractError {
     #[error("Not all users have been tallied yet")]
     NotAllUsersProcessed {},
 
+    #[error("Messages must be fully processed before tallying")]
+    MessagesNotFullyProcessed {},
+
     #[error(
         "Tally commitment mismatch: submitted results do not match the verified tally commitment"
     )]
@@ -233,4 +263,41 @@ pub enum ContractError {
 
     #[error("A round with no signups must finalize with all-zero results")]
     InvalidEmptyRoundResult {},
+
+    #[error("Results must contain at least one vote option")]
+    EmptyResults {},
+
+    #[error("Results length {length} exceeds vote option tree capacity {capacity}")]
+    ResultsExceedTreeCapacity { length: u32, capacity: u32 },
+
+    #[error("Invalid fee rate {fee_rate}: must be a percentage between 0 and 100")]
+    InvalidFeeRate { fee_rate: u64 },
+
+    #[error("Invalid tally delay hours: min {min_hours} must not exceed max {max_hours}")]
+    InvalidTallyDelayHours { min_hours: u64, max_hours: u64 },
+
+    #[error("Invalid vote options: {reason}")]
+    InvalidVoteOptions { reason: String },
+
+    #[error("Address {addr} is not in the whitelist")]
+    NotInWhitelist { addr: String },
+
+    #[error("Invalid oracle pubkey: expected a base64 secp256k1 public key")]
+    InvalidOraclePubkey {},
+
+    #[error("Unrecognized reply id {id}")]
+    UnRecognizedReplyIdErr { id: u64 },
+
+    #[error("Unsupported state tree depth {depth}: must be between {min} and {max}")]
+    UnsupportedStateTreeDepth {
+        depth: Uint256,
+        min: u128,
+        max: u128,
+    },
+
+    #[error("snarkjs proof error: {0}")]
+    Snarkjs(#[from] SnarkjsError),
+
+    #[error("Field element {value} is not below the SNARK scalar field modulus")]
+    FieldElementOutOfRange { value: Uint256 },
 }
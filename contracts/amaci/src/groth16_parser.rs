@@ -1,42 +1,24 @@
-use super::error::ContractError;
+use super::error::{ContractError, Groth16ParseError};
+use crate::msg::Groth16VKeyType;
 use crate::state::{Groth16ProofStr, Groth16VkeyStr};
 use bellman_ce_verifier::{Proof, VerifyingKey};
 use cosmwasm_std::ensure;
-use pairing_ce::bn256::{G1Affine, G1Uncompressed, G2Affine, G2Uncompressed};
-use pairing_ce::{CurveAffine, EncodedPoint, Engine};
+use ff_ce::{Field, PrimeField};
+use pairing_ce::bn256::{Bn256, Fr, FrRepr, G1Affine, G1Uncompressed, G2Affine, G2Uncompressed};
+use pairing_ce::{CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError};
+use sha2::{Digest, Sha256};
 
 /// convert the proof into the affine type, which will be used to verify
+///
+/// every point is decoded with the curve and subgroup checks of `into_affine`, so an
+/// off-curve or small-subgroup `pi_a`/`pi_b`/`pi_c` is rejected before any pairing runs
 pub fn parse_groth16_proof<E>(pof: Groth16ProofStr) -> Result<Proof<E>, ContractError>
 where
     E: Engine<G1Affine = G1Affine, G2Affine = G2Affine>,
 {
-    let pi_a = pof.pi_a;
-    let pi_b = pof.pi_b;
-    let pi_c = pof.pi_c;
-
-    ensure!(pi_a.len() == 64, ContractError::ErrorProof {});
-    ensure!(pi_b.len() == 128, ContractError::ErrorProof {});
-    ensure!(pi_c.len() == 64, ContractError::ErrorProof {});
-
-    let mut a_arr: [u8; 64] = [0; 64];
-    let mut b_arr: [u8; 128] = [0; 128];
-    let mut c_arr: [u8; 64] = [0; 64];
-
-    a_arr[..pi_a.len()].copy_from_slice(&pi_a[..]);
-
-    b_arr[..pi_b.len()].copy_from_slice(&pi_b[..]);
-
-    c_arr[..pi_c.len()].copy_from_slice(&pi_c[..]);
-
-    let pia_affine: G1Affine = G1Uncompressed::from_fixed_bytes(a_arr)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorProof {})?;
-    let pib_affine: G2Affine = G2Uncompressed::from_fixed_bytes(b_arr)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorProof {})?;
-    let pic_affine: G1Affine = G1Uncompressed::from_fixed_bytes(c_arr)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorProof {})?;
+    let pia_affine = decode_g1("pi_a", &pof.pi_a)?;
+    let pib_affine = decode_g2("pi_b", &pof.pi_b)?;
+    let pic_affine = decode_g1("pi_c", &pof.pi_c)?;
 
     Ok(Proof {
         a: pia_affine,
@@ -45,78 +27,105 @@ where
     })
 }
 
-/// convert the verification key into the affine type, which will be used in verification
-pub fn parse_groth16_vkey<E>(vk: Groth16VkeyStr) -> Result<VerifyingKey<E>, ContractError>
-where
-    E: Engine<G1Affine = G1Affine, G2Affine = G2Affine>,
-{
-    let vk_alpha_1 = vk.alpha_1;
-    let vk_beta_2 = vk.beta_2;
-    let vk_gamma_2 = vk.gamma_2;
-    let vk_delta_2 = vk.delta_2;
-    let vk_ic0 = vk.ic0;
-    let vk_ic1 = vk.ic1;
+/// number of IC points in a vkey: one constant term plus the single public input hash
+pub const GROTH16_IC_LEN: usize = 2;
 
-    ensure!(
-        vk_alpha_1.len() == 64,
-        ContractError::ErrorVerificationKey {}
-    );
-    ensure!(
-        vk_beta_2.len() == 128,
-        ContractError::ErrorVerificationKey {}
-    );
-    ensure!(
-        vk_gamma_2.len() == 128,
-        ContractError::ErrorVerificationKey {}
-    );
-    ensure!(
-        vk_delta_2.len() == 128,
-        ContractError::ErrorVerificationKey {}
-    );
-    ensure!(vk_ic0.len() == 64, ContractError::ErrorVerificationKey {});
-    ensure!(vk_ic1.len() == 64, ContractError::ErrorVerificationKey {});
+/// decode a hex-encoded vkey field, naming the field on failure
+fn decode_hex_field(field: &str, value: &str) -> Result<Vec<u8>, Groth16ParseError> {
+    hex::decode(value).map_err(|_| Groth16ParseError::HexDecode {
+        field: field.to_string(),
+    })
+}
 
-    let mut alpha1: [u8; 64] = [0; 64];
-    let mut beta2: [u8; 128] = [0; 128];
-    let mut gamma2: [u8; 128] = [0; 128];
-    let mut delta2: [u8; 128] = [0; 128];
-    let mut ic_0: [u8; 64] = [0; 64];
-    let mut ic_1: [u8; 64] = [0; 64];
-    let mut ic = Vec::new();
+/// decode the hex strings of a vkey message into raw bytes
+pub fn decode_groth16_vkey(vk: &Groth16VKeyType) -> Result<Groth16VkeyStr, Groth16ParseError> {
+    Ok(Groth16VkeyStr {
+        alpha_1: decode_hex_field("vk_alpha1", &vk.vk_alpha1)?,
+        beta_2: decode_hex_field("vk_beta_2", &vk.vk_beta_2)?,
+        gamma_2: decode_hex_field("vk_gamma_2", &vk.vk_gamma_2)?,
+        delta_2: decode_hex_field("vk_delta_2", &vk.vk_delta_2)?,
+        ic0: decode_hex_field("vk_ic0", &vk.vk_ic0)?,
+        ic1: decode_hex_field("vk_ic1", &vk.vk_ic1)?,
+    })
+}
 
-    alpha1[..vk_alpha_1.len()].copy_from_slice(&vk_alpha_1[..]);
+fn point_error(field: &str, err: GroupDecodingError) -> Groth16ParseError {
+    match err {
+        GroupDecodingError::NotOnCurve => Groth16ParseError::NotOnCurve {
+            field: field.to_string(),
+        },
+        GroupDecodingError::NotInSubgroup => Groth16ParseError::NotInSubgroup {
+            field: field.to_string(),
+        },
+        other => Groth16ParseError::InvalidPoint {
+            field: field.to_string(),
+            reason: other.to_string(),
+        },
+    }
+}
 
-    beta2[..vk_beta_2.len()].copy_from_slice(&vk_beta_2[..]);
+/// decode an uncompressed G1 point (64 bytes)
+fn decode_g1(field: &str, bytes: &[u8]) -> Result<G1Affine, Groth16ParseError> {
+    let mut arr: [u8; 64] = [0; 64];
+    ensure!(
+        bytes.len() == arr.len(),
+        Groth16ParseError::BadPointLength {
+            field: field.to_string(),
+            expected: arr.len(),
+            actual: bytes.len(),
+        }
+    );
+    arr.copy_from_slice(bytes);
 
-    gamma2[..vk_gamma_2.len()].copy_from_slice(&vk_gamma_2[..]);
+    G1Uncompressed::from_fixed_bytes(arr)
+        .into_affine()
+        .map_err(|e| point_error(field, e))
+}
 
-    delta2[..vk_delta_2.len()].copy_from_slice(&vk_delta_2[..]);
+/// decode an uncompressed G2 point (128 bytes)
+fn decode_g2(field: &str, bytes: &[u8]) -> Result<G2Affine, Groth16ParseError> {
+    let mut arr: [u8; 128] = [0; 128];
+    ensure!(
+        bytes.len() == arr.len(),
+        Groth16ParseError::BadPointLength {
+            field: field.to_string(),
+            expected: arr.len(),
+            actual: bytes.len(),
+        }
+    );
+    arr.copy_from_slice(bytes);
 
-    ic_0[..vk_ic0.len()].copy_from_slice(&vk_ic0[..]);
+    G2Uncompressed::from_fixed_bytes(arr)
+        .into_affine()
+        .map_err(|e| point_error(field, e))
+}
 
-    ic_1[..vk_ic1.len()].copy_from_slice(&vk_ic1[..]);
+/// decode the IC points of a vkey, which must number exactly `GROTH16_IC_LEN`
+pub fn parse_groth16_ic(ic: &[Vec<u8>]) -> Result<Vec<G1Affine>, Groth16ParseError> {
+    ensure!(
+        ic.len() == GROTH16_IC_LEN,
+        Groth16ParseError::WrongNumberOfIC {
+            expected: GROTH16_IC_LEN,
+            actual: ic.len(),
+        }
+    );
 
-    let alpha1_affine = G1Uncompressed::from_fixed_bytes(alpha1)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorVerificationKey {})?;
-    let beta2_affine = G2Uncompressed::from_fixed_bytes(beta2)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorVerificationKey {})?;
-    let gamma2_affine = G2Uncompressed::from_fixed_bytes(gamma2)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorVerificationKey {})?;
-    let delta2_affine = G2Uncompressed::from_fixed_bytes(delta2)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorVerificationKey {})?;
-    let ic0_affine = G1Uncompressed::from_fixed_bytes(ic_0)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorVerificationKey {})?;
-    let ic1_affine = G1Uncompressed::from_fixed_bytes(ic_1)
-        .into_affine()
-        .map_err(|_| ContractError::ErrorVerificationKey {})?;
+    ic.iter()
+        .enumerate()
+        .map(|(i, point)| decode_g1(&format!("ic{}", i), point))
+        .collect()
+}
 
-    ic.push(ic0_affine);
-    ic.push(ic1_affine);
+/// convert the verification key into the affine type, which will be used in verification
+pub fn parse_groth16_vkey<E>(vk: Groth16VkeyStr) -> Result<VerifyingKey<E>, ContractError>
+where
+    E: Engine<G1Affine = G1Affine, G2Affine = G2Affine>,
+{
+    let alpha1_affine = decode_g1("alpha_1", &vk.alpha_1)?;
+    let beta2_affine = decode_g2("beta_2", &vk.beta_2)?;
+    let gamma2_affine = decode_g2("gamma_2", &vk.gamma_2)?;
+    let delta2_affine = decode_g2("delta_2", &vk.delta_2)?;
+    let ic = parse_groth16_ic(&[vk.ic0, vk.ic1])?;
 
     // return verification key
     Ok(VerifyingKey {
@@ -129,3 +138,375 @@ where
         ic,
     })
 }
+
+/// Derive the per-proof batching scalars from a transcript of every proof and its inputs.
+/// Each scalar is 128 bits, which keeps the soundness error of the batch at 2^-128.
+fn batch_challenges(proofs: &[(Proof<Bn256>, Vec<Fr>)]) -> Option<Vec<Fr>> {
+    let mut transcript = Sha256::new();
+    for (proof, inputs) in proofs {
+        transcript.update(proof.a.into_uncompressed().as_ref());
+        transcript.update(proof.b.into_uncompressed().as_ref());
+        transcript.update(proof.c.into_uncompressed().as_ref());
+        transcript.update((inputs.len() as u64).to_le_bytes());
+        for input in inputs {
+            for limb in input.into_repr().as_ref() {
+                transcript.update(limb.to_le_bytes());
+            }
+        }
+    }
+    let seed = transcript.finalize();
+
+    (0..proofs.len() as u64)
+        .map(|i| {
+            let mut hasher = Sha256::new();
+            hasher.update(seed);
+            hasher.update(i.to_le_bytes());
+            let digest = hasher.finalize();
+
+            let mut lo = [0u8; 8];
+            let mut hi = [0u8; 8];
+            lo.copy_from_slice(&digest[..8]);
+            hi.copy_from_slice(&digest[8..16]);
+            Fr::from_repr(FrRepr([
+                u64::from_le_bytes(lo),
+                u64::from_le_bytes(hi),
+                0,
+                0,
+            ]))
+            .ok()
+        })
+        .collect()
+}
+
+/// verify a batch of proofs sharing one verification key with a random linear combination,
+/// using n + 3 miller loops and a single final exponentiation instead of n full verifications
+pub fn batch_verify(vk: &VerifyingKey<Bn256>, proofs: &[(Proof<Bn256>, Vec<Fr>)]) -> bool {
+    if proofs.is_empty() {
+        return true;
+    }
+    if proofs
+        .iter()
+        .any(|(_, inputs)| inputs.len() + 1 != vk.ic.len())
+    {
+        return false;
+    }
+
+    let challenges = match batch_challenges(proofs) {
+        Some(challenges) => challenges,
+        None => return false,
+    };
+
+    let mut g1_terms = Vec::with_capacity(proofs.len() + 3);
+    let mut g2_terms = Vec::with_capacity(proofs.len() + 3);
+
+    // sum of challenges scales alpha and ic[0]; per-input sums scale ic[1..]
+    let mut challenge_sum = Fr::zero();
+    let mut input_sums = vec![Fr::zero(); vk.ic.len() - 1];
+    let mut c_sum = <G1Affine as CurveAffine>::Projective::zero();
+
+    for ((proof, inputs), r) in proofs.iter().zip(challenges.iter()) {
+        g1_terms.push(proof.a.mul(*r).into_affine().prepare());
+        g2_terms.push(proof.b.prepare());
+
+        challenge_sum.add_assign(r);
+        for (sum, input) in input_sums.iter_mut().zip(inputs.iter()) {
+            let mut term = *input;
+            term.mul_assign(r);
+            sum.add_assign(&term);
+        }
+        c_sum.add_assign(&proof.c.mul(*r));
+    }
+
+    let mut alpha_sum = vk.alpha_g1.mul(challenge_sum);
+    alpha_sum.negate();
+    g1_terms.push(alpha_sum.into_affine().prepare());
+    g2_terms.push(vk.beta_g2.prepare());
+
+    let mut acc_sum = vk.ic[0].mul(challenge_sum);
+    for (ic, sum) in vk.ic[1..].iter().zip(input_sums.iter()) {
+        acc_sum.add_assign(&ic.mul(*sum));
+    }
+    acc_sum.negate();
+    g1_terms.push(acc_sum.into_affine().prepare());
+    g2_terms.push(vk.gamma_g2.prepare());
+
+    c_sum.negate();
+    g1_terms.push(c_sum.into_affine().prepare());
+    g2_terms.push(vk.delta_g2.prepare());
+
+    let terms: Vec<_> = g1_terms.iter().zip(g2_terms.iter()).collect();
+    match Bn256::final_exponentiation(&Bn256::miller_loop(terms.iter())) {
+        Some(result) => result == <Bn256 as Engine>::Fqk::one(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::Groth16ProofType;
+    use bellman_ce_verifier::{prepare_verifying_key, verify_proof};
+
+    fn fr(value: u64) -> Fr {
+        Fr::from_str(&value.to_string()).unwrap()
+    }
+
+    /// Toxic waste for a one-input circuit, kept so tests can forge valid proofs
+    struct Trapdoor {
+        alpha: Fr,
+        beta: Fr,
+        gamma: Fr,
+        delta: Fr,
+        ic: [Fr; 2],
+    }
+
+    fn setup() -> (VerifyingKey<Bn256>, Trapdoor) {
+        let td = Trapdoor {
+            alpha: fr(11),
+            beta: fr(13),
+            gamma: fr(17),
+            delta: fr(19),
+            ic: [fr(23), fr(29)],
+        };
+        let vk = VerifyingKey {
+            alpha_g1: G1Affine::one().mul(td.alpha).into_affine(),
+            beta_g1: G1Affine::zero(),
+            beta_g2: G2Affine::one().mul(td.beta).into_affine(),
+            gamma_g2: G2Affine::one().mul(td.gamma).into_affine(),
+            delta_g1: G1Affine::zero(),
+            delta_g2: G2Affine::one().mul(td.delta).into_affine(),
+            ic: td
+                .ic
+                .iter()
+                .map(|u| G1Affine::one().mul(*u).into_affine())
+                .collect(),
+        };
+        (vk, td)
+    }
+
+    /// Build a proof satisfying a*b = alpha*beta + gamma*(ic0 + input*ic1) + delta*c
+    fn prove(td: &Trapdoor, input: Fr, a: Fr, b: Fr) -> Proof<Bn256> {
+        let mut acc = td.ic[1];
+        acc.mul_assign(&input);
+        acc.add_assign(&td.ic[0]);
+
+        let mut c = a;
+        c.mul_assign(&b);
+        let mut alpha_beta = td.alpha;
+        alpha_beta.mul_assign(&td.beta);
+        c.sub_assign(&alpha_beta);
+        let mut gamma_acc = td.gamma;
+        gamma_acc.mul_assign(&acc);
+        c.sub_assign(&gamma_acc);
+        c.mul_assign(&td.delta.inverse().unwrap());
+
+        Proof {
+            a: G1Affine::one().mul(a).into_affine(),
+            b: G2Affine::one().mul(b).into_affine(),
+            c: G1Affine::one().mul(c).into_affine(),
+        }
+    }
+
+    fn batch(td: &Trapdoor, inputs: &[u64]) -> Vec<(Proof<Bn256>, Vec<Fr>)> {
+        inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                let proof = prove(td, fr(*input), fr(100 + i as u64), fr(200 + i as u64));
+                (proof, vec![fr(*input)])
+            })
+            .collect()
+    }
+
+    // process vkey of the 2-1-1-5 test circuit
+    fn test_vkey_msg() -> Groth16VKeyType {
+        Groth16VKeyType {
+            vk_alpha1: "2d4d9aa7e302d9df41749d5507949d05dbea33fbb16c643b22f599a2be6df2e214bedd503c37ceb061d8ec60209fe345ce89830a19230301f076caff004d1926".to_string(),
+            vk_beta_2: "0967032fcbf776d1afc985f88877f182d38480a653f2decaa9794cbc3bf3060c0e187847ad4c798374d0d6732bf501847dd68bc0e071241e0213bc7fc13db7ab304cfbd1e08a704a99f5e847d93f8c3caafddec46b7a0d379da69a4d112346a71739c1b1a457a8c7313123d24d2f9192f896b7c63eea05a9d57f06547ad0cec8".to_string(),
+            vk_gamma_2: "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa".to_string(),
+            vk_delta_2: "14fcf460ec67f39e7604ce59622a530797f70d5dce2cdbfcc6285acb11a091490a51cc6cc5aec73e4b1fcd45f152e8d18d9831efa2ca2d8aba5ec00a5258a86c2588c3053fe7907bd6e3b3b455283a441df9f5e9cc8b1aca6712d37004c28e4311915d144397caf4c4e74e150159574574d84d4a620cc22e1ee695b45f0c09fb".to_string(),
+            vk_ic0: "152b83fbeccaaf60ff7c47ebcefc9368d1d81271fc47d36e3032f589112849c900ce96219b641b39a390c8fe58b8ff805e21e55257e1437c26285d030fb52a70".to_string(),
+            vk_ic1: "0722c7bc6aaf03f86fc32096b9ad50fa14a99d113cd02d3a26df629703f7b9b61a19f057b25b77efefe232bb2cc87d9637bca988fc0325bd8c9e4b1febe5eca4".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_groth16_vkey_valid() {
+        let vk = decode_groth16_vkey(&test_vkey_msg()).unwrap();
+        assert!(parse_groth16_vkey::<Bn256>(vk).is_ok());
+    }
+
+    #[test]
+    fn test_parse_groth16_vkey_hex_decode_error() {
+        let mut msg = test_vkey_msg();
+        msg.vk_gamma_2 = "zz".to_string();
+
+        assert_eq!(
+            decode_groth16_vkey(&msg).unwrap_err(),
+            Groth16ParseError::HexDecode {
+                field: "vk_gamma_2".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_groth16_vkey_bad_point_length() {
+        let mut vk = decode_groth16_vkey(&test_vkey_msg()).unwrap();
+        vk.beta_2.truncate(64);
+
+        assert_eq!(
+            parse_groth16_vkey::<Bn256>(vk).unwrap_err(),
+            ContractError::Groth16Parse(Groth16ParseError::BadPointLength {
+                field: "beta_2".to_string(),
+                expected: 128,
+                actual: 64,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_groth16_vkey_not_on_curve() {
+        let mut vk = decode_groth16_vkey(&test_vkey_msg()).unwrap();
+        vk.alpha_1[63] ^= 0x01;
+
+        assert_eq!(
+            parse_groth16_vkey::<Bn256>(vk).unwrap_err(),
+            ContractError::Groth16Parse(Groth16ParseError::NotOnCurve {
+                field: "alpha_1".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_groth16_ic_wrong_count() {
+        let vk = decode_groth16_vkey(&test_vkey_msg()).unwrap();
+        let ic = vec![vk.ic0.clone(), vk.ic1.clone(), vk.ic1];
+
+        assert_eq!(
+            parse_groth16_ic(&ic).unwrap_err(),
+            Groth16ParseError::WrongNumberOfIC {
+                expected: 2,
+                actual: 3,
+            }
+        );
+    }
+
+    fn test_proof_str() -> Groth16ProofStr {
+        Groth16ProofStr {
+            pi_a: hex::decode("27fb48285bc59bc74c9197857856cf5f3dcce55f22b83589e399240b8469e45725c5495e3ebcdd3bc04620fd13fed113c31d19a685f7f037daf02dde02d26e4f").unwrap(),
+            pi_b: hex::decode("0d1bd72809defb6e85ea48de4c28e9ec9dcd2bc5111acdb66b5cdb38ccf6d4e32bdeac48a806c2fd6cef8e09bfde1983961693c8d4a513777ba26b07f2abacba1efb7600f04e786d93f321c6df732eb0043548cfe12fa8a5aea848a500ef5b9728dbc747fc76993c16dadf2c8ef68f3d757afa6d4caf9a767c424ec0d7ff4932").unwrap(),
+            pi_c: hex::decode("2062c6bee5dad15af1ebcb0e623b27f7d29775774cc92b2a7554d1801af818940309fa215204181d3a1fef15d162aa779b8900e2b84d8b8fa22a20b65652eb46").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_parse_groth16_proof_valid() {
+        assert!(parse_groth16_proof::<Bn256>(test_proof_str()).is_ok());
+    }
+
+    #[test]
+    fn test_groth16_proof_snarkjs_round_trip_parses() {
+        let pof = test_proof_str();
+        let proof = Groth16ProofType {
+            a: hex::encode(&pof.pi_a),
+            b: hex::encode(&pof.pi_b),
+            c: hex::encode(&pof.pi_c),
+        };
+
+        let json = proof.to_snarkjs().unwrap();
+        let parsed = Groth16ProofType::from_snarkjs(&json).unwrap();
+        assert_eq!(parsed, proof);
+        assert!(parse_groth16_proof::<Bn256>(Groth16ProofStr {
+            pi_a: hex::decode(&parsed.a).unwrap(),
+            pi_b: hex::decode(&parsed.b).unwrap(),
+            pi_c: hex::decode(&parsed.c).unwrap(),
+        })
+        .is_ok());
+
+        assert!(matches!(
+            Groth16ProofType::from_snarkjs(&json.replace("groth16", "plonk")),
+            Err(ContractError::Snarkjs(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_groth16_proof_rejects_off_curve_pi_a() {
+        let mut proof = test_proof_str();
+        proof.pi_a[63] ^= 0x01;
+
+        assert_eq!(
+            parse_groth16_proof::<Bn256>(proof).unwrap_err(),
+            ContractError::Groth16Parse(Groth16ParseError::NotOnCurve {
+                field: "pi_a".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_groth16_proof_rejects_off_curve_pi_b() {
+        let mut proof = test_proof_str();
+        proof.pi_b[127] ^= 0x01;
+
+        assert_eq!(
+            parse_groth16_proof::<Bn256>(proof).unwrap_err(),
+            ContractError::Groth16Parse(Groth16ParseError::NotOnCurve {
+                field: "pi_b".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_groth16_proof_bad_length() {
+        let mut proof = test_proof_str();
+        proof.pi_c.push(0);
+
+        assert_eq!(
+            parse_groth16_proof::<Bn256>(proof).unwrap_err(),
+            ContractError::Groth16Parse(Groth16ParseError::BadPointLength {
+                field: "pi_c".to_string(),
+                expected: 64,
+                actual: 65,
+            })
+        );
+    }
+
+    #[test]
+    fn test_batch_verify_valid_proofs() {
+        let (vk, td) = setup();
+        let proofs = batch(&td, &[1, 2, 3]);
+
+        let pvk = prepare_verifying_key(&vk);
+        for (proof, inputs) in &proofs {
+            assert!(verify_proof(&pvk, proof, inputs).unwrap());
+        }
+
+        assert!(batch_verify(&vk, &proofs));
+    }
+
+    #[test]
+    fn test_batch_verify_rejects_flipped_input() {
+        let (vk, td) = setup();
+        let mut proofs = batch(&td, &[1, 2, 3]);
+        proofs[1].1[0] = fr(4);
+
+        assert!(!batch_verify(&vk, &proofs));
+    }
+
+    #[test]
+    fn test_batch_verify_rejects_flipped_proof() {
+        let (vk, td) = setup();
+        let mut proofs = batch(&td, &[1, 2, 3]);
+        proofs[2].0.c = proofs[0].0.c;
+
+        assert!(!batch_verify(&vk, &proofs));
+    }
+
+    #[test]
+    fn test_batch_verify_rejects_wrong_input_count() {
+        let (vk, td) = setup();
+        let mut proofs = batch(&td, &[1, 2]);
+        proofs[0].1.push(fr(5));
+
+        assert!(!batch_verify(&vk, &proofs));
+    }
+}
@@ -9,4 +9,4 @@ pub mod state;
 #[cfg(any(feature = "mt", test))]
 pub mod multitest;
 
-pub use crate::error::ContractError;
+pub use crate::error::{ContractError, Groth16ParseError};
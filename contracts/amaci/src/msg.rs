@@ -1,3 +1,4 @@
+use crate::error::ContractError;
 #[allow(unused_imports)] // DelayRecords is used by the #[returns] proc-macro attribute
 use crate::state::{
     DelayRecords, Groth16VkeyStr, MaciParameters, MessageData, PeriodStatus, PubKey,
@@ -5,6 +6,7 @@ use crate::state::{
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Timestamp, Uint128, Uint256};
+use maci_utils::{groth16_proof_from_snarkjs, groth16_proof_to_snarkjs};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -21,6 +23,8 @@ pub struct InstantiateMsg {
     pub vote_option_map: Vec<String>,
     pub round_info: RoundInfo,
     pub voting_time: VotingTime,
+    // Minimum gap between start and end time in seconds (default: 600)
+    pub create_round_window: Option<u64>,
 
     // Circuit configuration
     pub circuit_type: Uint256,         // <0: 1p1v | 1: pv>
@@ -47,6 +51,8 @@ pub struct InstantiateMsg {
     pub message_fee: Uint128,
     pub deactivate_fee: Uint128,
     pub signup_fee: Uint128,
+    // native denom the fees are paid in and Claim pays out (default: "peaka")
+    pub fee_denom: Option<String>,
 
     // ── Delay configuration (seconds) injected by Registry ───────────────────
     // tally base delay: covers first 5^int_state_tree_depth-slot batch
@@ -124,6 +130,19 @@ pub struct Groth16ProofType {
     pub c: String,
 }
 
+impl Groth16ProofType {
+    /// Convert a `snarkjs groth16 prove` proof JSON into the hex `a`/`b`/`c` the contract verifies
+    pub fn from_snarkjs(json: &str) -> Result<Self, ContractError> {
+        let (a, b, c) = groth16_proof_from_snarkjs(json)?;
+        Ok(Groth16ProofType { a, b, c })
+    }
+
+    /// Convert back to snarkjs proof JSON
+    pub fn to_snarkjs(&self) -> Result<String, ContractError> {
+        Ok(groth16_proof_to_snarkjs(&self.a, &self.b, &self.c)?)
+    }
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     SetRoundInfo {
@@ -135,6 +154,22 @@ pub enum ExecuteMsg {
     SetVoteOptionsMap {
         vote_option_map: Vec<String>,
     },
+    RemoveFromWhitelist {
+        addr: Addr,
+    },
+    SetOracleWhitelistPubkey {
+        pubkey: String,
+    },
+    ExtendVotingTime {
+        new_end_time: Timestamp,
+    },
+    SetFeeRate {
+        fee_rate: u64,
+    },
+    SetTallyDelayHours {
+        min_hours: u64,
+        max_hours: u64,
+    },
     SignUp {
         pubkey: PubKey, // user's pubkey
         // Oracle mode parameter (optional for SignUpWithStaticWhitelist mode, required for SignUpWithOracle mode)
@@ -185,7 +220,10 @@ pub enum ExecuteMsg {
         results: Vec<Uint256>,
         salt: Uint256,
     },
-    Claim {},
+    /// Distribute the round's balance; the operator may send its reward to `reward_recipient`
+    Claim {
+        reward_recipient: Option<Addr>,
+    },
 }
 
 #[cw_serde]
@@ -223,18 +261,35 @@ pub enum QueryMsg {
     #[returns(Uint256)]
     GetProcessedDMsgCount {},
 
+    #[returns(Option<Timestamp>)]
+    GetFirstDmsgTimestamp {},
+
     #[returns(Uint256)]
     GetProcessedMsgCount {},
 
+    /// Message index range the next ProcessMessage proof must cover. Errors
+    /// once every message has been processed.
+    #[returns(BatchRange)]
+    GetNextProcessBatch {},
+
     #[returns(Uint256)]
     GetProcessedUserCount {},
 
+    /// Batch number and size the next ProcessTally proof must use. Errors once
+    /// every user has been tallied.
+    #[returns(TallyBatch)]
+    GetNextTallyBatch {},
+
     #[returns(Uint256)]
     GetStateTreeRoot {},
 
     #[returns(Uint256)]
     GetNode { index: Uint256 },
 
+    /// Deactivate tree node; only the root (index 0) is stored on-chain
+    #[returns(Uint256)]
+    GetDeactivateNode { index: Uint256 },
+
     #[returns(Uint256)]
     GetResult { index: Uint256 },
 
@@ -247,15 +302,26 @@ pub enum QueryMsg {
     #[returns(Uint256)]
     GetVoiceCreditBalance { index: Uint256 },
 
+    /// Voice credit balances for state indices in `[start, end)`, clamped to the
+    /// number of signups and at most `MAX_VOICE_CREDIT_BALANCES_QUERY` entries.
+    #[returns(Vec<Uint256>)]
+    GetAllVoiceCreditBalances { start: Uint256, end: Uint256 },
+
     #[returns(Uint256)]
     GetVoiceCreditAmount {},
 
     #[returns(Option<Uint256>)]
     Signuped { pubkey: PubKey },
 
+    #[returns(Option<Uint256>)]
+    SignupedByX { pubkey_x: Uint256 },
+
     #[returns(Vec<String>)]
     VoteOptionMap {},
 
+    #[returns(Vec<(u32, String)>)]
+    GetVoteOptionsIndexed {},
+
     #[returns(Uint256)]
     MaxVoteOptions {},
 
@@ -274,9 +340,25 @@ pub enum QueryMsg {
     #[returns(DelayRecords)]
     GetDelayRecords {},
 
+    /// Up to `limit` (at most 100) delay records starting at index `start`, in the order
+    /// they were recorded
+    #[returns(DelayRecords)]
+    GetDelayRecordsPaginated { start: u32, limit: u32 },
+
     #[returns(TallyDelayInfo)]
     GetTallyDelay {},
 
+    /// Work StopTallyingPeriod will be measured on and the tally window it allows, so far
+    #[returns(TallyWork)]
+    GetEstimatedTallyWork {},
+
+    /// Whether the tally deadline has passed, after which Claim refunds the admin
+    #[returns(bool)]
+    IsTallyTimedOut {},
+
+    #[returns(Uint256)]
+    ComputeResultsRoot { results: Vec<Uint256> },
+
     #[returns(Option<String>)]
     QueryOracleWhitelistConfig {},
 
@@ -313,6 +395,19 @@ pub enum QueryMsg {
         amount: Option<Uint256>,
     },
 
+    /// `(addr, is_whitelist, is_register)` for each address against the static
+    /// whitelist. All false when the round has no static whitelist.
+    #[returns(Vec<(Addr, bool, bool)>)]
+    WhitelistStatus { addrs: Vec<Addr> },
+
+    /// Number of voters signed up through the oracle (zero outside oracle rounds)
+    #[returns(Uint256)]
+    GetOracleSignupCount {},
+
+    /// The data returned by instantiate, as it was at instantiation time
+    #[returns(InstantiationData)]
+    GetInstantiationData {},
+
     // ── Aggregated fee/delay config getters ──────────────────────────────────
     #[returns(FeeConfigResponse)]
     GetFeeConfig {},
@@ -323,6 +418,26 @@ pub enum QueryMsg {
     /// Returns the stored Groth16 verifying keys for all circuits.
     #[returns(VkeysResponse)]
     GetVkeys {},
+
+    /// Returns the precomputed empty-subtree hashes used to build the state tree.
+    #[returns(TreeZeros)]
+    GetTreeZeros {},
+
+    /// Returns the round's static configuration in a single response.
+    #[returns(ConfigResponse)]
+    GetConfig {},
+
+    /// Returns the operator's recorded delays and the resulting miss rate.
+    #[returns(OperatorPerformance)]
+    GetOperatorPerformance {},
+
+    /// Whether StartProcessPeriod would succeed now, and if not, why.
+    #[returns(CanStartResponse)]
+    CanStartProcessing {},
+
+    /// Returns the current state, tally and deactivate commitments together.
+    #[returns(Commitments)]
+    GetCommitments {},
 }
 
 // Response type for GetRegistrationConfig query
@@ -353,11 +468,20 @@ pub struct TallyDelayInfo {
     pub calculated_hours: u64,
 }
 
+#[cw_serde]
+pub struct TallyWork {
+    // signups + published messages
+    pub total_work: u128,
+    // seconds after voting ends before StopTallyingPeriod records a tally delay
+    pub allowed_delay_seconds: u64,
+}
+
 #[cw_serde]
 pub struct FeeConfigResponse {
     pub message_fee: Uint128,
     pub deactivate_fee: Uint128,
     pub signup_fee: Uint128,
+    pub fee_denom: String,
 }
 
 #[cw_serde]
@@ -376,6 +500,63 @@ pub struct DelayConfigResponse {
     pub deactivate_delay: u64,
 }
 
+#[cw_serde]
+pub struct TreeZeros {
+    // zeros[i]: root of an empty depth-i subtree with a zero leaf
+    pub zeros: Vec<Uint256>,
+    // zeros_h10[i]: root of an empty depth-i subtree with an all-zero state leaf
+    pub zeros_h10: Vec<Uint256>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub parameters: MaciParameters,
+    pub circuit_type: Uint256,
+    pub certification_system: Uint256,
+    pub voice_credit_amount: Uint256,
+    pub voting_time: VotingTime,
+    pub max_vote_options: Uint256,
+    pub coordinator_hash: Uint256,
+    pub admin: Addr,
+    pub operator: Addr,
+    pub fee_recipient: Addr,
+}
+
+#[cw_serde]
+pub struct CanStartResponse {
+    pub can_start: bool,
+    pub reason: Option<String>,
+}
+
+/// Commitments that haven't been written yet are reported as zero; the state
+/// commitment is only set once StartProcessPeriod runs.
+#[cw_serde]
+pub struct Commitments {
+    pub state: Uint256,
+    pub tally: Uint256,
+    pub deactivate: Uint256,
+}
+
+/// Half-open range `[start, end)` of message indices
+#[cw_serde]
+pub struct BatchRange {
+    pub start: Uint256,
+    pub end: Uint256,
+}
+
+#[cw_serde]
+pub struct TallyBatch {
+    pub batch_num: Uint256,
+    pub batch_size: Uint256,
+}
+
+#[cw_serde]
+pub struct OperatorPerformance {
+    pub delay_deactivate_count: Uint256,
+    pub delay_tally_count: Uint256,
+    pub miss_rate: Uint256, // Miss rate, range 0-100, represents percentage of operator's deserved reward
+}
+
 #[cw_serde]
 pub struct InstantiationData {
     pub caller: Addr,
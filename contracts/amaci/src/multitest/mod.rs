@@ -10,7 +10,7 @@ use crate::state::{
     VotingTime, FEE_DENOM,
 };
 use crate::{
-    contract::{execute, instantiate, query},
+    contract::{execute, instantiate, query, reply},
     msg::*,
 };
 use maci_utils::uint256_from_hex_string;
@@ -92,16 +92,14 @@ impl MaciCodeId {
     }
 
     pub fn store_default_code(app: &mut DefaultApp) -> Self {
-        let contract =
-            // ContractWrapper::new(execute, instantiate, query).with_reply(reply);
-        ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
 
         let code_id = app.store_code(Box::new(contract));
         Self(code_id)
     }
 
     pub fn store_code(app: &mut App) -> Self {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
         let code_id = app.store_code(Box::new(contract));
         Self(code_id)
     }
@@ -285,6 +283,46 @@ impl MaciCodeId {
         user2: Addr,
         user3: Addr,
         label: &str,
+    ) -> AnyResult<MaciContract> {
+        self.instantiate_isqv_amaci_with_coordinator(
+            app,
+            sender,
+            user1,
+            user2,
+            user3,
+            test_pubkey1(),
+            label,
+        )
+    }
+
+    /// QV round matching src/test/amaci_test/logs.json, coordinated by the key
+    /// its proofs were generated with
+    pub fn instantiate_logs_fixture_round(
+        self,
+        app: &mut App,
+        label: &str,
+    ) -> AnyResult<MaciContract> {
+        self.instantiate_isqv_amaci_with_coordinator(
+            app,
+            owner(),
+            user1(),
+            user2(),
+            user3(),
+            logs_coordinator_pubkey(),
+            label,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn instantiate_isqv_amaci_with_coordinator(
+        self,
+        app: &mut App,
+        sender: Addr,
+        user1: Addr,
+        user2: Addr,
+        user3: Addr,
+        coordinator: PubKey,
+        label: &str,
     ) -> AnyResult<MaciContract> {
         let round_info = RoundInfo {
             title: String::from("HackWasm Berlin"),
@@ -319,6 +357,7 @@ impl MaciCodeId {
             app,
             self,
             sender,
+            coordinator,
             round_info,
             whitelist,
             voting_time,
@@ -392,6 +431,7 @@ impl MaciContract {
             admin: owner(),
             fee_recipient: fee_recipient(),
             poll_id: 1u64,
+            create_round_window: None,
             // Unified MACI Configuration
             voice_credit_mode: VoiceCreditMode::Unified {
                 amount: Uint256::from_u128(100u128),
@@ -402,6 +442,7 @@ impl MaciContract {
             message_fee: MESSAGE_FEE,
             deactivate_fee: DEACTIVATE_FEE,
             signup_fee: SIGNUP_FEE,
+            fee_denom: None,
             base_delay: BASE_DELAY,
             message_delay: PER_MESSAGE_DELAY,
             signup_delay: PER_SIGNUP_DELAY,
@@ -426,6 +467,7 @@ impl MaciContract {
         app: &mut App,
         code_id: MaciCodeId,
         sender: Addr,
+        coordinator: PubKey,
         round_info: RoundInfo,
         whitelist: Option<WhitelistBase>,
         voting_time: VotingTime,
@@ -441,14 +483,7 @@ impl MaciContract {
         };
         let init_msg = InstantiateMsg {
             parameters,
-            coordinator: PubKey {
-                x: uint256_from_decimal_string(
-                    "3557592161792765812904087712812111121909518311142005886657252371904276697771",
-                ),
-                y: uint256_from_decimal_string(
-                    "4363822302427519764561660537570341277214758164895027920046745209970137856681",
-                ),
-            },
+            coordinator,
             vote_option_map: vec![
                 "Option 1".to_string(),
                 "Option 2".to_string(),
@@ -464,6 +499,7 @@ impl MaciContract {
             admin: owner(),
             fee_recipient: fee_recipient(),
             poll_id: 1u64,
+            create_round_window: None,
             // Unified MACI Configuration
             voice_credit_mode: VoiceCreditMode::Unified {
                 amount: Uint256::from_u128(100u128),
@@ -474,6 +510,7 @@ impl MaciContract {
             message_fee: MESSAGE_FEE,
             deactivate_fee: DEACTIVATE_FEE,
             signup_fee: SIGNUP_FEE,
+            fee_denom: None,
             base_delay: BASE_DELAY,
             message_delay: PER_MESSAGE_DELAY,
             signup_delay: PER_SIGNUP_DELAY,
@@ -596,6 +633,85 @@ impl MaciContract {
         )
     }
 
+    #[track_caller]
+    pub fn extend_voting_time(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        new_end_time: Timestamp,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::ExtendVotingTime { new_end_time },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn set_fee_rate(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        fee_rate: u64,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetFeeRate { fee_rate },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn set_tally_delay_hours(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        min_hours: u64,
+        max_hours: u64,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetTallyDelayHours {
+                min_hours,
+                max_hours,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn remove_from_whitelist(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        addr: Addr,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::RemoveFromWhitelist { addr },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn set_oracle_whitelist_pubkey(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        pubkey: String,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetOracleWhitelistPubkey { pubkey },
+            &[],
+        )
+    }
+
     #[track_caller]
     pub fn set_empty_round_info(&self, app: &mut App, sender: Addr) -> AnyResult<AppResponse> {
         app.execute_contract(
@@ -659,6 +775,21 @@ impl MaciContract {
         )
     }
 
+    #[track_caller]
+    pub fn set_custom_vote_option_map(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        vote_option_map: Vec<String>,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetVoteOptionsMap { vote_option_map },
+            &[],
+        )
+    }
+
     #[track_caller]
     pub fn publish_deactivate_message(
         &self,
@@ -674,7 +805,7 @@ impl MaciContract {
                 message,
                 enc_pub_key,
             },
-            &[],
+            &coins(DEACTIVATE_FEE.u128(), FEE_DENOM),
         )
     }
 
@@ -818,7 +949,21 @@ impl MaciContract {
 
     #[track_caller]
     pub fn claim(&self, app: &mut App, sender: Addr) -> AnyResult<AppResponse> {
-        app.execute_contract(sender, self.addr(), &ExecuteMsg::Claim {}, &[])
+        self.claim_to(app, sender, None)
+    }
+
+    pub fn claim_to(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        reward_recipient: Option<Addr>,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::Claim { reward_recipient },
+            &[],
+        )
     }
 
     pub fn msg_length(&self, app: &App) -> StdResult<Uint256> {
@@ -841,11 +986,68 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::Signuped { pubkey })
     }
 
+    pub fn signuped_by_x(&self, app: &App, pubkey_x: Uint256) -> StdResult<Option<Uint256>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::SignupedByX { pubkey_x })
+    }
+
+    pub fn get_tally_delay(&self, app: &App) -> StdResult<TallyDelayInfo> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetTallyDelay {})
+    }
+
+    pub fn get_estimated_tally_work(&self, app: &App) -> StdResult<TallyWork> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetEstimatedTallyWork {})
+    }
+
+    pub fn is_tally_timed_out(&self, app: &App) -> StdResult<bool> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::IsTallyTimedOut {})
+    }
+
+    pub fn compute_results_root(&self, app: &App, results: Vec<Uint256>) -> StdResult<Uint256> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::ComputeResultsRoot { results })
+    }
+
+    pub fn get_first_dmsg_timestamp(&self, app: &App) -> StdResult<Option<Timestamp>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetFirstDmsgTimestamp {})
+    }
+
+    pub fn voice_credit_balance(&self, app: &App, index: Uint256) -> StdResult<Uint256> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetVoiceCreditBalance { index })
+    }
+
+    pub fn get_tree_zeros(&self, app: &App) -> StdResult<TreeZeros> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetTreeZeros {})
+    }
+
+    pub fn all_voice_credit_balances(
+        &self,
+        app: &App,
+        start: Uint256,
+        end: Uint256,
+    ) -> StdResult<Vec<Uint256>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetAllVoiceCreditBalances { start, end },
+        )
+    }
+
     pub fn vote_option_map(&self, app: &App) -> StdResult<Vec<String>> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::VoteOptionMap {})
     }
 
+    pub fn vote_options_indexed(&self, app: &App) -> StdResult<Vec<(u32, String)>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetVoteOptionsIndexed {})
+    }
+
     pub fn max_vote_options(&self, app: &App) -> StdResult<Uint256> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::MaxVoteOptions {})
@@ -876,6 +1078,18 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetDelayRecords {})
     }
 
+    pub fn query_delay_records_paginated(
+        &self,
+        app: &App,
+        start: u32,
+        limit: u32,
+    ) -> StdResult<DelayRecords> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetDelayRecordsPaginated { start, limit },
+        )
+    }
+
     pub fn query_admin(&self, app: &App) -> StdResult<Addr> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::Admin {})
@@ -891,6 +1105,65 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetRoundInfo {})
     }
 
+    pub fn get_config(&self, app: &App) -> StdResult<ConfigResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetConfig {})
+    }
+
+    pub fn can_start_processing(&self, app: &App) -> StdResult<CanStartResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::CanStartProcessing {})
+    }
+
+    pub fn get_instantiation_data(&self, app: &App) -> StdResult<InstantiationData> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetInstantiationData {})
+    }
+
+    pub fn get_oracle_signup_count(&self, app: &App) -> StdResult<Uint256> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetOracleSignupCount {})
+    }
+
+    pub fn get_fee_config(&self, app: &App) -> StdResult<FeeConfigResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetFeeConfig {})
+    }
+
+    pub fn get_commitments(&self, app: &App) -> StdResult<Commitments> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetCommitments {})
+    }
+
+    pub fn get_processed_msg_count(&self, app: &App) -> StdResult<Uint256> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetProcessedMsgCount {})
+    }
+
+    pub fn get_deactivate_node(&self, app: &App, index: Uint256) -> StdResult<Uint256> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetDeactivateNode { index })
+    }
+
+    pub fn get_next_process_batch(&self, app: &App) -> StdResult<BatchRange> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetNextProcessBatch {})
+    }
+
+    pub fn get_next_tally_batch(&self, app: &App) -> StdResult<TallyBatch> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetNextTallyBatch {})
+    }
+
+    pub fn whitelist_status(
+        &self,
+        app: &App,
+        addrs: Vec<Addr>,
+    ) -> StdResult<Vec<(Addr, bool, bool)>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::WhitelistStatus { addrs })
+    }
+
     #[track_caller]
     pub fn amaci_sign_up(
         &self,
@@ -1326,7 +1599,14 @@ impl MaciContract {
 
     #[track_caller]
     pub fn amaci_claim(&self, app: &mut DefaultApp, sender: Addr) -> AnyResult<AppResponse> {
-        app.execute_contract(sender, self.addr(), &ExecuteMsg::Claim {}, &[])
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::Claim {
+                reward_recipient: None,
+            },
+            &[],
+        )
     }
 
     pub fn amaci_msg_length(&self, app: &DefaultApp) -> StdResult<Uint256> {
@@ -1398,6 +1678,14 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetDelayRecords {})
     }
 
+    pub fn amaci_query_operator_performance(
+        &self,
+        app: &DefaultApp,
+    ) -> StdResult<OperatorPerformance> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetOperatorPerformance {})
+    }
+
     pub fn amaci_query_admin(&self, app: &DefaultApp) -> StdResult<Addr> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::Admin {})
@@ -1418,6 +1706,11 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetTallyDelay {})
     }
 
+    pub fn query_oracle_whitelist_config(&self, app: &App) -> StdResult<Option<String>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::QueryOracleWhitelistConfig {})
+    }
+
     pub fn amaci_get_registration_config(
         &self,
         app: &DefaultApp,
@@ -1486,6 +1779,7 @@ impl MaciContract {
             admin: owner(),
             fee_recipient: fee_recipient(),
             poll_id: 1u64,
+            create_round_window: None,
             // Unified MACI Configuration
             voice_credit_mode: VoiceCreditMode::Unified {
                 amount: Uint256::from_u128(100u128),
@@ -1496,6 +1790,7 @@ impl MaciContract {
             message_fee: MESSAGE_FEE,
             deactivate_fee: DEACTIVATE_FEE,
             signup_fee: SIGNUP_FEE,
+            fee_denom: None,
             base_delay: BASE_DELAY,
             message_delay: PER_MESSAGE_DELAY,
             signup_delay: PER_SIGNUP_DELAY,
@@ -1623,6 +1918,7 @@ impl MaciContract {
             admin: owner(),
             fee_recipient: fee_recipient(),
             poll_id: 1u64,
+            create_round_window: None,
             // Unified MACI Configuration
             voice_credit_mode: VoiceCreditMode::Unified {
                 amount: Uint256::from_u128(100u128),
@@ -1633,6 +1929,7 @@ impl MaciContract {
             message_fee: MESSAGE_FEE,
             deactivate_fee: DEACTIVATE_FEE,
             signup_fee: SIGNUP_FEE,
+            fee_denom: None,
             base_delay: BASE_DELAY,
             message_delay: PER_MESSAGE_DELAY,
             signup_delay: PER_SIGNUP_DELAY,
@@ -1652,6 +1949,159 @@ impl MaciContract {
     }
 }
 
+/// A voter registered during `run_full_round`
+pub struct RoundVoter {
+    pub addr: Addr,
+    pub pubkey: PubKey,
+}
+
+/// A message published during `run_full_round`
+pub struct RoundMessage {
+    pub sender: Addr,
+    pub message: MessageData,
+    pub enc_pub_key: PubKey,
+}
+
+/// A deactivate batch the operator proves during `run_full_round`
+pub struct RoundDeactivateBatch {
+    pub size: Uint256,
+    pub new_deactivate_commitment: Uint256,
+    pub new_deactivate_root: Uint256,
+    pub proof: Groth16ProofType,
+}
+
+/// A key rotated in through AddNewKey during `run_full_round`
+pub struct RoundNewKey {
+    pub sender: Addr,
+    pub pubkey: PubKey,
+    pub nullifier: Uint256,
+    pub d: [Uint256; 4],
+    pub proof: Groth16ProofType,
+}
+
+/// Deactivate phase of a round, run after signups and before messages
+#[derive(Default)]
+pub struct RoundDeactivation {
+    pub messages: Vec<RoundMessage>,
+    pub batches: Vec<RoundDeactivateBatch>,
+    pub new_keys: Vec<RoundNewKey>,
+}
+
+/// Operator outputs for a round: one entry per process/tally batch, plus the
+/// final results and salt revealed in `StopTallyingPeriod`
+#[derive(Default)]
+pub struct RoundProofs {
+    pub process: Vec<(Uint256, Groth16ProofType)>,
+    pub tally: Vec<(Uint256, Groth16ProofType)>,
+    pub results: Vec<Uint256>,
+    pub salt: Uint256,
+}
+
+/// Final on-chain state after `run_full_round`
+#[derive(Debug)]
+pub struct TallyResult {
+    pub period: Period,
+    pub results: Vec<Uint256>,
+    pub all_result: Uint256,
+    pub claim: AppResponse,
+}
+
+/// Signup, deactivate and publish phases of `run_full_round`, run with block
+/// time one second into the voting window
+#[track_caller]
+pub fn run_voting_phase(
+    app: &mut App,
+    contract: &MaciContract,
+    voters: &[RoundVoter],
+    deactivation: &RoundDeactivation,
+    messages: &[RoundMessage],
+) -> AnyResult<()> {
+    let voting_time = contract.get_voting_time(app)?;
+
+    app.update_block(|block| block.time = voting_time.start_time.plus_seconds(1));
+    for voter in voters {
+        contract.sign_up(app, voter.addr.clone(), voter.pubkey.clone())?;
+    }
+    for msg in &deactivation.messages {
+        contract.publish_deactivate_message(
+            app,
+            msg.sender.clone(),
+            msg.message.clone(),
+            msg.enc_pub_key.clone(),
+        )?;
+    }
+    for batch in &deactivation.batches {
+        contract.process_deactivate_message(
+            app,
+            operator(),
+            batch.size,
+            batch.new_deactivate_commitment,
+            batch.new_deactivate_root,
+            batch.proof.clone(),
+        )?;
+    }
+    for key in &deactivation.new_keys {
+        contract.add_key(
+            app,
+            key.sender.clone(),
+            key.pubkey.clone(),
+            key.nullifier,
+            key.d,
+            key.proof.clone(),
+        )?;
+    }
+    for msg in messages {
+        contract.publish_message(
+            app,
+            msg.sender.clone(),
+            msg.message.clone(),
+            msg.enc_pub_key.clone(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Drive a round through signup -> deactivate -> publish -> process -> tally
+/// -> claim.
+///
+/// Block time is moved past `end_time` after the voting phase. Any failing
+/// step aborts with its error so regressions surface at the phase that broke.
+#[track_caller]
+pub fn run_full_round(
+    app: &mut App,
+    contract: &MaciContract,
+    voters: &[RoundVoter],
+    deactivation: &RoundDeactivation,
+    messages: &[RoundMessage],
+    proofs: &RoundProofs,
+) -> AnyResult<TallyResult> {
+    run_voting_phase(app, contract, voters, deactivation, messages)?;
+
+    let voting_time = contract.get_voting_time(app)?;
+    app.update_block(|block| block.time = voting_time.end_time.plus_seconds(60));
+    contract.start_process(app, operator())?;
+    for (new_state_commitment, proof) in &proofs.process {
+        contract.process_message(app, operator(), *new_state_commitment, proof.clone())?;
+    }
+    contract.stop_processing(app, operator())?;
+
+    for (new_tally_commitment, proof) in &proofs.tally {
+        contract.process_tally(app, operator(), *new_tally_commitment, proof.clone())?;
+    }
+    contract.stop_tallying(app, operator(), proofs.results.clone(), proofs.salt)?;
+
+    let claim = contract.claim(app, operator())?;
+
+    Ok(TallyResult {
+        period: contract.get_period(app)?,
+        results: app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetAllResults {})?,
+        all_result: contract.get_all_result(app)?,
+        claim,
+    })
+}
+
 impl From<Addr> for MaciContract {
     fn from(value: Addr) -> Self {
         Self(value)
@@ -1705,6 +2155,18 @@ pub fn test_pubkey2() -> PubKey {
     }
 }
 
+// Coordinator key (private key 111111) behind src/test/amaci_test/logs.json
+pub fn logs_coordinator_pubkey() -> PubKey {
+    PubKey {
+        x: uint256_from_decimal_string(
+            "1421543221206310383340195030620766117814469350837741893103919053409918312818",
+        ),
+        y: uint256_from_decimal_string(
+            "5844095285348097514075616375079349318227570502756036806774906942110501360620",
+        ),
+    }
+}
+
 // BabyJubJub BASE8 generator point — a canonical valid curve point for test data
 pub fn test_pubkey3() -> PubKey {
     PubKey {
@@ -2,20 +2,24 @@
 mod test {
     use crate::error::ContractError;
     use crate::msg::{
-        ExecuteMsg, Groth16ProofType, InstantiateMsg, QueryMsg, RegistrationConfigInfo,
-        RegistrationConfigUpdate, RegistrationModeConfig, RegistrationStatus, WhitelistBase,
-        WhitelistBaseConfig,
+        BatchRange, ExecuteMsg, Groth16ProofType, InstantiateMsg, QueryMsg, RegistrationConfigInfo,
+        RegistrationConfigUpdate, RegistrationModeConfig, RegistrationStatus, TallyBatch,
+        WhitelistBase, WhitelistBaseConfig,
+    };
+    use crate::multitest::certificate_generator::{
+        generate_certificate_for_pubkey, generate_certificate_with_key, pubkey_b64_for_key,
     };
-    use crate::multitest::certificate_generator::generate_certificate_for_pubkey;
     use crate::multitest::{
         create_app, owner, test_oracle_pubkey, test_pubkey1, test_pubkey2, test_pubkey3,
         uint256_from_decimal_string, user1, user2, user3, BASE_DELAY, DEACTIVATE_DELAY,
         DEACTIVATE_FEE, MESSAGE_FEE, PER_MESSAGE_DELAY, PER_SIGNUP_DELAY, SIGNUP_FEE, MaciCodeId,
-        MaciContract,
+        MaciContract, operator, run_full_round, run_voting_phase, RoundDeactivateBatch, RoundDeactivation, RoundMessage,
+        RoundNewKey, RoundProofs, RoundVoter, App,
     };
     use crate::state::{
         DelayRecord, DelayRecords, DelayType, MaciParameters, MessageData, Period, PeriodStatus,
-        PubKey, RegistrationMode, RoundInfo, VoiceCreditMode, VotingTime,
+        PubKey, RegistrationMode, RoundInfo, VoiceCreditMode, VotingTime, FEE_RECIPIENT,
+        MACIPARAMETERS, PERIOD,
     };
     use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint256};
     use cw_multi_test::{next_block, Executor};
@@ -121,11 +125,9 @@ mod test {
 
     #[derive(Debug, Serialize, Deserialize)]
     struct Groth16Proof {
-        pi_a: Vec<String>,
-        pi_b: Vec<Vec<String>>,
-        pi_c: Vec<String>,
-        protocol: String,
-        curve: String,
+        pi_a: String,
+        pi_b: String,
+        pi_c: String,
     }
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -169,6 +171,184 @@ mod test {
         serde_json::from_value(data.clone()).expect("Unable to deserialize data")
     }
 
+    impl From<Groth16Proof> for Groth16ProofType {
+        fn from(proof: Groth16Proof) -> Self {
+            Groth16ProofType {
+                a: proof.pi_a,
+                b: proof.pi_b,
+                c: proof.pi_c,
+            }
+        }
+    }
+
+    fn pubkey_from_strings(point: &[String]) -> PubKey {
+        PubKey {
+            x: uint256_from_decimal_string(&point[0]),
+            y: uint256_from_decimal_string(&point[1]),
+        }
+    }
+
+    fn message_from_strings(message: &[String]) -> MessageData {
+        let mut data = [Uint256::zero(); 10];
+        for (slot, value) in data.iter_mut().zip(message) {
+            *slot = uint256_from_decimal_string(value);
+        }
+        MessageData { data }
+    }
+
+    /// Round recorded in src/test/amaci_test/logs.json by
+    /// packages/circuits/ts/generate-logs.ts: two voters sign up, both
+    /// deactivate, the first rotates to a new key, then three votes are
+    /// processed and tallied in one batch each.
+    struct LogsFixture {
+        voters: Vec<RoundVoter>,
+        deactivation: RoundDeactivation,
+        messages: Vec<RoundMessage>,
+        proofs: RoundProofs,
+    }
+
+    fn load_logs_fixture() -> LogsFixture {
+        let logs_content = fs::read_to_string("./src/test/amaci_test/logs.json")
+            .expect("Failed to read logs.json");
+        let logs_data: Vec<AMaciLogEntry> =
+            serde_json::from_str(&logs_content).expect("Failed to parse JSON");
+
+        // Leaf i is signed up by voter_addrs[i]; later leaves come from AddNewKey
+        let voter_addrs = [user1(), user2()];
+        let mut fixture = LogsFixture {
+            voters: vec![],
+            deactivation: RoundDeactivation::default(),
+            messages: vec![],
+            proofs: RoundProofs::default(),
+        };
+        for entry in &logs_data {
+            match entry.log_type.as_str() {
+                "setStateLeaf" => {
+                    let data: SetStateLeafData = deserialize_data(&entry.data);
+                    let leaf_idx: usize = data.leaf_idx.parse().unwrap();
+                    if let Some(addr) = voter_addrs.get(leaf_idx) {
+                        fixture.voters.push(RoundVoter {
+                            addr: addr.clone(),
+                            pubkey: pubkey_from_strings(&data.pub_key),
+                        });
+                    }
+                }
+                "publishDeactivateMessage" => {
+                    let data: PublishDeactivateMessageData = deserialize_data(&entry.data);
+                    let sender = voter_addrs[fixture.deactivation.messages.len()].clone();
+                    fixture.deactivation.messages.push(RoundMessage {
+                        sender,
+                        message: message_from_strings(&data.message),
+                        enc_pub_key: pubkey_from_strings(&data.enc_pub_key),
+                    });
+                }
+                "proofDeactivate" => {
+                    let data: ProofDeactivateData = deserialize_data(&entry.data);
+                    fixture.deactivation.batches.push(RoundDeactivateBatch {
+                        size: uint256_from_decimal_string(&data.size),
+                        new_deactivate_commitment: uint256_from_decimal_string(
+                            &data.new_deactivate_commitment,
+                        ),
+                        new_deactivate_root: uint256_from_decimal_string(&data.new_deactivate_root),
+                        proof: data.proof.into(),
+                    });
+                }
+                "proofAddNewKey" => {
+                    let data: ProofAddNewKeyData = deserialize_data(&entry.data);
+                    fixture.deactivation.new_keys.push(RoundNewKey {
+                        sender: user1(),
+                        pubkey: pubkey_from_strings(&data.pub_key),
+                        nullifier: uint256_from_decimal_string(&data.nullifier),
+                        d: [
+                            uint256_from_decimal_string(&data.d[0]),
+                            uint256_from_decimal_string(&data.d[1]),
+                            uint256_from_decimal_string(&data.d[2]),
+                            uint256_from_decimal_string(&data.d[3]),
+                        ],
+                        proof: data.proof.into(),
+                    });
+                }
+                "publishMessage" => {
+                    let data: PublishMessageData = deserialize_data(&entry.data);
+                    fixture.messages.push(RoundMessage {
+                        sender: user1(),
+                        message: message_from_strings(&data.message),
+                        enc_pub_key: pubkey_from_strings(&data.enc_pub_key),
+                    });
+                }
+                "processMessage" => {
+                    let data: ProcessMessageData = deserialize_data(&entry.data);
+                    fixture.proofs.process.push((
+                        uint256_from_decimal_string(&data.new_state_commitment),
+                        data.proof.into(),
+                    ));
+                }
+                "processTally" => {
+                    let data: ProcessTallyData = deserialize_data(&entry.data);
+                    fixture.proofs.tally.push((
+                        uint256_from_decimal_string(&data.new_tally_commitment),
+                        data.proof.into(),
+                    ));
+                }
+                "stopTallyingPeriod" => {
+                    let data: StopTallyingPeriodData = deserialize_data(&entry.data);
+                    fixture.proofs.results = data
+                        .results
+                        .iter()
+                        .map(|r| uint256_from_decimal_string(r))
+                        .collect();
+                    fixture.proofs.salt = uint256_from_decimal_string(&data.salt);
+                }
+                _ => println!("Unknown type: {}", entry.log_type),
+            }
+        }
+        fixture
+    }
+
+    /// Logs fixture round with every signup, deactivation and vote applied and
+    /// block time past `end_time`, ready for StartProcessPeriod
+    fn logs_fixture_round_after_voting(app: &mut App) -> (MaciContract, LogsFixture) {
+        let contract = MaciCodeId::store_code(app)
+            .instantiate_logs_fixture_round(app, "logs fixture")
+            .unwrap();
+        let fixture = load_logs_fixture();
+        run_voting_phase(
+            app,
+            &contract,
+            &fixture.voters,
+            &fixture.deactivation,
+            &fixture.messages,
+        )
+        .unwrap();
+        let end_time = contract.get_voting_time(app).unwrap().end_time;
+        app.update_block(|block| block.time = end_time.plus_seconds(60));
+        (contract, fixture)
+    }
+
+    /// Logs fixture round still in the voting window, with both deactivate
+    /// messages processed and the recorded AddNewKey not yet submitted
+    fn logs_fixture_round_before_add_key(app: &mut App) -> (MaciContract, LogsFixture) {
+        let contract = MaciCodeId::store_code(app)
+            .instantiate_logs_fixture_round(app, "logs fixture")
+            .unwrap();
+        let mut fixture = load_logs_fixture();
+        let new_keys = std::mem::take(&mut fixture.deactivation.new_keys);
+        run_voting_phase(app, &contract, &fixture.voters, &fixture.deactivation, &[]).unwrap();
+        fixture.deactivation.new_keys = new_keys;
+        (contract, fixture)
+    }
+
+    fn wasm_attr(res: &cw_multi_test::AppResponse, key: &str) -> String {
+        res.events
+            .iter()
+            .filter(|e| e.ty == "wasm")
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == key)
+            .unwrap_or_else(|| panic!("missing attribute {key}"))
+            .value
+            .clone()
+    }
+
     pub fn next_block_11_min(block: &mut BlockInfo) {
         block.time = block.time.plus_minutes(11);
         block.height += 1;
@@ -393,9 +573,30 @@ mod test {
             "process_message new state commitment {:?}",
             new_state_commitment
         );
+        // All 3 messages fit in one batch of 5
+        let batch = contract.get_next_process_batch(&app).unwrap();
+        assert_eq!(
+            batch,
+            BatchRange {
+                start: Uint256::zero(),
+                end: Uint256::from_u128(3u128),
+            }
+        );
         _ = contract
             .process_message(&mut app, owner(), new_state_commitment, proof)
             .unwrap();
+        assert_eq!(
+            contract.get_commitments(&app).unwrap().state,
+            new_state_commitment
+        );
+        assert_eq!(
+            contract.get_processed_msg_count(&app).unwrap(),
+            batch.end - batch.start
+        );
+        let next_batch_error = contract.get_next_process_batch(&app).unwrap_err();
+        assert!(next_batch_error
+            .to_string()
+            .contains(&ContractError::AllMessagesProcessed {}.to_string()));
 
         _ = contract.stop_processing(&mut app, owner());
         println!(
@@ -432,9 +633,17 @@ mod test {
             c: "0d6a9f2eb8cfb28368bf6976f2925a3fb8ac0ead8dc95fc9a79318d0518f24801dced0525cbb2f15f24198bfe3f77c1065120be9dcbc3d10c77ca5861c410910".to_string()
         };
 
+        let tally_batch = contract.get_next_tally_batch(&app).unwrap();
+        assert_eq!(tally_batch.batch_num, Uint256::zero());
+        assert_eq!(tally_batch.batch_size, Uint256::from_u128(5u128));
         _ = contract
             .process_tally(&mut app, owner(), new_tally_commitment, tally_proof)
             .unwrap();
+        // Both users fit in the first batch
+        assert!(contract.get_next_tally_batch(&app).is_err());
+        let commitments = contract.get_commitments(&app).unwrap();
+        assert_eq!(commitments.state, new_state_commitment);
+        assert_eq!(commitments.tally, new_tally_commitment);
 
         let results: Vec<Uint256> = result_data
             .results
@@ -949,6 +1158,10 @@ mod test {
                             proof,
                         )
                         .unwrap();
+                    assert_eq!(
+                        contract.get_deactivate_node(&app, Uint256::zero()).unwrap(),
+                        new_deactivate_root
+                    );
                 }
                 "proofAddNewKey" => {
                     let data: ProofAddNewKeyData = deserialize_data(&entry.data);
@@ -974,9 +1187,17 @@ mod test {
                                 };
 
                     println!("add_new_key proof {:?}", proof);
-                    _ = contract
-                        .pre_add_key(&mut app, owner(), new_key_pub, nullifier, d, proof)
+                    let res = contract
+                        .pre_add_key(&mut app, owner(), new_key_pub.clone(), nullifier, d, proof)
                         .unwrap();
+                    assert_eq!(
+                        uint256_from_decimal_string(&wasm_attr(&res, "pubkey_x")),
+                        new_key_pub.x
+                    );
+                    assert_eq!(
+                        uint256_from_decimal_string(&wasm_attr(&res, "pubkey_y")),
+                        new_key_pub.y
+                    );
                 }
                 "publishMessage" => {
                     let data: PublishMessageData = deserialize_data(&entry.data);
@@ -1299,6 +1520,7 @@ mod test {
             Some(Uint256::from_u128(1u128))
         );
 
+        let mut deactivate_root: Option<Uint256> = None;
         for entry in &logs_data {
             match entry.log_type.as_str() {
                 // "setStateLeaf" => {
@@ -1364,6 +1586,7 @@ mod test {
                             proof,
                         )
                         .unwrap();
+                    deactivate_root = Some(new_deactivate_root);
                 }
                 "proofAddNewKey" => {
                     let data: ProofAddNewKeyData = deserialize_data(&entry.data);
@@ -1389,9 +1612,20 @@ mod test {
                                 };
 
                     println!("add_new_key proof {:?}", proof);
-                    _ = contract
+                    let resp = contract
                         .add_key(&mut app, owner(), new_key_pub, nullifier, d, proof)
                         .unwrap();
+
+                    // The emitted root must match the deactivate tree node 0 set by
+                    // the preceding ProcessDeactivateMessage
+                    let deactivate_root = deactivate_root.expect("proofDeactivate runs first");
+                    let emitted_root = resp
+                        .events
+                        .iter()
+                        .flat_map(|e| e.attributes.iter())
+                        .find(|a| a.key == "new_deactivate_root")
+                        .map(|a| a.value.clone());
+                    assert_eq!(emitted_root, Some(deactivate_root.to_string()));
                 }
                 "publishMessage" => {
                     let data: PublishMessageData = deserialize_data(&entry.data);
@@ -2130,6 +2364,57 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_query_all_voice_credit_balances() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let user1 = user1();
+        let user2 = user2();
+
+        let maci_contract = code_id
+            .instantiate_with_voting_time(&mut app, owner(), user1.clone(), user2.clone(), "test")
+            .unwrap();
+        app.update_block(next_block);
+
+        // No signups yet - range is clamped to an empty result
+        let balances = maci_contract
+            .all_voice_credit_balances(&app, Uint256::zero(), Uint256::from_u128(10))
+            .unwrap();
+        assert!(balances.is_empty());
+
+        maci_contract
+            .sign_up(&mut app, user1.clone(), test_pubkey1())
+            .unwrap();
+        maci_contract
+            .sign_up(&mut app, user2.clone(), test_pubkey2())
+            .unwrap();
+
+        let expected: Vec<Uint256> = (0..2u128)
+            .map(|i| {
+                maci_contract
+                    .voice_credit_balance(&app, Uint256::from_u128(i))
+                    .unwrap()
+            })
+            .collect();
+        assert!(expected.iter().all(|balance| !balance.is_zero()));
+
+        // End past num_signups is clamped
+        let balances = maci_contract
+            .all_voice_credit_balances(&app, Uint256::zero(), Uint256::from_u128(10))
+            .unwrap();
+        assert_eq!(balances, expected);
+
+        let balances = maci_contract
+            .all_voice_credit_balances(&app, Uint256::from_u128(1), Uint256::from_u128(2))
+            .unwrap();
+        assert_eq!(balances, expected[1..].to_vec());
+
+        let balances = maci_contract
+            .all_voice_credit_balances(&app, Uint256::from_u128(2), Uint256::from_u128(1))
+            .unwrap();
+        assert!(balances.is_empty());
+    }
+
     // Note: Oracle whitelist test omitted as it requires complex setup.
     // The signuped query functionality for oracle mode is tested implicitly
     // in the existing comprehensive amaci tests.
@@ -3071,21 +3356,16 @@ mod test {
         );
     }
 
-    #[test]
-    fn test_update_registration_config_switch_to_signup_mode() {
-        let mut app = create_app();
-
-        // Start with PrePopulated mode
-        let code_id = MaciCodeId::store_code(&mut app);
-        let parameters = MaciParameters {
-            state_tree_depth: Uint256::from_u128(2u128),
-            int_state_tree_depth: Uint256::from_u128(1u128),
-            message_batch_size: Uint256::from_u128(5u128),
-            vote_option_tree_depth: Uint256::from_u128(1u128),
-        };
-
-        let init_msg = InstantiateMsg {
-            parameters,
+    // Same round as instantiate_default; tests override single fields with `..`
+    fn default_init_msg() -> InstantiateMsg {
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        InstantiateMsg {
+            parameters: MaciParameters {
+                state_tree_depth: Uint256::from_u128(2u128),
+                int_state_tree_depth: Uint256::from_u128(1u128),
+                message_batch_size: Uint256::from_u128(5u128),
+                vote_option_tree_depth: Uint256::from_u128(1u128),
+            },
             coordinator: PubKey {
                 x: uint256_from_decimal_string(
                     "3557592161792765812904087712812111121909518311142005886657252371904276697771",
@@ -3101,9 +3381,10 @@ mod test {
                 link: "".to_string(),
             },
             voting_time: VotingTime {
-                start_time: Timestamp::from_nanos(1571797424879000000),
-                end_time: Timestamp::from_nanos(1571797424879000000).plus_minutes(11),
+                start_time,
+                end_time: start_time.plus_minutes(11),
             },
+            create_round_window: None,
             circuit_type: Uint256::from_u128(0),
             certification_system: Uint256::from_u128(0),
             operator: owner(),
@@ -3113,154 +3394,435 @@ mod test {
             voice_credit_mode: VoiceCreditMode::Unified {
                 amount: Uint256::from_u128(100),
             },
-            registration_mode: RegistrationModeConfig::PrePopulated {
-                pre_deactivate_root: Uint256::from_u128(12345),
-                pre_deactivate_coordinator: test_pubkey2(),
+            registration_mode: RegistrationModeConfig::SignUpWithStaticWhitelist {
+                whitelist: WhitelistBase { users: vec![] },
             },
             message_fee: MESSAGE_FEE,
             deactivate_fee: DEACTIVATE_FEE,
             signup_fee: SIGNUP_FEE,
+            fee_denom: None,
             base_delay: BASE_DELAY,
             message_delay: PER_MESSAGE_DELAY,
             signup_delay: PER_SIGNUP_DELAY,
             deactivate_delay: DEACTIVATE_DELAY,
             deactivate_enabled: false,
-        };
-
-        let contract_addr = app
-            .instantiate_contract(
-                code_id.0,
-                owner(),
-                &init_msg,
-                &[],
-                "MACI PrePopulated",
-                None,
-            )
-            .unwrap();
-
-        let contract = MaciContract::new(contract_addr.clone());
+        }
+    }
 
-        // Query initial pre_deactivate_root (should be 12345)
-        let initial_root: Uint256 = app
-            .wrap()
-            .query_wasm_smart(contract.addr(), &QueryMsg::QueryPreDeactivateRoot {})
-            .unwrap();
-        assert_eq!(initial_root, Uint256::from_u128(12345));
+    #[test]
+    fn test_instantiate_rejects_off_curve_coordinator() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
 
-        // Switch to SignUp mode with StaticWhitelist
-        let config = RegistrationConfigUpdate {
-            deactivate_enabled: None,
-            voice_credit_mode: None,
-            registration_mode: Some(RegistrationModeConfig::SignUpWithStaticWhitelist {
-                whitelist: WhitelistBase { users: vec![] },
-            }),
+        let init_msg = InstantiateMsg {
+            // (1, 2) does not satisfy the Baby Jubjub curve equation
+            coordinator: PubKey {
+                x: Uint256::from_u128(1),
+                y: Uint256::from_u128(2),
+            },
+            ..default_init_msg()
         };
 
-        let _ = contract
-            .update_registration_config(&mut app, owner(), config)
-            .expect("Should successfully switch to SignUp mode");
-
-        // Verify pre_deactivate_root is cleared (should be 0)
-        let new_root: Uint256 = app
-            .wrap()
-            .query_wasm_smart(contract.addr(), &QueryMsg::QueryPreDeactivateRoot {})
-            .unwrap();
+        let err = app
+            .instantiate_contract(code_id.0, owner(), &init_msg, &[], "MACI", None)
+            .unwrap_err();
         assert_eq!(
-            new_root,
-            Uint256::zero(),
-            "Pre-deactivate root should be cleared"
+            ContractError::InvalidCoordinatorKey {},
+            err.downcast().unwrap()
         );
     }
 
     #[test]
-    fn test_update_registration_config_switch_to_prepopulated_mode() {
+    fn test_query_tree_zeros() {
         let mut app = create_app();
-        let maci_contract = MaciContract::instantiate_default(&mut app, false).unwrap();
-
-        // Switch from SignUp to PrePopulated mode
-        let config = RegistrationConfigUpdate {
-            deactivate_enabled: None,
-            voice_credit_mode: None,
-            registration_mode: Some(RegistrationModeConfig::PrePopulated {
-                pre_deactivate_root: Uint256::from_u128(99999),
-                pre_deactivate_coordinator: test_pubkey2(),
-            }),
-        };
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
 
-        let _ = maci_contract
-            .update_registration_config(&mut app, owner(), config)
-            .expect("Should successfully switch to PrePopulated mode");
+        let tree_zeros = contract.get_tree_zeros(&app).unwrap();
+        assert_eq!(tree_zeros.zeros.len(), 12);
+        assert_eq!(tree_zeros.zeros_h10.len(), 10);
 
-        // Verify pre_deactivate_root is set
-        let new_root: Uint256 = app
-            .wrap()
-            .query_wasm_smart(maci_contract.addr(), &QueryMsg::QueryPreDeactivateRoot {})
-            .unwrap();
+        assert_eq!(tree_zeros.zeros[0], Uint256::zero());
         assert_eq!(
-            new_root,
-            Uint256::from_u128(99999),
-            "Pre-deactivate root should be set"
+            tree_zeros.zeros[1],
+            uint256_from_decimal_string(
+                "14655542659562014735865511769057053982292279840403315552050801315682099828156"
+            )
+        );
+        assert_eq!(
+            tree_zeros.zeros_h10[0],
+            uint256_from_decimal_string(
+                "17275449213996161510934492606295966958609980169974699290756906233261208992839"
+            )
         );
 
-        // Verify coordinator hash is set
-        let coordinator_hash: Option<Uint256> = app
+        // Each level is the quinary hash of five copies of the level below
+        for zeros in [&tree_zeros.zeros, &tree_zeros.zeros_h10] {
+            for i in 1..zeros.len() {
+                assert_eq!(zeros[i], maci_utils::hash5([zeros[i - 1]; 5]));
+            }
+        }
+
+        // The empty state tree root (depth 2) matches zeros_h10
+        let root: Uint256 = app
             .wrap()
             .query_wasm_smart(
-                maci_contract.addr(),
-                &QueryMsg::QueryPreDeactivateCoordinatorHash {},
+                contract.addr(),
+                &QueryMsg::GetNode {
+                    index: Uint256::zero(),
+                },
             )
             .unwrap();
-        assert!(coordinator_hash.is_some(), "Coordinator hash should be set");
+        assert_eq!(root, tree_zeros.zeros_h10[2]);
     }
 
-    #[test]
-    fn test_update_registration_mode_multiple_times_before_voting() {
-        // 测试：在投票开始前且无用户注册时，可以多次修改 registration_mode
-        let mut app = create_app();
-        let maci_contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+    /// Stand-in for registry/saas: instantiates amaci as a reply-on-success
+    /// submessage and returns the child's instantiate data from its reply.
+    mod parent {
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::{
+            Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdError, StdResult,
+            SubMsg, WasmMsg,
+        };
 
-        // Set block time to be before voting period starts
-        app.update_block(|block| {
-            block.time = Timestamp::from_nanos(1571797424879000000 - 5 * 60_000_000_000);
-        });
+        pub const CREATE_CHILD_REPLY_ID: u64 = 1;
 
-        // Update 1: Switch to Oracle mode
-        let config1 = RegistrationConfigUpdate {
-            deactivate_enabled: None,
-            voice_credit_mode: None,
-            registration_mode: Some(RegistrationModeConfig::SignUpWithOracle {
-                oracle_pubkey: "test_oracle_key_123".to_string(),
-            }),
-        };
+        #[cw_serde]
+        pub struct CreateChild {
+            pub code_id: u64,
+            pub msg: Binary,
+        }
 
-        let _ = maci_contract
-            .update_registration_config(&mut app, owner(), config1)
-            .expect("Should allow switching to Oracle mode before voting");
+        pub fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+            Ok(Response::new())
+        }
 
-        // Verify registration mode is Oracle
-        let reg_config: RegistrationConfigInfo = app
-            .wrap()
-            .query_wasm_smart(maci_contract.addr(), &QueryMsg::GetRegistrationConfig {})
-            .unwrap();
+        pub fn execute(
+            _: DepsMut,
+            _: Env,
+            _: MessageInfo,
+            msg: CreateChild,
+        ) -> StdResult<Response> {
+            let instantiate = WasmMsg::Instantiate {
+                admin: None,
+                code_id: msg.code_id,
+                msg: msg.msg,
+                funds: vec![],
+                label: "child".to_string(),
+            };
+            Ok(Response::new()
+                .add_submessage(SubMsg::reply_on_success(instantiate, CREATE_CHILD_REPLY_ID)))
+        }
 
-        assert!(
-            matches!(
-                reg_config.registration_mode,
-                RegistrationMode::SignUpWithOracle { .. }
-            ),
-            "Should be Oracle mode"
-        );
+        pub fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+            Err(StdError::generic_err("no queries"))
+        }
 
-        // Update 2: Switch back to Whitelist mode
-        let config2 = RegistrationConfigUpdate {
-            deactivate_enabled: None,
-            voice_credit_mode: None,
-            registration_mode: Some(RegistrationModeConfig::SignUpWithStaticWhitelist {
-                whitelist: WhitelistBase {
-                    users: vec![WhitelistBaseConfig {
-                        addr: user1(),
-                        voice_credit_amount: None,
-                    }],
+        pub fn reply(_: DepsMut, _: Env, reply: Reply) -> StdResult<Response> {
+            assert_eq!(reply.id, CREATE_CHILD_REPLY_ID);
+            let res = cw_utils::parse_reply_instantiate_data(reply)
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+            Ok(Response::new().set_data(res.data.unwrap_or_default()))
+        }
+    }
+
+    #[test]
+    fn test_parent_reads_instantiation_data_from_reply() {
+        use crate::msg::InstantiationData;
+        use cosmwasm_std::{from_json, to_json_binary, Empty};
+        use cw_multi_test::ContractWrapper;
+
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let parent_code_id = app.store_code(Box::new(
+            ContractWrapper::new(parent::execute, parent::instantiate, parent::query)
+                .with_reply(parent::reply),
+        ));
+        let parent_addr = app
+            .instantiate_contract(parent_code_id, owner(), &Empty {}, &[], "parent", None)
+            .unwrap();
+
+        let init_msg = default_init_msg();
+        let res = app
+            .execute_contract(
+                owner(),
+                parent_addr.clone(),
+                &parent::CreateChild {
+                    code_id: code_id.0,
+                    msg: to_json_binary(&init_msg).unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        let data: InstantiationData = from_json(res.data.unwrap()).unwrap();
+        assert_eq!(data.caller, parent_addr);
+        assert_eq!(data.admin, init_msg.admin);
+        assert_eq!(data.operator, init_msg.operator);
+        assert_eq!(data.coordinator, init_msg.coordinator);
+        assert_eq!(data.voting_time, init_msg.voting_time);
+        assert_eq!(data.round_info, init_msg.round_info);
+        assert_eq!(data.poll_id, init_msg.poll_id);
+    }
+
+    #[test]
+    fn test_get_instantiation_data() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let init_msg = default_init_msg();
+        let contract = MaciContract::from(
+            app.instantiate_contract(code_id.0, user3(), &init_msg, &[], "MACI", None)
+                .unwrap(),
+        );
+
+        let data = contract.get_instantiation_data(&app).unwrap();
+        assert_eq!(data.caller, user3());
+        assert_eq!(data.parameters, init_msg.parameters);
+        assert_eq!(data.coordinator, init_msg.coordinator);
+        assert_eq!(data.admin, init_msg.admin);
+        assert_eq!(data.operator, init_msg.operator);
+        assert_eq!(data.vote_option_map, init_msg.vote_option_map);
+        assert_eq!(data.round_info, init_msg.round_info);
+        assert_eq!(data.voting_time, init_msg.voting_time);
+        assert_eq!(data.circuit_type, init_msg.circuit_type.to_string());
+        assert_eq!(
+            data.certification_system,
+            init_msg.certification_system.to_string()
+        );
+        assert_eq!(data.poll_id, init_msg.poll_id);
+        assert_eq!(data.deactivate_enabled, init_msg.deactivate_enabled);
+        assert_eq!(data.voice_credit_mode, init_msg.voice_credit_mode);
+        assert_eq!(
+            data.registration_mode,
+            RegistrationMode::SignUpWithStaticWhitelist
+        );
+    }
+
+    #[test]
+    fn test_instantiate_rejects_unsupported_state_tree_depth() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+
+        // 10 is past the zero tables, 1 below any circuit, and 60 would overflow 5^depth
+        for depth in [10u128, 1, 60] {
+            let mut init_msg = default_init_msg();
+            init_msg.parameters.state_tree_depth = Uint256::from_u128(depth);
+            let err = app
+                .instantiate_contract(code_id.0, owner(), &init_msg, &[], "MACI", None)
+                .unwrap_err();
+            assert_eq!(
+                ContractError::UnsupportedStateTreeDepth {
+                    depth: Uint256::from_u128(depth),
+                    min: 2,
+                    max: 9,
+                },
+                err.downcast().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_instantiate_with_custom_create_round_window() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let init_msg = |voting_minutes: u64, create_round_window: Option<u64>| {
+            let start_time = Timestamp::from_nanos(1571797424879000000);
+            InstantiateMsg {
+                voting_time: VotingTime {
+                    start_time,
+                    end_time: start_time.plus_minutes(voting_minutes),
+                },
+                create_round_window,
+                ..default_init_msg()
+            }
+        };
+
+        // A 5-minute round is rejected by the default 10-minute window
+        let err = app
+            .instantiate_contract(code_id.0, owner(), &init_msg(5, None), &[], "MACI", None)
+            .unwrap_err();
+        assert_eq!(ContractError::WrongTimeSet {}, err.downcast().unwrap());
+
+        // ... but accepted with a 1-minute window
+        app.instantiate_contract(
+            code_id.0,
+            owner(),
+            &init_msg(5, Some(60)),
+            &[],
+            "MACI",
+            None,
+        )
+        .unwrap();
+
+        // A voting period no longer than the window is still rejected
+        let err = app
+            .instantiate_contract(
+                code_id.0,
+                owner(),
+                &init_msg(1, Some(60)),
+                &[],
+                "MACI",
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::WrongTimeSet {}, err.downcast().unwrap());
+
+        let err = app
+            .instantiate_contract(code_id.0, owner(), &init_msg(5, Some(0)), &[], "MACI", None)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidCreateRoundWindow {},
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_update_registration_config_switch_to_signup_mode() {
+        let mut app = create_app();
+
+        // Start with PrePopulated mode
+        let code_id = MaciCodeId::store_code(&mut app);
+        let init_msg = InstantiateMsg {
+            registration_mode: RegistrationModeConfig::PrePopulated {
+                pre_deactivate_root: Uint256::from_u128(12345),
+                pre_deactivate_coordinator: test_pubkey2(),
+            },
+            ..default_init_msg()
+        };
+
+        let contract_addr = app
+            .instantiate_contract(
+                code_id.0,
+                owner(),
+                &init_msg,
+                &[],
+                "MACI PrePopulated",
+                None,
+            )
+            .unwrap();
+
+        let contract = MaciContract::new(contract_addr.clone());
+
+        // Query initial pre_deactivate_root (should be 12345)
+        let initial_root: Uint256 = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::QueryPreDeactivateRoot {})
+            .unwrap();
+        assert_eq!(initial_root, Uint256::from_u128(12345));
+
+        // Switch to SignUp mode with StaticWhitelist
+        let config = RegistrationConfigUpdate {
+            deactivate_enabled: None,
+            voice_credit_mode: None,
+            registration_mode: Some(RegistrationModeConfig::SignUpWithStaticWhitelist {
+                whitelist: WhitelistBase { users: vec![] },
+            }),
+        };
+
+        let _ = contract
+            .update_registration_config(&mut app, owner(), config)
+            .expect("Should successfully switch to SignUp mode");
+
+        // Verify pre_deactivate_root is cleared (should be 0)
+        let new_root: Uint256 = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::QueryPreDeactivateRoot {})
+            .unwrap();
+        assert_eq!(
+            new_root,
+            Uint256::zero(),
+            "Pre-deactivate root should be cleared"
+        );
+    }
+
+    #[test]
+    fn test_update_registration_config_switch_to_prepopulated_mode() {
+        let mut app = create_app();
+        let maci_contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        // Switch from SignUp to PrePopulated mode
+        let config = RegistrationConfigUpdate {
+            deactivate_enabled: None,
+            voice_credit_mode: None,
+            registration_mode: Some(RegistrationModeConfig::PrePopulated {
+                pre_deactivate_root: Uint256::from_u128(99999),
+                pre_deactivate_coordinator: test_pubkey2(),
+            }),
+        };
+
+        let _ = maci_contract
+            .update_registration_config(&mut app, owner(), config)
+            .expect("Should successfully switch to PrePopulated mode");
+
+        // Verify pre_deactivate_root is set
+        let new_root: Uint256 = app
+            .wrap()
+            .query_wasm_smart(maci_contract.addr(), &QueryMsg::QueryPreDeactivateRoot {})
+            .unwrap();
+        assert_eq!(
+            new_root,
+            Uint256::from_u128(99999),
+            "Pre-deactivate root should be set"
+        );
+
+        // Verify coordinator hash is set
+        let coordinator_hash: Option<Uint256> = app
+            .wrap()
+            .query_wasm_smart(
+                maci_contract.addr(),
+                &QueryMsg::QueryPreDeactivateCoordinatorHash {},
+            )
+            .unwrap();
+        assert!(coordinator_hash.is_some(), "Coordinator hash should be set");
+    }
+
+    #[test]
+    fn test_update_registration_mode_multiple_times_before_voting() {
+        // 测试：在投票开始前且无用户注册时，可以多次修改 registration_mode
+        let mut app = create_app();
+        let maci_contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+
+        // Set block time to be before voting period starts
+        app.update_block(|block| {
+            block.time = Timestamp::from_nanos(1571797424879000000 - 5 * 60_000_000_000);
+        });
+
+        // Update 1: Switch to Oracle mode
+        let config1 = RegistrationConfigUpdate {
+            deactivate_enabled: None,
+            voice_credit_mode: None,
+            registration_mode: Some(RegistrationModeConfig::SignUpWithOracle {
+                oracle_pubkey: "test_oracle_key_123".to_string(),
+            }),
+        };
+
+        let _ = maci_contract
+            .update_registration_config(&mut app, owner(), config1)
+            .expect("Should allow switching to Oracle mode before voting");
+
+        // Verify registration mode is Oracle
+        let reg_config: RegistrationConfigInfo = app
+            .wrap()
+            .query_wasm_smart(maci_contract.addr(), &QueryMsg::GetRegistrationConfig {})
+            .unwrap();
+
+        assert!(
+            matches!(
+                reg_config.registration_mode,
+                RegistrationMode::SignUpWithOracle { .. }
+            ),
+            "Should be Oracle mode"
+        );
+
+        // Update 2: Switch back to Whitelist mode
+        let config2 = RegistrationConfigUpdate {
+            deactivate_enabled: None,
+            voice_credit_mode: None,
+            registration_mode: Some(RegistrationModeConfig::SignUpWithStaticWhitelist {
+                whitelist: WhitelistBase {
+                    users: vec![WhitelistBaseConfig {
+                        addr: user1(),
+                        voice_credit_amount: None,
+                    }],
                 },
             }),
         };
@@ -3508,6 +4070,40 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_query_registration_status_malformed_certificate() {
+        let mut app = create_app();
+        let contract = instantiate_oracle_round(&mut app);
+
+        let query = |certificate: &str| {
+            app.wrap()
+                .query_wasm_smart::<RegistrationStatus>(
+                    contract.addr(),
+                    &QueryMsg::QueryRegistrationStatus {
+                        sender: None,
+                        pubkey: Some(test_pubkey1()),
+                        certificate: Some(certificate.to_string()),
+                        amount: None,
+                    },
+                )
+                .unwrap_err()
+                .to_string()
+        };
+
+        let err = query("not base64!");
+        assert!(
+            err.contains(&ContractError::InvalidBase64 {}.to_string()),
+            "{err}"
+        );
+
+        // Decodes fine, but is not a 64-byte signature
+        let err = query("aGVsbG8gd29ybGQ=");
+        assert!(
+            err.contains(&ContractError::VerificationFailed {}.to_string()),
+            "{err}"
+        );
+    }
+
     // ========== enc_pub_key Uniqueness Tests ==========
 
     /// Sending two separate publish_message calls with the same enc_pub_key must fail
@@ -3805,6 +4401,7 @@ mod test {
     fn test_process_deactivate_mismatched_proof_returns_error() {
         let (mut app, contract, (size, commitment, root)) =
             setup_contract_with_deactivate_message();
+        let root_before = contract.get_deactivate_node(&app, Uint256::zero()).unwrap();
 
         // Use the addKey proof bytes as a deliberately wrong proof for ProcessDeactivate.
         // The IC values for the deactivate circuit differ from those for the addKey
@@ -3871,6 +4468,12 @@ mod test {
             "Second call must also return InvalidProof (not AllDeactivateMessagesProcessed), \
              confirming that processed_dmsg_count was rolled back by the first failure"
         );
+
+        // The rejected proofs must not have touched the deactivate root
+        assert_eq!(
+            contract.get_deactivate_node(&app, Uint256::zero()).unwrap(),
+            root_before
+        );
     }
 
     /// Verify that PreAddNewKey with a mismatched proof returns
@@ -4213,26 +4816,115 @@ mod test {
         );
     }
 
-    // stop_tallying computes the elapsed time since end_time with saturating_sub.
-    // If the block time is earlier than end_time it must still finalize cleanly
-    // without panicking.
+    // A round with no signups needs no process/tally proofs, so every phase
+    // runs with an empty proof set while still collecting message fees for the
+    // final claim.
     #[test]
-    fn stop_tallying_does_not_panic_when_block_time_before_end_time() {
+    fn run_full_round_completes_lifecycle() {
         let mut app = create_app();
         let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
 
-        // Advance past the voting window so the round can be processed.
-        app.update_block(|block| {
-            block.time = Timestamp::from_nanos(1571797424879000000).plus_minutes(12);
-        });
-        contract.start_process(&mut app, owner()).unwrap();
-        contract.stop_processing(&mut app, owner()).unwrap();
+        let messages = vec![RoundMessage {
+            sender: user3(),
+            message: MessageData {
+                data: [Uint256::from_u128(1); 10],
+            },
+            enc_pub_key: test_pubkey1(),
+        }];
+        let proofs = RoundProofs {
+            results: vec![Uint256::zero(); 5],
+            ..Default::default()
+        };
 
-        // Rewind the block time to BEFORE end_time (end_time = start + 11 min),
-        // exercising the saturating path where current_time < end_time.
-        app.update_block(|block| {
-            block.time = Timestamp::from_nanos(1571797424879000000).plus_minutes(5);
-        });
+        let tally = run_full_round(
+            &mut app,
+            &contract,
+            &[],
+            &RoundDeactivation::default(),
+            &messages,
+            &proofs,
+        )
+        .unwrap();
+
+        assert_eq!(
+            tally.period,
+            Period {
+                status: PeriodStatus::Ended
+            }
+        );
+        assert_eq!(tally.results, vec![Uint256::zero(); 5]);
+        assert_eq!(tally.all_result, Uint256::zero());
+        assert!(tally
+            .claim
+            .events
+            .iter()
+            .flat_map(|event| &event.attributes)
+            .any(|attr| attr.key == "action" && attr.value == "claim"));
+        assert_eq!(contract.msg_length(&app).unwrap(), Uint256::from_u128(1));
+    }
+
+    #[test]
+    fn run_full_round_replays_logs_fixture() {
+        let mut app = create_app();
+        let contract = MaciCodeId::store_code(&mut app)
+            .instantiate_logs_fixture_round(&mut app, "logs fixture")
+            .unwrap();
+        let fixture = load_logs_fixture();
+
+        let tally = run_full_round(
+            &mut app,
+            &contract,
+            &fixture.voters,
+            &fixture.deactivation,
+            &fixture.messages,
+            &fixture.proofs,
+        )
+        .unwrap();
+
+        assert_eq!(
+            tally.period,
+            Period {
+                status: PeriodStatus::Ended
+            }
+        );
+        assert_eq!(tally.results, fixture.proofs.results);
+        // Both original keys were deactivated, so only the rotated key's
+        // weight-6 vote for option 2 is counted (votes * 10^24 + votes^2)
+        assert_eq!(
+            tally.results,
+            vec![
+                Uint256::zero(),
+                Uint256::zero(),
+                uint256_from_decimal_string("6000000000000000000000036"),
+                Uint256::zero(),
+                Uint256::zero(),
+            ]
+        );
+        assert_eq!(contract.num_sign_up(&app).unwrap(), Uint256::from_u128(3));
+        assert_eq!(contract.dmsg_length(&app).unwrap(), Uint256::from_u128(2));
+        assert_eq!(contract.msg_length(&app).unwrap(), Uint256::from_u128(3));
+    }
+
+    // stop_tallying computes the elapsed time since end_time with saturating_sub.
+    // If the block time is earlier than end_time it must still finalize cleanly
+    // without panicking.
+    #[test]
+    fn stop_tallying_does_not_panic_when_block_time_before_end_time() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        // Advance past the voting window so the round can be processed.
+        app.update_block(|block| {
+            block.time = Timestamp::from_nanos(1571797424879000000).plus_minutes(12);
+        });
+        contract.start_process(&mut app, owner()).unwrap();
+        contract.stop_processing(&mut app, owner()).unwrap();
+
+        // Rewind the block time to BEFORE end_time (end_time = start + 11 min),
+        // exercising the saturating path where current_time < end_time.
+        app.update_block(|block| {
+            block.time = Timestamp::from_nanos(1571797424879000000).plus_minutes(5);
+        });
 
         // Must not panic; empty round finalizes with all-zero results.
         contract
@@ -4245,4 +4937,1755 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn test_query_config_matches_individual_queries() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        let config = contract.get_config(&app).unwrap();
+        let query = |msg: &QueryMsg| -> Uint256 {
+            app.wrap().query_wasm_smart(contract.addr(), msg).unwrap()
+        };
+
+        // No dedicated query exposes the parameters or the fee recipient, so
+        // read them straight from the contract's storage.
+        assert_eq!(
+            config.parameters,
+            MACIPARAMETERS.query(&app.wrap(), contract.addr()).unwrap()
+        );
+        assert_eq!(config.circuit_type, query(&QueryMsg::QueryCircuitType {}));
+        assert_eq!(
+            config.certification_system,
+            query(&QueryMsg::QueryCertSystem {})
+        );
+        assert_eq!(
+            config.voice_credit_amount,
+            query(&QueryMsg::GetVoiceCreditAmount {})
+        );
+        assert_eq!(config.voting_time, contract.get_voting_time(&app).unwrap());
+        assert_eq!(
+            config.max_vote_options,
+            contract.max_vote_options(&app).unwrap()
+        );
+
+        let coordinator_hash: Option<Uint256> = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::GetCoordinatorHash {})
+            .unwrap();
+        assert_eq!(Some(config.coordinator_hash), coordinator_hash);
+
+        assert_eq!(config.admin, contract.query_admin(&app).unwrap());
+        assert_eq!(config.operator, contract.query_operator(&app).unwrap());
+        assert_eq!(
+            config.fee_recipient,
+            FEE_RECIPIENT.query(&app.wrap(), contract.addr()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_can_start_processing_reports_blocking_reason() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        // Voting has not ended yet
+        let status = contract.can_start_processing(&app).unwrap();
+        assert!(!status.can_start);
+        assert_eq!(
+            status.reason,
+            Some("Voting period has not ended".to_string())
+        );
+
+        app.update_block(|block| {
+            block.time = Timestamp::from_nanos(1571797424879000000).plus_minutes(12);
+        });
+        let status = contract.can_start_processing(&app).unwrap();
+        assert!(status.can_start);
+        assert_eq!(status.reason, None);
+
+        // Once processing has started, the round is no longer Pending
+        contract.start_process(&mut app, owner()).unwrap();
+        let status = contract.can_start_processing(&app).unwrap();
+        assert!(!status.can_start);
+        assert_eq!(
+            status.reason,
+            Some("Round is already in Processing status".to_string())
+        );
+    }
+
+    #[test]
+    fn test_can_start_processing_blocked_by_pending_deactivate_messages() {
+        use cosmwasm_std::coins;
+        use cw_multi_test::next_block;
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_with_deactivate_enabled(&mut app, true).unwrap();
+        app.update_block(next_block);
+
+        app.execute_contract(
+            user1(),
+            contract.addr(),
+            &ExecuteMsg::PublishDeactivateMessage {
+                message: MessageData {
+                    data: [Uint256::from_u128(1); 10],
+                },
+                enc_pub_key: test_pubkey1(),
+            },
+            &coins(DEACTIVATE_FEE.u128(), "peaka"),
+        )
+        .unwrap();
+
+        app.update_block(|block| {
+            block.time = Timestamp::from_nanos(1571797424879000000).plus_minutes(12);
+        });
+        let status = contract.can_start_processing(&app).unwrap();
+        assert!(!status.can_start);
+        assert_eq!(
+            status.reason,
+            Some("1 deactivate messages are left to process".to_string())
+        );
+
+        // The query agrees with StartProcessPeriod itself
+        let err = contract.start_process(&mut app, owner()).unwrap_err();
+        assert_eq!(ContractError::DmsgLeftProcess {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn test_extend_voting_time_before_start() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        let new_end_time = start_time.plus_minutes(30);
+
+        let err = contract
+            .extend_voting_time(&mut app, user1(), new_end_time)
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        contract
+            .extend_voting_time(&mut app, owner(), new_end_time)
+            .unwrap();
+        let voting_time = contract.get_voting_time(&app).unwrap();
+        assert_eq!(voting_time.start_time, start_time);
+        assert_eq!(voting_time.end_time, new_end_time);
+    }
+
+    #[test]
+    fn test_extend_voting_time_after_start_should_fail() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        app.update_block(next_block);
+
+        let err = contract
+            .extend_voting_time(&mut app, owner(), start_time.plus_minutes(30))
+            .unwrap_err();
+        assert_eq!(ContractError::PeriodError {}, err.downcast().unwrap());
+
+        let voting_time = contract.get_voting_time(&app).unwrap();
+        assert_eq!(voting_time.end_time, start_time.plus_minutes(11));
+    }
+
+    #[test]
+    fn test_extend_voting_time_with_short_gap_should_fail() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        // The default create-round window is 10 minutes
+        let err = contract
+            .extend_voting_time(&mut app, owner(), start_time.plus_minutes(10))
+            .unwrap_err();
+        assert_eq!(ContractError::WrongTimeSet {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn test_get_commitments_tracks_round_progress() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        let deactivate: Option<Uint256> = app
+            .wrap()
+            .query_wasm_smart(
+                contract.addr(),
+                &QueryMsg::GetCurrentDeactivateCommitment {},
+            )
+            .unwrap();
+        let commitments = contract.get_commitments(&app).unwrap();
+        assert_eq!(commitments.state, Uint256::zero());
+        assert_eq!(commitments.tally, Uint256::zero());
+        assert_eq!(Some(commitments.deactivate), deactivate);
+
+        // StartProcessPeriod seeds the state commitment from the state root
+        app.update_block(|block| {
+            block.time = Timestamp::from_nanos(1571797424879000000).plus_minutes(12);
+        });
+        contract.start_process(&mut app, owner()).unwrap();
+        let state: Option<Uint256> = app
+            .wrap()
+            .query_wasm_smart(contract.addr(), &QueryMsg::QueryCurrentStateCommitment {})
+            .unwrap();
+        let processing = contract.get_commitments(&app).unwrap();
+        assert_ne!(processing.state, Uint256::zero());
+        assert_eq!(Some(processing.state), state);
+        assert_eq!(processing.tally, Uint256::zero());
+        assert_eq!(processing.deactivate, commitments.deactivate);
+
+        // An empty round has no batches, so the commitments carry through tallying
+        contract.stop_processing(&mut app, owner()).unwrap();
+        contract
+            .stop_tallying(&mut app, owner(), vec![Uint256::zero(); 5], Uint256::zero())
+            .unwrap();
+        assert_eq!(contract.get_commitments(&app).unwrap(), processing);
+    }
+
+    #[test]
+    fn test_get_commitments_follow_fixture_batches() {
+        let mut app = create_app();
+        let (contract, fixture) = logs_fixture_round_after_voting(&mut app);
+        let deactivate = fixture.deactivation.batches[0].new_deactivate_commitment;
+
+        contract.start_process(&mut app, operator()).unwrap();
+        let started = contract.get_commitments(&app).unwrap();
+        assert_eq!(started.tally, Uint256::zero());
+        assert_eq!(started.deactivate, deactivate);
+
+        let (new_state_commitment, proof) = &fixture.proofs.process[0];
+        contract
+            .process_message(&mut app, operator(), *new_state_commitment, proof.clone())
+            .unwrap();
+        let processed = contract.get_commitments(&app).unwrap();
+        assert_ne!(processed.state, started.state);
+        assert_eq!(processed.state, *new_state_commitment);
+        assert_eq!(processed.tally, Uint256::zero());
+        assert_eq!(processed.deactivate, deactivate);
+
+        contract.stop_processing(&mut app, operator()).unwrap();
+        let (new_tally_commitment, proof) = &fixture.proofs.tally[0];
+        contract
+            .process_tally(&mut app, operator(), *new_tally_commitment, proof.clone())
+            .unwrap();
+        let tallied = contract.get_commitments(&app).unwrap();
+        assert_ne!(tallied.tally, processed.tally);
+        assert_eq!(tallied.tally, *new_tally_commitment);
+        assert_eq!(tallied.state, processed.state);
+        assert_eq!(tallied.deactivate, deactivate);
+    }
+
+    #[test]
+    fn test_set_fee_rate_validation() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        let err = contract.set_fee_rate(&mut app, user1(), 20).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        let err = contract.set_fee_rate(&mut app, owner(), 101).unwrap_err();
+        assert_eq!(
+            ContractError::InvalidFeeRate { fee_rate: 101 },
+            err.downcast().unwrap()
+        );
+
+        contract.set_fee_rate(&mut app, owner(), 100).unwrap();
+
+        app.update_block(next_block);
+        let err = contract.set_fee_rate(&mut app, owner(), 20).unwrap_err();
+        assert_eq!(ContractError::PeriodError {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn test_set_fee_rate_changes_claim_split() {
+        let claim_split = |fee_rate: Option<u64>| -> (u128, u128) {
+            let mut app = create_app();
+            let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+            if let Some(fee_rate) = fee_rate {
+                contract.set_fee_rate(&mut app, owner(), fee_rate).unwrap();
+            }
+
+            let messages = vec![RoundMessage {
+                sender: user3(),
+                message: MessageData {
+                    data: [Uint256::from_u128(1); 10],
+                },
+                enc_pub_key: test_pubkey1(),
+            }];
+            let proofs = RoundProofs {
+                results: vec![Uint256::zero(); 5],
+                ..Default::default()
+            };
+            let tally = run_full_round(
+                &mut app,
+                &contract,
+                &[],
+                &RoundDeactivation::default(),
+                &messages,
+                &proofs,
+            )
+            .unwrap();
+
+            let attr = |key: &str| -> u128 {
+                tally
+                    .claim
+                    .events
+                    .iter()
+                    .flat_map(|event| &event.attributes)
+                    .find(|attr| attr.key == key)
+                    .unwrap()
+                    .value
+                    .parse()
+                    .unwrap()
+            };
+            let total = attr("fee_to_recipient") + attr("operator_reward") + attr("penalty_amount");
+            (attr("fee_to_recipient"), total)
+        };
+
+        let (default_fee, total) = claim_split(None);
+        assert!(total > 0);
+        assert_eq!(default_fee, total / 10);
+
+        let (fee, total) = claim_split(Some(50));
+        assert_eq!(fee, total / 2);
+        assert!(fee > default_fee);
+
+        let (fee, _) = claim_split(Some(0));
+        assert_eq!(fee, 0);
+    }
+
+    #[test]
+    fn test_signuped_by_x_after_signup() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+        app.update_block(next_block);
+
+        let pubkey = test_pubkey1();
+        assert_eq!(contract.signuped_by_x(&app, pubkey.x).unwrap(), None);
+
+        contract.sign_up(&mut app, user1(), pubkey.clone()).unwrap();
+        contract.sign_up(&mut app, user2(), test_pubkey2()).unwrap();
+
+        assert_eq!(
+            contract.signuped_by_x(&app, pubkey.x).unwrap(),
+            Some(Uint256::zero())
+        );
+        assert_eq!(
+            contract.signuped_by_x(&app, test_pubkey2().x).unwrap(),
+            contract.signuped(&app, test_pubkey2()).unwrap()
+        );
+        assert_eq!(contract.signuped_by_x(&app, pubkey.y).unwrap(), None);
+    }
+
+    #[test]
+    fn test_empty_round_skips_tally_and_ends() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        // Messages without any signups are all invalid but still published
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        contract
+            .publish_message(
+                &mut app,
+                user3(),
+                MessageData {
+                    data: [Uint256::from_u128(1); 10],
+                },
+                test_pubkey1(),
+            )
+            .unwrap();
+
+        app.update_block(|block| block.time = start_time.plus_minutes(12));
+        contract.start_process(&mut app, owner()).unwrap();
+        contract.stop_processing(&mut app, owner()).unwrap();
+
+        // ProcessTally has no users to process and leaves the round untouched
+        let err = contract
+            .process_tally(
+                &mut app,
+                owner(),
+                Uint256::from_u128(1),
+                Groth16ProofType {
+                    a: String::new(),
+                    b: String::new(),
+                    c: String::new(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::AllUsersProcessed {}, err.downcast().unwrap());
+        assert_eq!(
+            contract.get_period(&app).unwrap(),
+            Period {
+                status: PeriodStatus::Tallying
+            }
+        );
+        assert_eq!(
+            contract.get_commitments(&app).unwrap().tally,
+            Uint256::zero()
+        );
+
+        contract
+            .stop_tallying(&mut app, owner(), vec![Uint256::zero(); 5], Uint256::zero())
+            .unwrap();
+        assert_eq!(
+            contract.get_period(&app).unwrap(),
+            Period {
+                status: PeriodStatus::Ended
+            }
+        );
+        assert_eq!(contract.get_all_result(&app).unwrap(), Uint256::zero());
+    }
+
+    #[test]
+    fn test_process_tally_with_messages_left_should_fail() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        contract.sign_up(&mut app, user1(), test_pubkey1()).unwrap();
+        contract
+            .publish_message(
+                &mut app,
+                user1(),
+                MessageData {
+                    data: [Uint256::from_u128(1); 10],
+                },
+                test_pubkey2(),
+            )
+            .unwrap();
+
+        app.update_block(|block| block.time = start_time.plus_minutes(12));
+        contract.start_process(&mut app, owner()).unwrap();
+        let err = contract.stop_processing(&mut app, owner()).unwrap_err();
+        assert_eq!(ContractError::MsgLeftProcess {}, err.downcast().unwrap());
+
+        // Force the period forward without processing the message
+        PERIOD
+            .save(
+                app.contract_storage_mut(&contract.addr()).as_mut(),
+                &Period {
+                    status: PeriodStatus::Tallying,
+                },
+            )
+            .unwrap();
+
+        let err = contract
+            .process_tally(
+                &mut app,
+                owner(),
+                Uint256::from_u128(1),
+                Groth16ProofType {
+                    a: String::new(),
+                    b: String::new(),
+                    c: String::new(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::MessagesNotFullyProcessed {},
+            err.downcast().unwrap()
+        );
+    }
+
+    // circuit_type 2 (1p1v with deactivation) has no circuit in either
+    // contract; both must reject it with the same variant and message
+    #[test]
+    fn wrong_circuit_type_is_rejected_like_maci() {
+        let mut app = create_app();
+
+        let maci_err = cw_maci::multitest::MaciCodeId::store_code(&mut app)
+            .instantiate_with_wrong_circuit_type(&mut app, owner(), "maci")
+            .unwrap_err();
+        let maci_err: cw_maci::ContractError = maci_err.downcast().unwrap();
+        assert_eq!(maci_err, cw_maci::ContractError::UnsupportedCircuitType {});
+
+        let code_id = MaciCodeId::store_code(&mut app);
+        let amaci_err = MaciContract::instantiate(
+            &mut app,
+            code_id,
+            owner(),
+            RoundInfo {
+                title: String::from("HackWasm Berlin"),
+                description: String::from("Hack In Brelin"),
+                link: String::from("https://baidu.com"),
+            },
+            None,
+            VotingTime {
+                start_time: Timestamp::from_nanos(1571797424879000000),
+                end_time: Timestamp::from_nanos(1571797424879000000).plus_minutes(11),
+            },
+            Uint256::from_u128(2u128),
+            Uint256::from_u128(0u128),
+            "amaci",
+        )
+        .unwrap_err();
+        let amaci_err: ContractError = amaci_err.downcast().unwrap();
+        assert_eq!(amaci_err, ContractError::UnsupportedCircuitType {});
+
+        assert_eq!(amaci_err.to_string(), maci_err.to_string());
+    }
+
+    #[test]
+    fn test_first_dmsg_timestamp_tracks_batch_start() {
+        use crate::multitest::App;
+        use crate::state::{DMSG_CHAIN_LENGTH, PROCESSED_DMSG_COUNT};
+        use cosmwasm_std::coins;
+
+        let mut app = create_app();
+        app.sudo(cw_multi_test::SudoMsg::Bank(
+            cw_multi_test::BankSudo::Mint {
+                to_address: user1().to_string(),
+                amount: coins(100_000_000_000_000_000_000, "peaka"),
+            },
+        ))
+        .unwrap();
+
+        let contract = MaciContract::instantiate_with_deactivate_enabled(&mut app, true).unwrap();
+        app.update_block(next_block);
+
+        assert_eq!(contract.get_first_dmsg_timestamp(&app).unwrap(), None);
+
+        let publish = |app: &mut App| {
+            app.execute_contract(
+                user1(),
+                contract.addr(),
+                &ExecuteMsg::PublishDeactivateMessage {
+                    message: MessageData {
+                        data: [Uint256::from_u128(1); 10],
+                    },
+                    enc_pub_key: test_pubkey1(),
+                },
+                &coins(DEACTIVATE_FEE.u128(), "peaka"),
+            )
+            .unwrap();
+        };
+
+        // The first message of a batch records its block time
+        let first_time = app.block_info().time;
+        publish(&mut app);
+        assert_eq!(
+            contract.get_first_dmsg_timestamp(&app).unwrap(),
+            Some(first_time)
+        );
+
+        // Later messages in the same batch leave it untouched
+        app.update_block(next_block);
+        publish(&mut app);
+        assert_eq!(
+            contract.get_first_dmsg_timestamp(&app).unwrap(),
+            Some(first_time)
+        );
+
+        // Simulate the operator processing the pending batch
+        let dmsg_chain_length = DMSG_CHAIN_LENGTH
+            .load(app.contract_storage(&contract.addr()).as_ref())
+            .unwrap();
+        PROCESSED_DMSG_COUNT
+            .save(
+                app.contract_storage_mut(&contract.addr()).as_mut(),
+                &dmsg_chain_length,
+            )
+            .unwrap();
+
+        // The next message starts a new batch and advances the timestamp
+        app.update_block(next_block);
+        let next_time = app.block_info().time;
+        assert!(next_time > first_time);
+        publish(&mut app);
+        assert_eq!(
+            contract.get_first_dmsg_timestamp(&app).unwrap(),
+            Some(next_time)
+        );
+    }
+
+    #[test]
+    fn test_set_tally_delay_hours_validation() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        let err = contract
+            .set_tally_delay_hours(&mut app, user1(), 1, 2)
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        let err = contract
+            .set_tally_delay_hours(&mut app, owner(), 3, 2)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidTallyDelayHours {
+                min_hours: 3,
+                max_hours: 2
+            },
+            err.downcast().unwrap()
+        );
+
+        contract
+            .set_tally_delay_hours(&mut app, owner(), 2, 2)
+            .unwrap();
+
+        app.update_block(next_block);
+        let err = contract
+            .set_tally_delay_hours(&mut app, owner(), 1, 2)
+            .unwrap_err();
+        assert_eq!(ContractError::PeriodError {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn test_tally_delay_stays_within_configured_hours() {
+        use crate::state::MSG_CHAIN_LENGTH;
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        contract
+            .set_tally_delay_hours(&mut app, owner(), 1, 2)
+            .unwrap();
+
+        let mut delay_for_messages = |msg_chain_length: u128| -> u64 {
+            MSG_CHAIN_LENGTH
+                .save(
+                    app.contract_storage_mut(&contract.addr()).as_mut(),
+                    &Uint256::from_u128(msg_chain_length),
+                )
+                .unwrap();
+            contract.get_tally_delay(&app).unwrap().delay_seconds
+        };
+
+        // Light workload: (200 + 0) * 3 = 600s is raised to the 1 hour floor
+        assert_eq!(delay_for_messages(0), 3600);
+        // Moderate workload: (200 + 1000 * 2) * 3 = 6600s falls between the bounds
+        assert_eq!(delay_for_messages(1000), 6600);
+        // Heavy workload: (200 + 2000 * 2) * 3 = 12600s is capped at 2 hours
+        assert_eq!(delay_for_messages(2000), 7200);
+    }
+
+    #[test]
+    fn test_compute_results_root_matches_stop_tallying() {
+        use crate::state::{CURRENT_TALLY_COMMITMENT, PROCESSED_USER_COUNT};
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        contract.sign_up(&mut app, user1(), test_pubkey1()).unwrap();
+
+        let results: Vec<Uint256> = [3u128, 1, 0, 4, 2]
+            .iter()
+            .map(|v| Uint256::from_u128(*v))
+            .collect();
+        let salt = Uint256::from_u128(42);
+
+        let results_root = contract
+            .compute_results_root(&app, results.clone())
+            .unwrap();
+        let mut other_results = results.clone();
+        other_results[0] = Uint256::from_u128(4);
+        assert_ne!(
+            contract
+                .compute_results_root(&app, other_results.clone())
+                .unwrap(),
+            results_root
+        );
+
+        // Invalid result vectors are rejected rather than hashed
+        assert!(contract.compute_results_root(&app, vec![]).is_err());
+        assert!(contract
+            .compute_results_root(&app, vec![Uint256::zero(); 6])
+            .is_err());
+
+        // Simulate a fully processed tally committing to `results`
+        app.update_block(|block| block.time = start_time.plus_minutes(12));
+        let mut storage = app.contract_storage_mut(&contract.addr());
+        PERIOD
+            .save(
+                storage.as_mut(),
+                &Period {
+                    status: PeriodStatus::Tallying,
+                },
+            )
+            .unwrap();
+        PROCESSED_USER_COUNT
+            .save(storage.as_mut(), &Uint256::from_u128(1))
+            .unwrap();
+        CURRENT_TALLY_COMMITMENT
+            .save(storage.as_mut(), &maci_utils::hash2([results_root, salt]))
+            .unwrap();
+        drop(storage);
+
+        let err = contract
+            .stop_tallying(&mut app, owner(), other_results, salt)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::TallyCommitmentMismatch {},
+            err.downcast().unwrap()
+        );
+
+        contract
+            .stop_tallying(&mut app, owner(), results.clone(), salt)
+            .unwrap();
+        assert_eq!(
+            contract.get_all_result(&app).unwrap(),
+            Uint256::from_u128(10)
+        );
+    }
+
+    #[test]
+    fn test_vote_options_indexed() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        contract.set_vote_option_map(&mut app, owner()).unwrap();
+
+        let vote_option_map = contract.vote_option_map(&app).unwrap();
+        let indexed = contract.vote_options_indexed(&app).unwrap();
+        assert_eq!(indexed.len(), vote_option_map.len());
+        for (position, (index, label)) in indexed.iter().enumerate() {
+            assert_eq!(*index, position as u32);
+            assert_eq!(*label, vote_option_map[position]);
+        }
+        assert_eq!(indexed[0], (0, String::from("did_not_vote")));
+        assert_eq!(indexed[4], (4, String::from("abstain")));
+    }
+
+    #[test]
+    fn test_set_vote_options_map_rejects_duplicate_label() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        let err = contract
+            .set_custom_vote_option_map(
+                &mut app,
+                owner(),
+                vec![String::from("yes"), String::from("no"), String::from("yes")],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidVoteOptions {
+                reason: String::from("option 2 duplicates \"yes\""),
+            },
+            err.downcast().unwrap()
+        );
+        assert_eq!(
+            contract.vote_option_map(&app).unwrap(),
+            vec!["Option 1", "Option 2", "Option 3", "Option 4", "Option 5"]
+        );
+    }
+
+    #[test]
+    fn test_set_vote_options_map_rejects_empty_label() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        let err = contract
+            .set_custom_vote_option_map(
+                &mut app,
+                owner(),
+                vec![String::from("yes"), String::from(" "), String::from("no")],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidVoteOptions {
+                reason: String::from("option 1 is empty"),
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_instantiate_rejects_invalid_vote_option_labels() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+
+        let cases = [
+            (vec!["yes", ""], "option 1 is empty"),
+            (vec!["yes", "no", "yes"], "option 2 duplicates \"yes\""),
+        ];
+        for (labels, reason) in cases {
+            let init_msg = InstantiateMsg {
+                vote_option_map: labels.into_iter().map(String::from).collect(),
+                ..default_init_msg()
+            };
+            let err = app
+                .instantiate_contract(code_id.0, owner(), &init_msg, &[], "MACI", None)
+                .unwrap_err();
+            assert_eq!(
+                ContractError::InvalidVoteOptions {
+                    reason: String::from(reason),
+                },
+                err.downcast().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_instantiate_emits_vote_option_map_hash() {
+        use crate::contract::vote_option_map_hash;
+        use cosmwasm_std::{to_json_binary, WasmMsg};
+
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let init_msg = InstantiateMsg {
+            vote_option_map: vec!["yes".to_string(), "no".to_string()],
+            ..default_init_msg()
+        };
+
+        // Instantiate through a WasmMsg to keep the response events
+        let res = app
+            .execute(
+                owner(),
+                WasmMsg::Instantiate {
+                    admin: None,
+                    code_id: code_id.id(),
+                    msg: to_json_binary(&init_msg).unwrap(),
+                    funds: vec![],
+                    label: "vote option hash".to_string(),
+                }
+                .into(),
+            )
+            .unwrap();
+        assert_eq!(
+            wasm_attr(&res, "vote_option_map_hash"),
+            vote_option_map_hash(&init_msg.vote_option_map)
+        );
+    }
+
+    #[test]
+    fn test_vote_option_map_hash_changes_with_map() {
+        use crate::contract::vote_option_map_hash;
+        use cw_multi_test::AppResponse;
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+
+        let emitted_hash = |res: &AppResponse| -> String {
+            res.events
+                .iter()
+                .flat_map(|event| &event.attributes)
+                .find(|attr| attr.key == "vote_option_map_hash")
+                .unwrap()
+                .value
+                .clone()
+        };
+
+        let res = contract.set_vote_option_map(&mut app, owner()).unwrap();
+        let first_hash = emitted_hash(&res);
+        assert_eq!(
+            first_hash,
+            vote_option_map_hash(&contract.vote_option_map(&app).unwrap())
+        );
+
+        // Re-submitting the same map yields the same hash
+        let res = contract.set_vote_option_map(&mut app, owner()).unwrap();
+        assert_eq!(emitted_hash(&res), first_hash);
+
+        let res = contract
+            .set_custom_vote_option_map(
+                &mut app,
+                owner(),
+                vec![String::from("yes"), String::from("no")],
+            )
+            .unwrap();
+        let second_hash = emitted_hash(&res);
+        assert_ne!(second_hash, first_hash);
+        assert_eq!(
+            second_hash,
+            vote_option_map_hash(&["yes".to_string(), "no".to_string()])
+        );
+
+        // Label boundaries are part of the hash
+        assert_ne!(
+            vote_option_map_hash(&["ab".to_string(), "c".to_string()]),
+            vote_option_map_hash(&["a".to_string(), "bc".to_string()])
+        );
+        assert_ne!(
+            vote_option_map_hash(&["abc".to_string()]),
+            vote_option_map_hash(&["ab".to_string(), "c".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_next_tally_batch_advances_per_batch() {
+        use crate::state::{NUMSIGNUPS, PROCESSED_USER_COUNT};
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+
+        // Simulate 12 signups being tallied in batches of 5^int_state_tree_depth
+        let set_counts = |app: &mut crate::multitest::App, processed: u128| {
+            let mut storage = app.contract_storage_mut(&contract.addr());
+            NUMSIGNUPS
+                .save(storage.as_mut(), &Uint256::from_u128(12))
+                .unwrap();
+            PROCESSED_USER_COUNT
+                .save(storage.as_mut(), &Uint256::from_u128(processed))
+                .unwrap();
+        };
+
+        for (processed, batch_num) in [(0u128, 0u128), (5, 1), (10, 2)] {
+            set_counts(&mut app, processed);
+            assert_eq!(
+                contract.get_next_tally_batch(&app).unwrap(),
+                TallyBatch {
+                    batch_num: Uint256::from_u128(batch_num),
+                    batch_size: Uint256::from_u128(5),
+                }
+            );
+        }
+
+        // The last batch overshoots num_sign_ups; nothing is left to tally
+        set_counts(&mut app, 15);
+        let err = contract.get_next_tally_batch(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::AllUsersProcessed {}.to_string()));
+    }
+
+    #[test]
+    fn test_next_tally_batch_around_fixture_tally() {
+        let mut app = create_app();
+        let (contract, fixture) = logs_fixture_round_after_voting(&mut app);
+        contract.start_process(&mut app, operator()).unwrap();
+        for (new_state_commitment, proof) in &fixture.proofs.process {
+            contract
+                .process_message(&mut app, operator(), *new_state_commitment, proof.clone())
+                .unwrap();
+        }
+        contract.stop_processing(&mut app, operator()).unwrap();
+
+        // All 3 signups fit in the first batch of 5^int_state_tree_depth
+        assert_eq!(
+            contract.get_next_tally_batch(&app).unwrap(),
+            TallyBatch {
+                batch_num: Uint256::zero(),
+                batch_size: Uint256::from_u128(5),
+            }
+        );
+
+        let (new_tally_commitment, proof) = &fixture.proofs.tally[0];
+        contract
+            .process_tally(&mut app, operator(), *new_tally_commitment, proof.clone())
+            .unwrap();
+        let err = contract.get_next_tally_batch(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::AllUsersProcessed {}.to_string()));
+    }
+
+    #[test]
+    fn test_whitelist_status() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        contract.sign_up(&mut app, user1(), test_pubkey1()).unwrap();
+
+        let statuses = contract
+            .whitelist_status(&app, vec![user1(), user2(), user3()])
+            .unwrap();
+        assert_eq!(
+            statuses,
+            vec![
+                (user1(), true, true),
+                (user2(), true, false),
+                (user3(), false, false),
+            ]
+        );
+        assert!(contract.whitelist_status(&app, vec![]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_remove_from_whitelist() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        let err = contract
+            .remove_from_whitelist(&mut app, user1(), user2())
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        let err = contract
+            .remove_from_whitelist(&mut app, owner(), user3())
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotInWhitelist {
+                addr: user3().to_string()
+            },
+            err.downcast().unwrap()
+        );
+
+        contract
+            .remove_from_whitelist(&mut app, owner(), user2())
+            .unwrap();
+        assert_eq!(
+            contract
+                .whitelist_status(&app, vec![user1(), user2()])
+                .unwrap(),
+            vec![(user1(), true, false), (user2(), false, false)]
+        );
+
+        // The removed address can no longer sign up; the rest still can
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        let err = contract
+            .sign_up(&mut app, user2(), test_pubkey2())
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+        contract.sign_up(&mut app, user1(), test_pubkey1()).unwrap();
+
+        // Pruning is only allowed before voting starts
+        let err = contract
+            .remove_from_whitelist(&mut app, owner(), user1())
+            .unwrap_err();
+        assert_eq!(ContractError::PeriodError {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn test_is_tally_timed_out() {
+        use crate::state::TALLY_TIMEOUT_EXTRA_SECONDS;
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+        let end_time = Timestamp::from_nanos(1571797424879000000).plus_minutes(11);
+
+        assert!(!contract.is_tally_timed_out(&app).unwrap());
+
+        let delay_seconds = contract.get_tally_delay(&app).unwrap().delay_seconds;
+        let deadline = end_time.plus_seconds(delay_seconds + TALLY_TIMEOUT_EXTRA_SECONDS);
+
+        app.update_block(|block| block.time = deadline);
+        assert!(!contract.is_tally_timed_out(&app).unwrap());
+
+        app.update_block(|block| block.time = deadline.plus_seconds(1));
+        assert!(contract.is_tally_timed_out(&app).unwrap());
+    }
+
+    // Any valid secp256k1 scalar; stands in for the oracle's next signing key
+    const ROTATED_ORACLE_KEY_HEX: &str =
+        "1f8a5c2e9b7d4036e1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f701";
+
+    fn instantiate_oracle_round(app: &mut App) -> MaciContract {
+        let code_id = MaciCodeId::store_code(app);
+        MaciContract::instantiate_with_oracle(
+            app,
+            code_id,
+            owner(),
+            RoundInfo {
+                title: "Oracle Rotation Round".to_string(),
+                description: "".to_string(),
+                link: "".to_string(),
+            },
+            None,
+            VotingTime {
+                start_time: Timestamp::from_seconds(1577836800),
+                end_time: Timestamp::from_seconds(1577836800 + 11 * 60),
+            },
+            Uint256::from_u128(0u128),
+            Uint256::from_u128(0u128),
+            test_oracle_pubkey(),
+            "Oracle Rotation",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_set_oracle_whitelist_pubkey_non_admin() {
+        let mut app = create_app();
+        let contract = instantiate_oracle_round(&mut app);
+
+        let rotated = pubkey_b64_for_key(ROTATED_ORACLE_KEY_HEX);
+        let err = contract
+            .set_oracle_whitelist_pubkey(&mut app, user1(), rotated)
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+        assert_eq!(
+            contract.query_oracle_whitelist_config(&app).unwrap(),
+            Some(test_oracle_pubkey())
+        );
+    }
+
+    #[test]
+    fn test_set_oracle_whitelist_pubkey_invalid() {
+        let mut app = create_app();
+        let contract = instantiate_oracle_round(&mut app);
+
+        let err = contract
+            .set_oracle_whitelist_pubkey(&mut app, owner(), "not base64!".to_string())
+            .unwrap_err();
+        assert_eq!(ContractError::InvalidBase64 {}, err.downcast().unwrap());
+
+        // Valid base64, but not a SEC1 secp256k1 key
+        let err = contract
+            .set_oracle_whitelist_pubkey(&mut app, owner(), "aGVsbG8gd29ybGQ=".to_string())
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidOraclePubkey {},
+            err.downcast().unwrap()
+        );
+
+        // Rotation only applies to oracle rounds
+        let whitelist_round = MaciContract::instantiate_default(&mut app, true).unwrap();
+        let err = whitelist_round
+            .set_oracle_whitelist_pubkey(&mut app, owner(), test_oracle_pubkey())
+            .unwrap_err();
+        assert_eq!(
+            ContractError::OracleWhitelistNotConfigured {},
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_oracle_whitelist_pubkey_rotation() {
+        let mut app = create_app();
+        let contract = instantiate_oracle_round(&mut app);
+        let contract_addr = contract.addr().to_string();
+
+        let rotated = pubkey_b64_for_key(ROTATED_ORACLE_KEY_HEX);
+        contract
+            .set_oracle_whitelist_pubkey(&mut app, owner(), rotated.clone())
+            .unwrap();
+        assert_eq!(
+            contract.query_oracle_whitelist_config(&app).unwrap(),
+            Some(rotated.clone())
+        );
+
+        app.update_block(|block| block.time = Timestamp::from_seconds(1577836800 + 60));
+
+        // Certificates from the retired key are rejected
+        let pubkey1 = test_pubkey1();
+        let stale_cert = generate_certificate_for_pubkey(
+            &contract_addr,
+            &pubkey1.x.to_string(),
+            &pubkey1.y.to_string(),
+            100u128,
+        );
+        let err = contract
+            .sign_up_oracle(&mut app, user1(), pubkey1.clone(), stale_cert)
+            .unwrap_err();
+        assert_eq!(ContractError::InvalidSignature {}, err.downcast().unwrap());
+
+        let cert = generate_certificate_with_key(
+            ROTATED_ORACLE_KEY_HEX,
+            &contract_addr,
+            &pubkey1.x.to_string(),
+            &pubkey1.y.to_string(),
+            100u128,
+        );
+        contract
+            .sign_up_oracle(&mut app, user1(), pubkey1, cert)
+            .unwrap();
+        assert_eq!(
+            contract.num_sign_up(&app).unwrap(),
+            Uint256::from_u128(1u128)
+        );
+
+        // Rotation is only allowed before voting starts
+        let err = contract
+            .set_oracle_whitelist_pubkey(&mut app, owner(), test_oracle_pubkey())
+            .unwrap_err();
+        assert_eq!(ContractError::PeriodError {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn test_oracle_signup_count() {
+        let mut app = create_app();
+        let contract = instantiate_oracle_round(&mut app);
+        let contract_addr = contract.addr().to_string();
+        assert_eq!(
+            contract.get_oracle_signup_count(&app).unwrap(),
+            Uint256::zero()
+        );
+
+        app.update_block(|block| block.time = Timestamp::from_seconds(1577836800 + 60));
+        for (i, (user, pubkey)) in [(user1(), test_pubkey1()), (user2(), test_pubkey2())]
+            .into_iter()
+            .enumerate()
+        {
+            let cert = generate_certificate_for_pubkey(
+                &contract_addr,
+                &pubkey.x.to_string(),
+                &pubkey.y.to_string(),
+                100u128,
+            );
+            contract
+                .sign_up_oracle(&mut app, user, pubkey, cert)
+                .unwrap();
+            assert_eq!(
+                contract.get_oracle_signup_count(&app).unwrap(),
+                Uint256::from_u128(i as u128 + 1)
+            );
+        }
+
+        // Static whitelist rounds never touch the oracle counter
+        let whitelist_round = MaciContract::instantiate_default(&mut app, true).unwrap();
+        assert_eq!(
+            whitelist_round.get_oracle_signup_count(&app).unwrap(),
+            Uint256::zero()
+        );
+    }
+
+    #[test]
+    fn test_sign_up_event_attributes() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+        app.update_block(|block| {
+            block.time = Timestamp::from_nanos(1571797424879000000).plus_minutes(1)
+        });
+        let pubkey = test_pubkey1();
+        let res = contract.sign_up(&mut app, user1(), pubkey.clone()).unwrap();
+        assert_eq!(
+            wasm_attr(&res, "action_detail"),
+            "SignUpWithStaticWhitelist"
+        );
+        assert_eq!(wasm_attr(&res, "state_idx"), "0");
+        assert_eq!(
+            uint256_from_decimal_string(&wasm_attr(&res, "pubkey_x")),
+            pubkey.x
+        );
+        assert_eq!(
+            uint256_from_decimal_string(&wasm_attr(&res, "pubkey_y")),
+            pubkey.y
+        );
+
+        let contract = instantiate_oracle_round(&mut app);
+        app.update_block(|block| block.time = Timestamp::from_seconds(1577836800 + 60));
+        let pubkey = test_pubkey2();
+        let cert = generate_certificate_for_pubkey(
+            contract.addr().as_str(),
+            &pubkey.x.to_string(),
+            &pubkey.y.to_string(),
+            100u128,
+        );
+        let res = contract
+            .sign_up_oracle(&mut app, user2(), pubkey.clone(), cert)
+            .unwrap();
+        assert_eq!(wasm_attr(&res, "action_detail"), "SignUpWithOracle");
+        assert_eq!(wasm_attr(&res, "pubkey_x"), pubkey.x.to_string());
+        assert_eq!(wasm_attr(&res, "pubkey_y"), pubkey.y.to_string());
+        assert_eq!(wasm_attr(&res, "balance"), "100");
+    }
+
+    #[test]
+    fn test_pubkey_attributes_are_plain_decimal() {
+        use crate::state::FEE_DENOM;
+        use cosmwasm_std::coins;
+
+        let parse = |res: &cw_multi_test::AppResponse, prefix: &str| PubKey {
+            x: wasm_attr(res, &format!("{prefix}_x")).parse().unwrap(),
+            y: wasm_attr(res, &format!("{prefix}_y")).parse().unwrap(),
+        };
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_with_deactivate_enabled(&mut app, true).unwrap();
+        app.update_block(next_block);
+
+        let res = contract.sign_up(&mut app, user1(), test_pubkey1()).unwrap();
+        assert_eq!(parse(&res, "pubkey"), test_pubkey1());
+        // The combined form stays for existing indexers
+        assert_eq!(
+            wasm_attr(&res, "pubkey"),
+            format!(
+                "{:?},{:?}",
+                test_pubkey1().x.to_string(),
+                test_pubkey1().y.to_string()
+            )
+        );
+
+        let message = MessageData {
+            data: [Uint256::from_u128(1); 10],
+        };
+        let res = contract
+            .publish_message(&mut app, user1(), message.clone(), test_pubkey2())
+            .unwrap();
+        assert_eq!(parse(&res, "msg_0_enc_pub_key"), test_pubkey2());
+
+        let res = app
+            .execute_contract(
+                user1(),
+                contract.addr(),
+                &ExecuteMsg::PublishDeactivateMessage {
+                    message,
+                    enc_pub_key: test_pubkey3(),
+                },
+                &coins(DEACTIVATE_FEE.u128(), FEE_DENOM),
+            )
+            .unwrap();
+        assert_eq!(parse(&res, "enc_pub_key"), test_pubkey3());
+    }
+
+    #[test]
+    fn test_add_new_key_pubkey_attributes_are_plain_decimal() {
+        let mut app = create_app();
+        let (contract, fixture) = logs_fixture_round_before_add_key(&mut app);
+        let key = &fixture.deactivation.new_keys[0];
+
+        let res = contract
+            .add_key(
+                &mut app,
+                key.sender.clone(),
+                key.pubkey.clone(),
+                key.nullifier,
+                key.d,
+                key.proof.clone(),
+            )
+            .unwrap();
+        assert_eq!(wasm_attr(&res, "pubkey_x"), key.pubkey.x.to_string());
+        assert_eq!(wasm_attr(&res, "pubkey_y"), key.pubkey.y.to_string());
+    }
+
+    #[test]
+    fn test_get_delay_records_paginated() {
+        use crate::state::DELAY_RECORDS;
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, true).unwrap();
+
+        let records: Vec<DelayRecord> = (0..5u64)
+            .map(|i| DelayRecord {
+                delay_timestamp: Timestamp::from_seconds(1_000 + i),
+                delay_duration: 60 * (i + 1),
+                delay_reason: format!("delay {i}"),
+                delay_process_dmsg_count: Uint256::from_u128(i as u128),
+                delay_type: if i % 2 == 0 {
+                    DelayType::DeactivateDelay
+                } else {
+                    DelayType::TallyDelay
+                },
+            })
+            .collect();
+        DELAY_RECORDS
+            .save(
+                app.contract_storage_mut(&contract.addr()).as_mut(),
+                &DelayRecords {
+                    records: records.clone(),
+                },
+            )
+            .unwrap();
+
+        for (i, expected) in records.chunks(2).enumerate() {
+            let page = contract
+                .query_delay_records_paginated(&app, 2 * i as u32, 2)
+                .unwrap();
+            assert_eq!(page.records, expected);
+        }
+        assert_eq!(contract.query_delay_records(&app).unwrap().records, records);
+
+        assert!(contract
+            .query_delay_records_paginated(&app, 5, 2)
+            .unwrap()
+            .records
+            .is_empty());
+        assert!(contract
+            .query_delay_records_paginated(&app, 0, 0)
+            .unwrap()
+            .records
+            .is_empty());
+
+        // Oversized limits are capped at 100 records
+        let many: Vec<DelayRecord> = records.iter().cycle().take(120).cloned().collect();
+        DELAY_RECORDS
+            .save(
+                app.contract_storage_mut(&contract.addr()).as_mut(),
+                &DelayRecords {
+                    records: many.clone(),
+                },
+            )
+            .unwrap();
+        let page = contract
+            .query_delay_records_paginated(&app, 0, u32::MAX)
+            .unwrap();
+        assert_eq!(page.records, many[..100]);
+        let page = contract
+            .query_delay_records_paginated(&app, 100, u32::MAX)
+            .unwrap();
+        assert_eq!(page.records, many[100..]);
+    }
+
+    #[test]
+    fn test_custom_fee_denom_is_charged_and_claimed() {
+        use crate::state::FEE_DENOM;
+        use cosmwasm_std::{coin, coins, Uint128};
+
+        const DENOM: &str = "uatom";
+
+        let mut app = create_app();
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &user3(),
+                    vec![
+                        coin(MESSAGE_FEE.u128(), FEE_DENOM),
+                        coin(MESSAGE_FEE.u128(), DENOM),
+                    ],
+                )
+                .unwrap();
+        });
+        let code_id = MaciCodeId::store_code(&mut app);
+        let mut init_msg = default_init_msg();
+        init_msg.vote_option_map = (1..=5).map(|i| format!("Option {i}")).collect();
+        init_msg.fee_denom = Some(DENOM.to_string());
+        let contract: MaciContract = app
+            .instantiate_contract(code_id.id(), owner(), &init_msg, &[], "amaci", None)
+            .unwrap()
+            .into();
+        assert_eq!(contract.get_fee_config(&app).unwrap().fee_denom, DENOM);
+
+        let start_time = init_msg.voting_time.start_time;
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        let message = MessageData {
+            data: [Uint256::from_u128(1); 10],
+        };
+
+        // The default denom is no longer accepted
+        let err = contract
+            .publish_message(&mut app, user3(), message.clone(), test_pubkey1())
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InsufficientFundsSend {},
+            err.downcast().unwrap()
+        );
+
+        let res = app
+            .execute_contract(
+                user3(),
+                contract.addr(),
+                &ExecuteMsg::PublishMessage {
+                    messages: vec![message],
+                    enc_pub_keys: vec![test_pubkey1()],
+                },
+                &coins(MESSAGE_FEE.u128(), DENOM),
+            )
+            .unwrap();
+        assert_eq!(wasm_attr(&res, "fee_paid"), format!("{MESSAGE_FEE}{DENOM}"));
+
+        app.update_block(|block| block.time = start_time.plus_minutes(12));
+        contract.start_process(&mut app, owner()).unwrap();
+        contract.stop_processing(&mut app, owner()).unwrap();
+        contract
+            .stop_tallying(&mut app, owner(), vec![Uint256::zero(); 5], Uint256::zero())
+            .unwrap();
+        contract.claim(&mut app, owner()).unwrap();
+
+        // owner() is admin, operator and fee recipient, so it receives the whole fee
+        let balance = |addr: Addr| app.wrap().query_balance(addr, DENOM).unwrap().amount;
+        assert_eq!(balance(contract.addr()), Uint128::zero());
+        assert_eq!(balance(owner()), MESSAGE_FEE);
+    }
+
+    #[test]
+    fn test_claim_sends_operator_reward_to_reward_recipient() {
+        use crate::multitest::dora_mock_api;
+        use crate::state::FEE_DENOM;
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        contract
+            .publish_message(
+                &mut app,
+                user3(),
+                MessageData {
+                    data: [Uint256::from_u128(1); 10],
+                },
+                test_pubkey1(),
+            )
+            .unwrap();
+
+        app.update_block(|block| block.time = start_time.plus_minutes(12));
+        contract.start_process(&mut app, owner()).unwrap();
+        contract.stop_processing(&mut app, owner()).unwrap();
+        contract
+            .stop_tallying(&mut app, owner(), vec![Uint256::zero(); 5], Uint256::zero())
+            .unwrap();
+
+        let payout = dora_mock_api().addr_make("payout");
+        let err = contract
+            .claim_to(&mut app, user1(), Some(payout.clone()))
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        let balance = |app: &App, addr: &Addr| app.wrap().query_balance(addr, FEE_DENOM).unwrap();
+        let operator_before = balance(&app, &operator());
+
+        let res = contract
+            .claim_to(&mut app, operator(), Some(payout.clone()))
+            .unwrap();
+        assert_eq!(wasm_attr(&res, "reward_recipient"), payout.to_string());
+
+        // No delays were recorded, so the operator earns everything after the 10% fee
+        let reward: u128 = wasm_attr(&res, "operator_reward").parse().unwrap();
+        assert_eq!(reward, MESSAGE_FEE.u128() * 9 / 10);
+        assert_eq!(balance(&app, &payout).amount.u128(), reward);
+        assert_eq!(balance(&app, &operator()), operator_before);
+    }
+
+    #[test]
+    fn test_estimated_tally_work_matches_stop_tallying() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        for enc_pub_key in [test_pubkey1(), test_pubkey2()] {
+            contract
+                .publish_message(
+                    &mut app,
+                    user3(),
+                    MessageData {
+                        data: [Uint256::from_u128(1); 10],
+                    },
+                    enc_pub_key,
+                )
+                .unwrap();
+        }
+
+        let work = contract.get_estimated_tally_work(&app).unwrap();
+        assert_eq!(work.total_work, 2);
+        assert_eq!(
+            work.allowed_delay_seconds,
+            contract.get_tally_delay(&app).unwrap().delay_seconds
+        );
+
+        app.update_block(|block| block.time = start_time.plus_minutes(12));
+        contract.start_process(&mut app, owner()).unwrap();
+        contract.stop_processing(&mut app, owner()).unwrap();
+        let res = contract
+            .stop_tallying(&mut app, owner(), vec![Uint256::zero(); 5], Uint256::zero())
+            .unwrap();
+
+        assert_eq!(wasm_attr(&res, "total_work"), work.total_work.to_string());
+        assert_eq!(
+            wasm_attr(&res, "actual_delay_seconds"),
+            work.allowed_delay_seconds.to_string()
+        );
+        assert_eq!(contract.get_estimated_tally_work(&app).unwrap(), work);
+    }
+
+    #[test]
+    fn test_next_process_batch_tracks_processed_messages() {
+        use crate::state::PROCESSED_MSG_COUNT;
+
+        let msg_content = fs::read_to_string("./src/test/msg_test.json").unwrap();
+        let data: MsgData = serde_json::from_str(&msg_content).unwrap();
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+
+        // Seven messages with distinct enc keys: one full batch of 5 and a partial one
+        let mut enc_pub_keys: Vec<PubKey> = data
+            .enc_pub_keys
+            .iter()
+            .map(|key| PubKey {
+                x: uint256_from_decimal_string(&key[0]),
+                y: uint256_from_decimal_string(&key[1]),
+            })
+            .collect();
+        enc_pub_keys.extend([test_pubkey1(), test_pubkey2()]);
+
+        app.update_block(|block| block.time = start_time.plus_minutes(1));
+        for (i, enc_pub_key) in enc_pub_keys.into_iter().enumerate() {
+            contract
+                .publish_message(
+                    &mut app,
+                    user3(),
+                    MessageData {
+                        data: [Uint256::from_u128(i as u128 + 1); 10],
+                    },
+                    enc_pub_key,
+                )
+                .unwrap();
+        }
+
+        app.update_block(|block| block.time = start_time.plus_minutes(12));
+        contract.start_process(&mut app, owner()).unwrap();
+
+        // Messages are processed newest-first, so the partial batch comes first
+        let batch = contract.get_next_process_batch(&app).unwrap();
+        assert_eq!(
+            batch,
+            BatchRange {
+                start: Uint256::from_u128(5),
+                end: Uint256::from_u128(7),
+            }
+        );
+
+        // A rejected proof leaves the batch where it was
+        let err = contract
+            .process_message(
+                &mut app,
+                owner(),
+                Uint256::from_u128(1),
+                Groth16ProofType {
+                    a: "053eb9bf62de01898e5d7049bfeaee4611b78b54f516ff4b0fd93ffcdc491d8b170e2c3de370f8eeec93ebb57e49279adc68fb137f4aafe1b4206d7186592673".to_string(),
+                    b: "2746ba15cb4478a1a90bd512844cd0e57070357ff17ad90964b699f962f4f24817ce4dcc89d350df5d63ae7f05f0069272c3d352cb92237e682222e68d52da0f00551f58de3a3cac33d6af2fb052e4ff4d42008b5f33b310756a5e7017919087284dc00b9753a3891872ee599467348976ec2d72703d46949a9b8093a97718eb".to_string(),
+                    c: "1832b7d8607c041bd1437f43fe1d207ad64bea58f346cc91d0c72d9c02bbc4031decf433ecafc3874f4bcedbfae591caaf87834ad6867c7d342b96b6299ddd0a".to_string(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidProof {
+                step: "Process".to_string()
+            },
+            err.downcast().unwrap()
+        );
+        assert_eq!(contract.get_next_process_batch(&app).unwrap(), batch);
+
+        // Force the count a verified ProcessMessage would leave behind
+        let mut processed = Uint256::zero();
+        for expected in [
+            BatchRange {
+                start: Uint256::from_u128(5),
+                end: Uint256::from_u128(7),
+            },
+            BatchRange {
+                start: Uint256::zero(),
+                end: Uint256::from_u128(5),
+            },
+        ] {
+            let batch = contract.get_next_process_batch(&app).unwrap();
+            assert_eq!(batch, expected);
+
+            processed += batch.end - batch.start;
+            PROCESSED_MSG_COUNT
+                .save(
+                    app.contract_storage_mut(&contract.addr()).as_mut(),
+                    &processed,
+                )
+                .unwrap();
+            assert_eq!(contract.get_processed_msg_count(&app).unwrap(), processed);
+        }
+
+        assert_eq!(processed, Uint256::from_u128(7));
+        let err = contract.get_next_process_batch(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::AllMessagesProcessed {}.to_string()));
+    }
+
+    #[test]
+    fn test_get_deactivate_node() {
+        use crate::state::DNODES;
+
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_with_deactivate_enabled(&mut app, true).unwrap();
+
+        // Instantiate seeds the root with zero; unset nodes read as zero too
+        assert_eq!(
+            contract.get_deactivate_node(&app, Uint256::zero()).unwrap(),
+            Uint256::zero()
+        );
+        assert_eq!(
+            contract
+                .get_deactivate_node(&app, Uint256::from_u128(6))
+                .unwrap(),
+            Uint256::zero()
+        );
+
+        let root = uint256_from_decimal_string(
+            "2066250734862463440364862380285893469106296812213935917346898934498400651862",
+        );
+        let node = Uint256::from_u128(42);
+        for (index, value) in [(Uint256::zero(), root), (Uint256::from_u128(6), node)] {
+            DNODES
+                .save(
+                    app.contract_storage_mut(&contract.addr()).as_mut(),
+                    index.to_be_bytes().to_vec(),
+                    &value,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            contract.get_deactivate_node(&app, Uint256::zero()).unwrap(),
+            root
+        );
+        assert_eq!(
+            contract
+                .get_deactivate_node(&app, Uint256::from_u128(6))
+                .unwrap(),
+            node
+        );
+        assert_eq!(
+            contract
+                .get_deactivate_node(&app, Uint256::from_u128(1))
+                .unwrap(),
+            Uint256::zero()
+        );
+    }
+
+    #[test]
+    fn test_process_deactivate_sets_deactivate_root() {
+        let mut app = create_app();
+        let contract = MaciCodeId::store_code(&mut app)
+            .instantiate_logs_fixture_round(&mut app, "logs fixture")
+            .unwrap();
+        let mut fixture = load_logs_fixture();
+        let deactivation = RoundDeactivation {
+            messages: std::mem::take(&mut fixture.deactivation.messages),
+            ..Default::default()
+        };
+        run_voting_phase(&mut app, &contract, &fixture.voters, &deactivation, &[]).unwrap();
+        assert_eq!(
+            contract.get_deactivate_node(&app, Uint256::zero()).unwrap(),
+            Uint256::zero()
+        );
+
+        let batch = &fixture.deactivation.batches[0];
+        contract
+            .process_deactivate_message(
+                &mut app,
+                operator(),
+                batch.size,
+                batch.new_deactivate_commitment,
+                batch.new_deactivate_root,
+                batch.proof.clone(),
+            )
+            .unwrap();
+        assert_eq!(
+            contract.get_deactivate_node(&app, Uint256::zero()).unwrap(),
+            batch.new_deactivate_root
+        );
+    }
+
+    #[test]
+    fn test_add_new_key_emits_processed_deactivate_root() {
+        let mut app = create_app();
+        let (contract, fixture) = logs_fixture_round_before_add_key(&mut app);
+        let key = &fixture.deactivation.new_keys[0];
+
+        let res = contract
+            .add_key(
+                &mut app,
+                key.sender.clone(),
+                key.pubkey.clone(),
+                key.nullifier,
+                key.d,
+                key.proof.clone(),
+            )
+            .unwrap();
+
+        let root = contract.get_deactivate_node(&app, Uint256::zero()).unwrap();
+        assert_eq!(root, fixture.deactivation.batches[0].new_deactivate_root);
+        assert_eq!(wasm_attr(&res, "new_deactivate_root"), root.to_string());
+    }
+
+    #[test]
+    fn test_extend_voting_time_cannot_shorten_round() {
+        let mut app = create_app();
+        let contract = MaciContract::instantiate_default(&mut app, false).unwrap();
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        let end_time = start_time.plus_minutes(11);
+
+        // Still past the 10 minute create-round window, but earlier than the current end
+        for new_end_time in [start_time.plus_seconds(630), end_time] {
+            let err = contract
+                .extend_voting_time(&mut app, owner(), new_end_time)
+                .unwrap_err();
+            assert_eq!(ContractError::WrongTimeSet {}, err.downcast().unwrap());
+        }
+        assert_eq!(contract.get_voting_time(&app).unwrap().end_time, end_time);
+    }
 }
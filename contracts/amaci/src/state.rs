@@ -1,3 +1,5 @@
+use crate::error::ContractError;
+use crate::msg::InstantiationData;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Timestamp, Uint128, Uint256};
 use cw_storage_plus::{Item, Map};
@@ -154,11 +156,7 @@ pub const REGISTRATION_MODE: Item<RegistrationMode> = Item::new("registration_mo
 // End of Unified MACI Configuration Types
 // ============================================
 
-#[cw_serde]
-pub struct PubKey {
-    pub x: Uint256,
-    pub y: Uint256,
-}
+pub use maci_utils::PubKey;
 
 #[cw_serde]
 pub struct StateLeaf {
@@ -168,6 +166,11 @@ pub struct StateLeaf {
     pub nonce: Uint256,
 }
 
+// Leaf layouts are fixed by the circuits, which recompute them, so no extra
+// domain tag can be added here. The two kinds are still separated: the plain
+// leaf is a single width-5 Poseidon, while AMACI leaves are a width-2 Poseidon
+// over (leaf, ciphertext hash), and Poseidon widths use distinct parameters.
+// A sign-up leaf is deliberately the new-key leaf with an all-zero `d`.
 impl StateLeaf {
     pub fn hash_state_leaf(&self) -> Uint256 {
         let mut plaintext: [Uint256; 5] = [Uint256::from_u128(0); 5];
@@ -213,6 +216,9 @@ pub const LEAF_IDX_0: Item<Uint256> = Item::new("leaf_idx_0");
 pub const COORDINATORHASH: Item<Uint256> = Item::new("coordinator_hash");
 pub const ZEROS: Item<[Uint256; 12]> = Item::new("zeros");
 pub const ZEROS_H10: Item<[Uint256; 10]> = Item::new("zeros_h10");
+// Depths the zero tables cover: ZEROS_H10[depth] and ZEROS[depth + 2] must exist
+pub const MIN_STATE_TREE_DEPTH: u128 = 2;
+pub const MAX_STATE_TREE_DEPTH: u128 = 9;
 
 #[cw_serde]
 /// Message data structure for encrypted vote messages
@@ -241,6 +247,9 @@ pub const CURRENT_DEACTIVATE_COMMITMENT: Item<Uint256> = Item::new("current_deac
 // Map (pubkey.x, pubkey.y) to stateIdx for signup tracking
 // Using both x and y to handle potential x-coordinate collisions on the curve
 pub const SIGNUPED: Map<&(Vec<u8>, Vec<u8>), Uint256> = Map::new("signuped");
+// Secondary index from pubkey.x to stateIdx for clients that only keep x.
+// (x, y) and (x, -y) share an x, so this holds the latest signup for that x
+pub const SIGNUPED_BY_X: Map<Vec<u8>, Uint256> = Map::new("signuped_by_x");
 pub const PRE_DEACTIVATE_ROOT: Item<Uint256> = Item::new("pre_deactivate_root");
 pub const PRE_DEACTIVATE_COORDINATOR_HASH: Item<Uint256> =
     Item::new("pre_deactivate_coordinator_hash");
@@ -260,7 +269,7 @@ pub struct QuinaryTreeRoot {
 impl QuinaryTreeRoot {
     const DEGREE: u32 = 5;
 
-    pub fn root_of(&self, depth: Uint256, nodes: Vec<Uint256>) -> Uint256 {
+    pub fn root_of(&self, depth: Uint256, nodes: Vec<Uint256>) -> Result<Uint256, ContractError> {
         let _depth = depth
             .to_string()
             .parse()
@@ -268,7 +277,12 @@ impl QuinaryTreeRoot {
         let capacity = Self::DEGREE.pow(_depth);
         let length = nodes.len() as u32;
 
-        assert!(capacity >= length, "overflow");
+        if length == 0 {
+            return Err(ContractError::EmptyResults {});
+        }
+        if length > capacity {
+            return Err(ContractError::ResultsExceedTreeCapacity { length, capacity });
+        }
 
         let mut c = capacity / Self::DEGREE;
         let mut pl = (length - 1) / Self::DEGREE + 1;
@@ -313,7 +327,7 @@ impl QuinaryTreeRoot {
         if result == Uint256::zero() {
             result = self.get_zero(_depth);
         }
-        result
+        Ok(result)
     }
 
     fn get_zero(&self, height: u32) -> Uint256 {
@@ -353,6 +367,13 @@ impl Whitelist {
             .expect("address should exist in whitelist as verified by is_whitelist()")
             .is_register = true;
     }
+
+    /// Drop `addr` from the whitelist; returns whether it was present
+    pub fn remove(&mut self, addr: &Addr) -> bool {
+        let len = self.users.len();
+        self.users.retain(|a| a.addr != addr);
+        self.users.len() != len
+    }
 }
 
 pub const WHITELIST: Item<Whitelist> = Item::new("whitelist");
@@ -381,6 +402,7 @@ pub const MACI_OPERATOR: Item<Addr> = Item::new("maci_operator");
 pub const PENALTY_RATE: Item<Uint256> = Item::new("penalty_rate");
 pub const CREATE_ROUND_WINDOW: Item<Timestamp> = Item::new("create_round_window");
 
+pub const TALLY_DELAY_MIN_HOURS: Item<u64> = Item::new("tally_delay_min_hours"); // tally delay min hours
 pub const TALLY_DELAY_MAX_HOURS: Item<u64> = Item::new("tally_delay_max_hours"); // tally delay max hours
 
 pub const TALLY_TIMEOUT: Item<Timestamp> = Item::new("tally_timeout"); // tally timeout in seconds
@@ -389,12 +411,19 @@ pub const FIRST_DMSG_TIMESTAMP: Item<Timestamp> = Item::new("first_dmsg_timestam
 
 pub const FEE_RECIPIENT: Item<Addr> = Item::new("fee_recipient");
 
+// Percentage of the claimed balance sent to FEE_RECIPIENT (0-100)
+pub const FEE_RATE: Item<u64> = Item::new("fee_rate");
+
 // Deactivate feature enabled/disabled flag
 pub const DEACTIVATE_ENABLED: Item<bool> = Item::new("deactivate_enabled");
 
-// Shared fee denomination
+// Default fee denomination, used when InstantiateMsg.fee_denom is unset
 pub const FEE_DENOM: &str = "peaka";
 
+// Native denom fees are paid in and Claim distributes; absent for rounds created before
+// it was configurable, which use FEE_DENOM
+pub const FEE_DENOM_CONFIG: Item<String> = Item::new("fee_denom");
+
 #[cw_serde]
 pub struct FeeConfig {
     // per-message fee for PublishMessage
@@ -449,6 +478,9 @@ pub struct DelayRecords {
 
 pub const DELAY_RECORDS: Item<DelayRecords> = Item::new("delay_records");
 
+// Snapshot of the data returned by instantiate; later config updates don't touch it
+pub const INSTANTIATION_DATA: Item<InstantiationData> = Item::new("instantiation_data");
+
 // Oracle whitelist storage per user
 #[cw_serde]
 pub struct OracleWhitelistUser {
@@ -468,6 +500,8 @@ impl OracleWhitelistUser {
 
 pub const ORACLE_WHITELIST: Map<&(Vec<u8>, Vec<u8>), OracleWhitelistUser> =
     Map::new("oracle_whitelist");
+// Number of ORACLE_WHITELIST entries, kept alongside the map so it needn't be iterated
+pub const ORACLE_SIGNUP_COUNT: Item<Uint256> = Item::new("oracle_signup_count");
 
 #[cfg(test)]
 mod tests {
@@ -484,4 +518,68 @@ mod tests {
         assert!(config.is_admin(alice.as_ref()));
         assert!(!config.is_admin("other"));
     }
+
+    #[test]
+    fn root_of_rejects_empty_or_oversized_results() {
+        let qtr_lib = QuinaryTreeRoot {
+            zeros: [Uint256::zero(); 12],
+        };
+
+        assert!(qtr_lib
+            .root_of(Uint256::from_u128(1), vec![Uint256::from_u128(1); 5])
+            .is_ok());
+        assert_eq!(
+            qtr_lib
+                .root_of(Uint256::from_u128(1), vec![Uint256::from_u128(1); 6])
+                .unwrap_err(),
+            ContractError::ResultsExceedTreeCapacity {
+                length: 6,
+                capacity: 5
+            }
+        );
+        assert_eq!(
+            qtr_lib.root_of(Uint256::from_u128(1), vec![]).unwrap_err(),
+            ContractError::EmptyResults {}
+        );
+    }
+
+    #[test]
+    fn state_leaf_kinds_hash_differently() {
+        let leaf = StateLeaf {
+            pub_key: PubKey {
+                x: Uint256::from_u128(1),
+                y: Uint256::from_u128(2),
+            },
+            voice_credit_balance: Uint256::from_u128(100),
+            vote_option_tree_root: Uint256::zero(),
+            nonce: Uint256::zero(),
+        };
+
+        assert_ne!(leaf.hash_state_leaf(), leaf.hash_decativate_state_leaf());
+        assert_ne!(
+            leaf.hash_decativate_state_leaf(),
+            leaf.hash_new_key_state_leaf([Uint256::from_u128(1); 4])
+        );
+        assert_eq!(
+            leaf.hash_decativate_state_leaf(),
+            leaf.hash_new_key_state_leaf([Uint256::zero(); 4])
+        );
+    }
+
+    #[test]
+    fn period_status_serializes_as_stable_strings() {
+        let cases = [
+            (PeriodStatus::Pending, "\"pending\""),
+            (PeriodStatus::Processing, "\"processing\""),
+            (PeriodStatus::Tallying, "\"tallying\""),
+            (PeriodStatus::Ended, "\"ended\""),
+        ];
+        for (status, json) in cases {
+            assert_eq!(cosmwasm_std::to_json_string(&status).unwrap(), json);
+            assert_eq!(
+                cosmwasm_std::from_json::<PeriodStatus>(json).unwrap(),
+                status
+            );
+        }
+    }
 }
@@ -6,6 +6,7 @@ use cosmwasm_std::{
 };
 use cw2::set_contract_version;
 use cw_utils::may_pay;
+use serde::Deserialize;
 
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as SdkCoin;
 use cosmos_sdk_proto::cosmos::feegrant::v1beta1::{
@@ -15,7 +16,7 @@ use cosmos_sdk_proto::Any;
 use prost::Message;
 
 // External contract types with aliases to avoid path conflicts
-use cw_amaci::msg::RegistrationModeConfig;
+use cw_amaci::msg::{QueryMsg as AmaciQueryMsg, RegistrationModeConfig};
 use cw_amaci::state::{RoundInfo, VoiceCreditMode, VotingTime, FEE_DENOM};
 
 // use cw_maci::state::VotingPowerMode; // Unused after Unified MACI refactoring
@@ -550,7 +551,7 @@ pub fn execute_publish_message(
         contract_addr: target_addr.to_string(),
         msg: to_json_binary(&amaci_msg)?,
         funds: vec![Coin {
-            denom: FEE_DENOM.to_string(),
+            denom: round_fee_denom(deps.as_ref(), &target_addr)?,
             amount: required,
         }],
     };
@@ -603,7 +604,7 @@ pub fn execute_publish_deactivate_message(
         contract_addr: target_addr.to_string(),
         msg: to_json_binary(&amaci_msg)?,
         funds: vec![Coin {
-            denom: FEE_DENOM.to_string(),
+            denom: round_fee_denom(deps.as_ref(), &target_addr)?,
             amount: required,
         }],
     };
@@ -649,6 +650,31 @@ fn deduct_signup_fee(
     Ok((target_addr, required))
 }
 
+/// The `fee_denom` field of the amaci GetFeeConfig response. Rounds created before the
+/// denom was configurable omit it and always charge FEE_DENOM.
+#[derive(Deserialize)]
+struct RoundFeeDenom {
+    fee_denom: Option<String>,
+}
+
+/// Denom to forward round fees in. TOTAL_BALANCE only tracks `config.denom`, so rounds
+/// that charge fees in any other denom are rejected.
+fn round_fee_denom(deps: Deps, round_addr: &Addr) -> Result<String, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let round_denom = deps
+        .querier
+        .query_wasm_smart::<RoundFeeDenom>(round_addr, &AmaciQueryMsg::GetFeeConfig {})?
+        .fee_denom
+        .unwrap_or_else(|| FEE_DENOM.to_string());
+    if round_denom != config.denom {
+        return Err(ContractError::RoundFeeDenomMismatch {
+            expected: config.denom,
+            actual: round_denom,
+        });
+    }
+    Ok(config.denom)
+}
+
 /// Proxy sign_up to amaci contract, paying signup_fee from SAAS balance.
 pub fn execute_sign_up(
     mut deps: DepsMut,
@@ -676,7 +702,7 @@ pub fn execute_sign_up(
         vec![]
     } else {
         vec![Coin {
-            denom: FEE_DENOM.to_string(),
+            denom: round_fee_denom(deps.as_ref(), &target_addr)?,
             amount: required,
         }]
     };
@@ -724,7 +750,7 @@ pub fn execute_add_new_key(
         vec![]
     } else {
         vec![Coin {
-            denom: FEE_DENOM.to_string(),
+            denom: round_fee_denom(deps.as_ref(), &target_addr)?,
             amount: required,
         }]
     };
@@ -772,7 +798,7 @@ pub fn execute_pre_add_new_key(
         vec![]
     } else {
         vec![Coin {
-            denom: FEE_DENOM.to_string(),
+            denom: round_fee_denom(deps.as_ref(), &target_addr)?,
             amount: required,
         }]
     };
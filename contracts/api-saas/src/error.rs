@@ -64,4 +64,10 @@ pub enum ContractError {
 
     #[error("round_addr not found in registry reply events")]
     RoundAddrNotInReplyEvents {},
+
+    #[error("Round charges fees in {actual}, but SaaS balance is held in {expected}")]
+    RoundFeeDenomMismatch { expected: String, actual: String },
+
+    #[error("Message must have {expected} fields, got {actual}")]
+    InvalidMessageLength { expected: usize, actual: usize },
 }
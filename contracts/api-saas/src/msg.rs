@@ -1,8 +1,10 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Uint256};
 use cw_amaci::msg::RegistrationModeConfig;
-use cw_amaci::state::{RoundInfo, VoiceCreditMode, VotingTime};
+use cw_amaci::state::{MessageData, RoundInfo, VoiceCreditMode, VotingTime};
+use std::str::FromStr;
 
+use crate::error::ContractError;
 use crate::state::{Config, OperatorInfo, SaasFeeConfig};
 
 #[cw_serde]
@@ -16,6 +18,35 @@ pub struct MessageDataParam {
     pub data: Vec<String>,
 }
 
+impl From<MessageData> for MessageDataParam {
+    fn from(message: MessageData) -> Self {
+        Self {
+            data: message.data.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+}
+
+impl TryFrom<MessageDataParam> for MessageData {
+    type Error = ContractError;
+
+    fn try_from(param: MessageDataParam) -> Result<Self, Self::Error> {
+        let mut data = [Uint256::zero(); 10];
+        if param.data.len() != data.len() {
+            return Err(ContractError::InvalidMessageLength {
+                expected: data.len(),
+                actual: param.data.len(),
+            });
+        }
+        for (slot, value) in data.iter_mut().zip(param.data) {
+            *slot = Uint256::from_str(&value).map_err(|e| ContractError::ParseError {
+                value,
+                reason: e.to_string(),
+            })?;
+        }
+        Ok(MessageData { data })
+    }
+}
+
 /// Groth16 proof parameters (mirrors cw_amaci::msg::Groth16ProofType).
 #[cw_serde]
 pub struct Groth16ProofParam {
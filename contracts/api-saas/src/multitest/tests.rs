@@ -1192,6 +1192,57 @@ fn test_saas_publish_message_batch_success() {
     assert_eq!(balance_after, balance_before - expected_fee);
 }
 
+/// SAAS only accounts for its configured denom, so a round charging fees in any other
+/// denom must be rejected instead of paid from untracked coins.
+#[test]
+fn test_saas_publish_message_rejects_round_fee_denom_mismatch() {
+    use cw_amaci::state::FEE_DENOM_CONFIG;
+
+    const ROUND_DENOM: &str = "uatom";
+
+    let PublishTestEnv {
+        mut app,
+        saas,
+        amaci_addr,
+    } = setup_publish_env(100_000_000_000_000_000_000, false);
+
+    let amaci = Addr::unchecked(&amaci_addr);
+    FEE_DENOM_CONFIG
+        .save(
+            app.contract_storage_mut(&amaci).as_mut(),
+            &ROUND_DENOM.to_string(),
+        )
+        .unwrap();
+
+    let balance_before = saas.query_balance(&app).unwrap();
+
+    let pubkey = test_pubkey1();
+    let err = saas
+        .publish_message(
+            &mut app,
+            operator1(),
+            amaci_addr.clone(),
+            vec![EncPubKeyParam {
+                x: pubkey.x.to_string(),
+                y: pubkey.y.to_string(),
+            }],
+            vec![MessageDataParam {
+                data: vec!["1".to_string(); 10],
+            }],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::RoundFeeDenomMismatch {
+            expected: DORA_DEMON.to_string(),
+            actual: ROUND_DENOM.to_string(),
+        }
+    );
+    assert_eq!(query_msg_chain_length(&app, &amaci_addr), Uint256::zero());
+    assert_eq!(saas.query_balance(&app).unwrap(), balance_before);
+}
+
 /// A non-operator address must not be allowed to call `publish_message`.
 #[test]
 fn test_saas_publish_message_unauthorized() {
@@ -1830,3 +1881,37 @@ fn test_saas_full_round_signup_addnewkey_tally() {
         final_balance
     );
 }
+
+#[test]
+fn test_message_data_param_conversion_round_trip() {
+    use cw_amaci::state::MessageData;
+
+    let mut data = [Uint256::zero(); 10];
+    for (i, slot) in data.iter_mut().enumerate() {
+        *slot = uint256_from_decimal_string(&format!("{}123456789012345678901234567890", i + 1));
+    }
+    let message = MessageData { data };
+
+    let param = MessageDataParam::from(message.clone());
+    assert_eq!(param.data.len(), 10);
+    assert_eq!(param.data[0], "1123456789012345678901234567890");
+    assert_eq!(MessageData::try_from(param).unwrap(), message);
+
+    let short = MessageDataParam {
+        data: vec!["1".to_string(); 9],
+    };
+    assert_eq!(
+        MessageData::try_from(short).unwrap_err(),
+        ContractError::InvalidMessageLength {
+            expected: 10,
+            actual: 9
+        }
+    );
+
+    let mut invalid = vec!["1".to_string(); 10];
+    invalid[3] = "0xabc".to_string();
+    assert!(matches!(
+        MessageData::try_from(MessageDataParam { data: invalid }).unwrap_err(),
+        ContractError::ParseError { value, .. } if value == "0xabc"
+    ));
+}
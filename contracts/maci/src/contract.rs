@@ -31,7 +31,9 @@ use cosmwasm_std::{
     Response, StdResult, Uint128, Uint256,
 };
 
-use maci_utils::{hash2, hash5, hash_256_uint256_list, uint256_from_hex_string};
+use maci_utils::{
+    address_to_uint256, hash2, hash5, hash_256_uint256_list, pack_vals, uint256_from_hex_string,
+};
 
 use bellman_ce::plonk::better_cs::verifier::verify as plonk_verify;
 use bellman_ce::plonk::commitments::transcript::keccak_transcript::RollingKeccakTranscript;
@@ -69,29 +71,6 @@ fn get_circuit_max_voters(state_tree_depth: &Uint256) -> u128 {
     }
 }
 
-/// Convert a contract address to Uint256 format
-/// This function takes the address bytes and converts them to a Uint256
-fn address_to_uint256(address: &Addr) -> Uint256 {
-    let address_bytes = address.as_bytes();
-
-    // Use SHA256 hash to convert the address to a fixed-length 32-byte format
-    let mut hasher = Sha256::new();
-    hasher.update(address_bytes);
-    let hash_result = hasher.finalize();
-
-    // Convert the hash bytes to Uint256
-    let mut bytes = [0u8; 32];
-    bytes.copy_from_slice(&hash_result[..]);
-
-    // Convert bytes to Uint256 (big-endian)
-    let mut uint256_bytes = [0u8; 32];
-    for (i, &byte) in bytes.iter().enumerate() {
-        uint256_bytes[31 - i] = byte; // Reverse for little-endian to big-endian conversion
-    }
-
-    Uint256::from_be_bytes(uint256_bytes)
-}
-
 fn get_circuit_max_vote_options(vote_option_tree_depth: &Uint256) -> u128 {
     if *vote_option_tree_depth == Uint256::from_u128(1) {
         CIRCUIT_2_1_1_5_MAX_OPTIONS
@@ -880,26 +859,21 @@ pub fn execute_process_message(
     let mut processed_msg_count = PROCESSED_MSG_COUNT.load(deps.storage)?;
     let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
 
-    // Check that all messages have not been processed yet
-    assert!(
-        processed_msg_count < msg_chain_length,
-        "all messages have been processed"
-    );
+    // Check that all messages have not been processed yet; this also keeps the
+    // batch index computation below from underflowing
+    if processed_msg_count >= msg_chain_length {
+        return Err(ContractError::AllMessagesProcessed {});
+    }
 
     // Create an array to store the input values for the SNARK proof
     let mut input: [Uint256; 7] = [Uint256::zero(); 7];
 
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
     let max_vote_options = MAX_VOTE_OPTIONS.load(deps.storage)?;
+    // circuit_type is 0 (1p1v) or 1 (qv), enforced at instantiation
     let circuit_type = CIRCUITTYPE.load(deps.storage)?;
-    if circuit_type == Uint256::from_u128(0u128) {
-        // 1p1v
-        input[0] = (num_sign_ups << 32) + max_vote_options; // packedVals
-    } else if circuit_type == Uint256::from_u128(1u128) {
-        // qv
-        input[0] = (num_sign_ups << 32) + (circuit_type << 64) + max_vote_options;
-        // packedVals
-    }
+    input[0] = pack_vals(num_sign_ups, circuit_type, max_vote_options); // packedVals
+
     // Load the coordinator's public key hash
     let coordinator_hash = COORDINATORHASH.load(deps.storage)?;
     input[1] = coordinator_hash; // coordPubKeyHash
@@ -1317,7 +1291,7 @@ fn execute_stop_tallying_period(
     let parameters = MACIPARAMETERS.load(deps.storage)?;
 
     // Calculate the results root
-    let results_root = qtr_lib.root_of(parameters.vote_option_tree_depth, results.clone());
+    let results_root = qtr_lib.root_of(parameters.vote_option_tree_depth, results.clone())?;
 
     // Calculate the tally commitment
     let tally_commitment = hash2([results_root, salt]);
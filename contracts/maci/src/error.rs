@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use maci_utils::SnarkjsError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -99,7 +100,7 @@ pub enum ContractError {
     #[error("Voting power is zero")]
     VotingPowerIsZero {},
 
-    #[error("Unsupported circuit type")]
+    #[error("Unsupported circuit type.")]
     UnsupportedCircuitType {},
 
     #[error("Unsupported certification system.")]
@@ -141,4 +142,16 @@ pub enum ContractError {
 
     #[error("Storage read failed for key: {key}")]
     StorageReadError { key: String },
+
+    #[error("Results must contain at least one vote option")]
+    EmptyResults {},
+
+    #[error("Results length {length} exceeds vote option tree capacity {capacity}")]
+    ResultsExceedTreeCapacity { length: u32, capacity: u32 },
+
+    #[error("All messages have already been processed")]
+    AllMessagesProcessed {},
+
+    #[error("snarkjs proof error: {0}")]
+    Snarkjs(#[from] SnarkjsError),
 }
@@ -7,7 +7,7 @@ pub mod plonk_parser;
 pub mod state;
 // pub mod utils;  // Moved to shared maci-utils library
 
-#[cfg(test)]
+#[cfg(any(feature = "mt", test))]
 pub mod multitest;
 
 pub use crate::error::ContractError;
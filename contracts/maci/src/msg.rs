@@ -1,9 +1,11 @@
+use crate::error::ContractError;
 use crate::state::{
     MaciParameters, MessageData, OracleWhitelistConfig, PeriodStatus, PubKey, RoundInfo,
     VotingPowerMode, VotingTime, WhitelistConfig,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Uint256};
+use maci_utils::{groth16_proof_from_snarkjs, groth16_proof_to_snarkjs, plonk_proof_from_snarkjs};
 
 #[cw_serde]
 pub struct WhitelistBase {
@@ -60,6 +62,19 @@ pub struct Groth16ProofType {
     pub c: String,
 }
 
+impl Groth16ProofType {
+    /// Convert a `snarkjs groth16 prove` proof JSON into the hex `a`/`b`/`c` the contract verifies
+    pub fn from_snarkjs(json: &str) -> Result<Self, ContractError> {
+        let (a, b, c) = groth16_proof_from_snarkjs(json)?;
+        Ok(Groth16ProofType { a, b, c })
+    }
+
+    /// Convert back to snarkjs proof JSON
+    pub fn to_snarkjs(&self) -> Result<String, ContractError> {
+        Ok(groth16_proof_to_snarkjs(&self.a, &self.b, &self.c)?)
+    }
+}
+
 #[cw_serde]
 pub struct PlonkVKeyType {
     pub n: usize,
@@ -71,6 +86,10 @@ pub struct PlonkVKeyType {
     pub g2_elements: Vec<String>,
 }
 
+/// A bellman_ce width-4 PlonK proof (`PlonkCsWidth4WithNextStepParams`, Keccak transcript).
+///
+/// Commitments are uncompressed G1 points, `x || y` as 64-byte big-endian hex; field
+/// elements are big-endian hex accepted by `ff_ce::from_hex`.
 #[cw_serde]
 pub struct PlonkProofType {
     pub num_inputs: usize,
@@ -89,6 +108,33 @@ pub struct PlonkProofType {
     pub opening_at_z_omega_proof: String,
 }
 
+impl PlonkProofType {
+    /// Convert a `snarkjs plonk prove` proof JSON into the contract's hex encoding.
+    /// snarkjs writes the public signals separately and the proof has no
+    /// `wire_values_at_z_omega`, so `num_inputs`, `n`, `input_values` and that
+    /// field are left empty for the caller to fill in. snarkjs proofs are 3-wire,
+    /// so the width-4 verifier still rejects them.
+    pub fn from_snarkjs_plonk(json: &str) -> Result<Self, ContractError> {
+        let proof = plonk_proof_from_snarkjs(json)?;
+        Ok(PlonkProofType {
+            num_inputs: 0,
+            n: 0,
+            input_values: vec![],
+            wire_commitments: proof.wire_commitments,
+            grand_product_commitment: proof.grand_product_commitment,
+            quotient_poly_commitments: proof.quotient_poly_commitments,
+            wire_values_at_z: proof.wire_values_at_z,
+            wire_values_at_z_omega: vec![],
+            grand_product_at_z_omega: proof.grand_product_at_z_omega,
+            quotient_polynomial_at_z: proof.quotient_polynomial_at_z,
+            linearization_polynomial_at_z: proof.linearization_polynomial_at_z,
+            permutation_polynomials_at_z: proof.permutation_polynomials_at_z,
+            opening_at_z_proof: proof.opening_at_z_proof,
+            opening_at_z_omega_proof: proof.opening_at_z_omega_proof,
+        })
+    }
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     SetRoundInfo {
@@ -862,4 +862,39 @@ mod test {
             "Different users should have different state indices"
         );
     }
+
+    #[test]
+    fn test_process_message_after_all_messages_processed() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_with_voting_time(&mut app, owner(), "test")
+            .unwrap();
+
+        // Voting starts, then ends with no messages published
+        app.update_block(next_block);
+        app.update_block(next_block);
+        contract.start_process(&mut app, owner()).unwrap();
+        assert_eq!(
+            Period {
+                status: PeriodStatus::Processing
+            },
+            contract.get_period(&app).unwrap()
+        );
+
+        // processed_msg_count == msg_chain_length == 0: rejected before the
+        // batch index is computed
+        let proof = Groth16ProofType {
+            a: String::new(),
+            b: String::new(),
+            c: String::new(),
+        };
+        let err = contract
+            .process_message(&mut app, owner(), Uint256::zero(), proof)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::AllMessagesProcessed {},
+            err.downcast().unwrap()
+        );
+    }
 }
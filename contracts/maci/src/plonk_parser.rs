@@ -270,3 +270,61 @@ where
         _marker: std::marker::PhantomData,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::PlonkProofType;
+    use bellman_ce::plonk::better_cs::cs::PlonkCsWidth4WithNextStepParams;
+    use pairing_ce::bn256::{Bn256, Fr};
+
+    #[test]
+    fn test_snarkjs_plonk_proof_parses() {
+        // Every commitment is the BN254 G1 generator
+        let g1 = ["1", "2", "1"];
+        let json = serde_json::json!({
+            "A": g1, "B": g1, "C": g1, "Z": g1,
+            "T1": g1, "T2": g1, "T3": g1, "Wxi": g1, "Wxiw": g1,
+            "eval_a": "1", "eval_b": "2", "eval_c": "3",
+            "eval_s1": "4", "eval_s2": "5", "eval_zw": "6",
+            "protocol": "plonk",
+            "curve": "bn128"
+        })
+        .to_string();
+
+        let proof = PlonkProofType::from_snarkjs_plonk(&json).unwrap();
+        let decode = |value: &String| hex::decode(value).unwrap();
+        let pof = parse_plonk_proof::<Bn256, PlonkCsWidth4WithNextStepParams>(PlonkProofStr {
+            num_inputs: proof.num_inputs,
+            n: proof.n,
+            input_values: proof.input_values,
+            wire_commitments: proof.wire_commitments.iter().map(decode).collect(),
+            grand_product_commitment: decode(&proof.grand_product_commitment),
+            quotient_poly_commitments: proof.quotient_poly_commitments.iter().map(decode).collect(),
+            wire_values_at_z: proof.wire_values_at_z,
+            wire_values_at_z_omega: proof.wire_values_at_z_omega,
+            grand_product_at_z_omega: proof.grand_product_at_z_omega,
+            quotient_polynomial_at_z: proof.quotient_polynomial_at_z,
+            linearization_polynomial_at_z: proof.linearization_polynomial_at_z,
+            permutation_polynomials_at_z: proof.permutation_polynomials_at_z,
+            opening_at_z_proof: decode(&proof.opening_at_z_proof),
+            opening_at_z_omega_proof: decode(&proof.opening_at_z_omega_proof),
+        })
+        .unwrap();
+
+        assert_eq!(pof.wire_commitments, vec![G1Affine::one(); 3]);
+        assert_eq!(pof.quotient_poly_commitments, vec![G1Affine::one(); 3]);
+        assert_eq!(pof.grand_product_commitment, G1Affine::one());
+        assert_eq!(pof.opening_at_z_proof, G1Affine::one());
+        assert_eq!(pof.opening_at_z_omega_proof, G1Affine::one());
+        let fr = |value: &str| from_hex::<Fr>(value).unwrap();
+        assert_eq!(pof.wire_values_at_z, vec![fr("01"), fr("02"), fr("03")]);
+        assert_eq!(pof.permutation_polynomials_at_z, vec![fr("04"), fr("05")]);
+        assert_eq!(pof.grand_product_at_z_omega, fr("06"));
+
+        assert!(matches!(
+            PlonkProofType::from_snarkjs_plonk(&json.replace("plonk", "groth16")),
+            Err(ContractError::Snarkjs(_))
+        ));
+    }
+}
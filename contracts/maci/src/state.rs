@@ -1,3 +1,4 @@
+use crate::error::ContractError;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Binary, Timestamp, Uint128, Uint256};
 use cw_storage_plus::{Item, Map};
@@ -83,11 +84,7 @@ pub const CURRENT_TALLY_COMMITMENT: Item<Uint256> = Item::new("current_tally_com
 pub const RESULT: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
 pub const TOTAL_RESULT: Item<Uint256> = Item::new("total_result");
 
-#[cw_serde]
-pub struct PubKey {
-    pub x: Uint256,
-    pub y: Uint256,
-}
+pub use maci_utils::PubKey;
 
 #[cw_serde]
 pub struct StateLeaf {
@@ -148,7 +145,7 @@ pub struct QuinaryTreeRoot {
 impl QuinaryTreeRoot {
     const DEGREE: u32 = 5;
 
-    pub fn root_of(&self, depth: Uint256, nodes: Vec<Uint256>) -> Uint256 {
+    pub fn root_of(&self, depth: Uint256, nodes: Vec<Uint256>) -> Result<Uint256, ContractError> {
         let _depth = depth
             .to_string()
             .parse()
@@ -156,7 +153,12 @@ impl QuinaryTreeRoot {
         let capacity = Self::DEGREE.pow(_depth);
         let length = nodes.len() as u32;
 
-        assert!(capacity >= length, "overflow");
+        if length == 0 {
+            return Err(ContractError::EmptyResults {});
+        }
+        if length > capacity {
+            return Err(ContractError::ResultsExceedTreeCapacity { length, capacity });
+        }
 
         let mut c = capacity / Self::DEGREE;
         let mut pl = (length - 1) / Self::DEGREE + 1;
@@ -201,7 +203,7 @@ impl QuinaryTreeRoot {
         if result == Uint256::zero() {
             result = self.get_zero(_depth);
         }
-        result
+        Ok(result)
     }
 
     fn get_zero(&self, height: u32) -> Uint256 {
@@ -325,4 +327,46 @@ mod tests {
         assert!(config.is_admin(alice.as_ref()));
         assert!(!config.is_admin("other"));
     }
+
+    #[test]
+    fn root_of_rejects_empty_or_oversized_results() {
+        let qtr_lib = QuinaryTreeRoot {
+            zeros: [Uint256::zero(); 9],
+        };
+
+        assert!(qtr_lib
+            .root_of(Uint256::from_u128(1), vec![Uint256::from_u128(1); 5])
+            .is_ok());
+        assert_eq!(
+            qtr_lib
+                .root_of(Uint256::from_u128(1), vec![Uint256::from_u128(1); 6])
+                .unwrap_err(),
+            ContractError::ResultsExceedTreeCapacity {
+                length: 6,
+                capacity: 5
+            }
+        );
+        assert_eq!(
+            qtr_lib.root_of(Uint256::from_u128(1), vec![]).unwrap_err(),
+            ContractError::EmptyResults {}
+        );
+    }
+
+    #[test]
+    fn period_status_serializes_as_stable_strings() {
+        let cases = [
+            (PeriodStatus::Pending, "\"pending\""),
+            (PeriodStatus::Voting, "\"voting\""),
+            (PeriodStatus::Processing, "\"processing\""),
+            (PeriodStatus::Tallying, "\"tallying\""),
+            (PeriodStatus::Ended, "\"ended\""),
+        ];
+        for (status, json) in cases {
+            assert_eq!(cosmwasm_std::to_json_string(&status).unwrap(), json);
+            assert_eq!(
+                cosmwasm_std::from_json::<PeriodStatus>(json).unwrap(),
+                status
+            );
+        }
+    }
 }
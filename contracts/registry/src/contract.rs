@@ -2,8 +2,8 @@ use bech32::{self};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coins, from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply,
-    Response, StdError, StdResult, SubMsg, SubMsgResponse, Uint128, Uint256, WasmMsg,
+    attr, coins, from_json, to_json_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo,
+    Order, Reply, Response, StdError, StdResult, SubMsg, SubMsgResponse, Uint128, Uint256, WasmMsg,
 };
 use maci_utils::is_on_babyjubjub_curve;
 
@@ -11,16 +11,18 @@ use crate::error::ContractError;
 use crate::migrates::migrate_v0_1_6::migrate_v0_1_6;
 use crate::msg::{ExecuteMsg, InstantiateMsg, InstantiationData, MigrateMsg, QueryMsg};
 use crate::state::{
-    Admin, CircuitChargeConfig, DelayConfig, FeeConfig, ValidatorSet, ADDRESS_TO_POLL_ID, ADMIN,
-    AMACI_CODE_ID, CIRCUIT_CHARGE_CONFIG, COORDINATOR_PUBKEY_MAP, DELAY_CONFIG, FEE_CONFIG,
-    MACI_OPERATOR_IDENTITY, MACI_OPERATOR_PUBKEY, MACI_OPERATOR_SET, MACI_VALIDATOR_LIST,
-    MACI_VALIDATOR_OPERATOR_SET, NEXT_POLL_ID, OPERATOR, POLL_ID_TO_ADDRESS,
+    default_config, Admin, CircuitChargeConfig, Config, DelayConfig, FeeConfig, ValidatorSet,
+    ADDRESS_TO_POLL_ID, ADMIN, AMACI_CODE_ID, CIRCUIT_CHARGE_CONFIG, CONFIG,
+    COORDINATOR_PUBKEY_MAP, DELAY_CONFIG, FEE_CONFIG, MACI_OPERATOR_IDENTITY, MACI_OPERATOR_PUBKEY,
+    MACI_OPERATOR_SET, MACI_VALIDATOR_LIST, MACI_VALIDATOR_OPERATOR_SET, NEXT_POLL_ID, OPERATOR,
+    POLL_ID_TO_ADDRESS, SLASHED_ROUNDS,
 };
 use crate::utils::get_maci_parameters;
 use cosmwasm_std::Decimal;
 use cw2::set_contract_version;
 use cw_amaci::msg::{
     InstantiateMsg as AMaciInstantiateMsg, InstantiationData as AMaciInstantiationData,
+    OperatorPerformance, QueryMsg as AMaciQueryMsg,
 };
 use cw_amaci::state::{PubKey, RoundInfo, VotingTime};
 use cw_utils::parse_instantiate_response_data;
@@ -71,6 +73,9 @@ pub fn instantiate(
     };
     DELAY_CONFIG.save(deps.storage, &delay_config)?;
 
+    // Operator bond / slash config
+    CONFIG.save(deps.storage, &default_config())?;
+
     // Initialize poll ID counter starting from 1
     NEXT_POLL_ID.save(deps.storage, &1u64)?;
 
@@ -138,6 +143,9 @@ pub fn execute(
         ExecuteMsg::UpdateDelayConfig { config } => {
             execute_update_delay_config(deps, env, info, config)
         }
+        ExecuteMsg::UpdateConfig { config } => execute_update_config(deps, env, info, config),
+        ExecuteMsg::Bond {} => execute_bond(deps, env, info),
+        ExecuteMsg::SlashOperator { round } => execute_slash_operator(deps, env, info, round),
     }
 }
 
@@ -183,14 +191,21 @@ pub fn execute_create_round(
     let maci_parameters = get_maci_parameters()?;
 
     // Verify payment
-    let denom = "peaka".to_string();
+    let config = CONFIG.load(deps.storage)?;
     let amount = info
         .funds
         .iter()
-        .find(|fund| fund.denom == denom)
+        .find(|fund| fund.denom == config.denom)
         .map(|fund| fund.amount)
         .unwrap_or(Uint128::zero());
 
+    if amount < config.min_deposit_amount {
+        return Err(ContractError::InsufficientDeposit {
+            required: config.min_deposit_amount,
+            provided: amount,
+        });
+    }
+
     if amount != required_fee {
         return Err(if amount < required_fee {
             ContractError::InsufficientFee {
@@ -230,6 +245,7 @@ pub fn execute_create_round(
         circuit_type,
         certification_system,
         poll_id,
+        create_round_window: None,
         deactivate_enabled,
         // Unified MACI Configuration
         voice_credit_mode,
@@ -238,6 +254,7 @@ pub fn execute_create_round(
         message_fee: fee_config.message_fee,
         deactivate_fee: fee_config.deactivate_fee,
         signup_fee: fee_config.signup_fee,
+        fee_denom: Some(config.denom.clone()),
         base_delay: delay_config.base_delay,
         message_delay: delay_config.message_delay,
         signup_delay: delay_config.signup_delay,
@@ -250,7 +267,7 @@ pub fn execute_create_round(
             admin: Some(env.contract.address.to_string()),
             code_id: amaci_code_id,
             msg: to_json_binary(&init_msg)?,
-            funds: coins(required_fee.u128(), "peaka"),
+            funds: coins(required_fee.u128(), config.denom),
             label: "Unified MACI".to_string(),
         },
         CREATED_ROUND_REPLY_ID,
@@ -285,6 +302,7 @@ pub fn execute_set_maci_operator(
         return Err(ContractError::ExistedMaciOperator {});
     }
 
+    let mut refund = None;
     if is_validator_operator_set(deps.as_ref(), &info.sender)? {
         let old_operator = MACI_VALIDATOR_OPERATOR_SET.load(deps.storage, &info.sender)?;
 
@@ -300,6 +318,7 @@ pub fn execute_set_maci_operator(
             MACI_OPERATOR_PUBKEY.remove(deps.storage, &old_operator);
         }
 
+        refund = refund_operator_bond(deps.as_ref(), &old_operator)?;
         MACI_OPERATOR_SET.remove(deps.storage, &old_operator);
 
         MACI_VALIDATOR_OPERATOR_SET.save(deps.storage, &info.sender, &operator)?;
@@ -309,6 +328,7 @@ pub fn execute_set_maci_operator(
     MACI_VALIDATOR_OPERATOR_SET.save(deps.storage, &info.sender, &operator)?;
     MACI_OPERATOR_SET.save(deps.storage, &operator, &Uint128::from(0u128))?;
     Ok(Response::new()
+        .add_messages(refund)
         .add_attribute("action", "set_maci_operator")
         .add_attribute("validator", &info.sender.to_string())
         .add_attribute("maci_operator", operator.to_string()))
@@ -325,7 +345,7 @@ pub fn execute_set_maci_operator_pubkey(
         Err(ContractError::Unauthorized {})
     } else {
         if !is_on_babyjubjub_curve(pubkey.x, pubkey.y) {
-            return Err(ContractError::InvalidPubKey {});
+            return Err(ContractError::InvalidOperatorPubkey {});
         }
 
         if COORDINATOR_PUBKEY_MAP.has(
@@ -412,9 +432,11 @@ pub fn execute_remove_validator(
         maci_validator_set.remove_validator(&address);
         MACI_VALIDATOR_LIST.save(deps.storage, &maci_validator_set)?;
 
+        let mut refund = None;
         if MACI_VALIDATOR_OPERATOR_SET.has(deps.storage, &address) {
             let old_operator = MACI_VALIDATOR_OPERATOR_SET.load(deps.storage, &address)?;
 
+            refund = refund_operator_bond(deps.as_ref(), &old_operator)?;
             MACI_VALIDATOR_OPERATOR_SET.remove(deps.storage, &address);
             MACI_OPERATOR_SET.remove(deps.storage, &old_operator);
 
@@ -440,6 +462,7 @@ pub fn execute_remove_validator(
         //     Map::new("coordinator_pubkey_map"); //
 
         Ok(Response::new()
+            .add_messages(refund)
             .add_attribute("action", "remove_validator")
             .add_attribute("validator", address.to_string()))
     }
@@ -539,6 +562,163 @@ pub fn execute_update_delay_config(
         .add_attribute("deactivate_delay", config.deactivate_delay.to_string()))
 }
 
+pub fn execute_update_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: Config,
+) -> Result<Response, ContractError> {
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if config.denom.is_empty() {
+        return Err(ContractError::InvalidConfig {
+            reason: "denom must not be empty".to_string(),
+        });
+    }
+    if config.min_deposit_amount.is_zero() || config.slash_amount.is_zero() {
+        return Err(ContractError::InvalidConfig {
+            reason: "min_deposit_amount and slash_amount must be non-zero".to_string(),
+        });
+    }
+    if config.slash_amount > config.min_deposit_amount {
+        return Err(ContractError::InvalidConfig {
+            reason: "slash_amount must not exceed min_deposit_amount".to_string(),
+        });
+    }
+
+    // Bonds are held in the configured denom, so switching it would strand them
+    let current = CONFIG.load(deps.storage)?;
+    if config.denom != current.denom {
+        for entry in MACI_OPERATOR_SET.range(deps.storage, None, None, Order::Ascending) {
+            let (_, bond) = entry?;
+            if !bond.is_zero() {
+                return Err(ContractError::DenomChangeWithBonds {});
+            }
+        }
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("denom", config.denom)
+        .add_attribute("min_deposit_amount", config.min_deposit_amount.to_string())
+        .add_attribute("slash_amount", config.slash_amount.to_string()))
+}
+
+// maci operator
+pub fn execute_bond(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if !is_operator_set(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let amount = info
+        .funds
+        .iter()
+        .find(|fund| fund.denom == config.denom)
+        .map(|fund| fund.amount)
+        .unwrap_or(Uint128::zero());
+    if amount.is_zero() {
+        return Err(ContractError::MissingDenom(config.denom));
+    }
+
+    let bond = MACI_OPERATOR_SET
+        .load(deps.storage, &info.sender)?
+        .checked_add(amount)?;
+    MACI_OPERATOR_SET.save(deps.storage, &info.sender, &bond)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "bond")
+        .add_attribute("maci_operator", info.sender.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("bond", bond.to_string()))
+}
+
+/// Slashes the operator of a registry-created round whose delay records
+/// lowered its miss rate. Each round can be slashed at most once.
+pub fn execute_slash_operator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    round: Addr,
+) -> Result<Response, ContractError> {
+    if !is_operator(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !ADDRESS_TO_POLL_ID.has(deps.storage, &round) {
+        return Err(ContractError::UnknownRound {
+            round: round.to_string(),
+        });
+    }
+    if SLASHED_ROUNDS.has(deps.storage, &round) {
+        return Err(ContractError::AlreadySlashed {
+            round: round.to_string(),
+        });
+    }
+
+    let round_operator: Addr = deps
+        .querier
+        .query_wasm_smart(&round, &AMaciQueryMsg::Operator {})?;
+    let performance: OperatorPerformance = deps
+        .querier
+        .query_wasm_smart(&round, &AMaciQueryMsg::GetOperatorPerformance {})?;
+
+    if performance.miss_rate >= Uint256::from_u128(100u128) {
+        return Err(ContractError::NoOperatorDelay {
+            operator: round_operator.to_string(),
+        });
+    }
+
+    let bond = MACI_OPERATOR_SET
+        .may_load(deps.storage, &round_operator)?
+        .unwrap_or_default();
+    if bond.is_zero() {
+        return Err(ContractError::NoBondToSlash {
+            operator: round_operator.to_string(),
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let slash_amount = std::cmp::min(config.slash_amount, bond);
+    MACI_OPERATOR_SET.save(deps.storage, &round_operator, &(bond - slash_amount))?;
+    SLASHED_ROUNDS.save(deps.storage, &round, &slash_amount)?;
+
+    let admin = ADMIN.load(deps.storage)?.admin;
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: admin.to_string(),
+            amount: coins(slash_amount.u128(), config.denom),
+        })
+        .add_attribute("action", "slash_operator")
+        .add_attribute("round", round.to_string())
+        .add_attribute("maci_operator", round_operator.to_string())
+        .add_attribute("miss_rate", performance.miss_rate.to_string())
+        .add_attribute("slash_amount", slash_amount.to_string()))
+}
+
+// Refunds whatever is left of an operator's bond before it is deregistered
+fn refund_operator_bond(deps: Deps, operator: &Addr) -> Result<Option<BankMsg>, ContractError> {
+    let bond = MACI_OPERATOR_SET
+        .may_load(deps.storage, operator)?
+        .unwrap_or_default();
+    if bond.is_zero() {
+        return Ok(None);
+    }
+
+    let denom = CONFIG.load(deps.storage)?.denom;
+    Ok(Some(BankMsg::Send {
+        to_address: operator.to_string(),
+        amount: coins(bond.u128(), denom),
+    }))
+}
+
 // Only admin can execute
 fn is_admin(deps: Deps, sender: &str) -> StdResult<bool> {
     let cfg = ADMIN.load(deps.storage)?;
@@ -590,7 +770,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 .unwrap_or_default(),
         ),
         QueryMsg::GetMaciOperatorPubkey { address } => {
-            to_json_binary(&MACI_OPERATOR_PUBKEY.load(deps.storage, &address)?)
+            to_json_binary(&MACI_OPERATOR_PUBKEY.may_load(deps.storage, &address)?)
         }
         QueryMsg::GetMaciOperatorIdentity { address } => {
             to_json_binary(&MACI_OPERATOR_IDENTITY.load(deps.storage, &address)?)
@@ -604,6 +784,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetDelayConfig {} => {
             to_json_binary(&DELAY_CONFIG.load(deps.storage)?)
         }
+        QueryMsg::GetConfig {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::GetPollId { address } => {
             to_json_binary(&ADDRESS_TO_POLL_ID.load(deps.storage, &address)?)
         }
@@ -26,8 +26,11 @@ pub enum ContractError {
     #[error("This Maci Operator is Already Register")]
     ExistedMaciOperator {},
 
-    #[error("Insufficient deposit amount, minimum deposit {min_deposit_amount}")]
-    InsufficientDeposit { min_deposit_amount: Uint128 },
+    #[error("Insufficient deposit. Required: {required}, provided: {provided}")]
+    InsufficientDeposit {
+        required: Uint128,
+        provided: Uint128,
+    },
 
     #[error("No claims that can be released currently")]
     NothingToClaim {},
@@ -68,8 +71,8 @@ pub enum ContractError {
     #[error("This pubkey is already existed.")]
     PubkeyExisted {},
 
-    #[error("Invalid pubkey: coordinates must be on the BabyJubJub curve")]
-    InvalidPubKey {},
+    #[error("Invalid operator pubkey: coordinates must be on the BabyJubJub curve")]
+    InvalidOperatorPubkey {},
 
     #[error("Not set operator pubkey.")]
     NotSetOperatorPubkey,
@@ -108,6 +111,24 @@ pub enum ContractError {
     #[error("Failed to parse numeric value: {value}, reason: {reason}")]
     ParseError { value: String, reason: String },
 
+    #[error("Round {round} was not created by this registry")]
+    UnknownRound { round: String },
+
+    #[error("Round {round} has already been slashed")]
+    AlreadySlashed { round: String },
+
+    #[error("Operator {operator} has no recorded delays in this round")]
+    NoOperatorDelay { operator: String },
+
+    #[error("Operator {operator} has no bond to slash")]
+    NoBondToSlash { operator: String },
+
+    #[error("Invalid config: {reason}")]
+    InvalidConfig { reason: String },
+
+    #[error("Cannot change the bond denom while operators still have bonds")]
+    DenomChangeWithBonds {},
+
     #[error("Invalid Keybase identity: must be exactly 16 uppercase hexadecimal characters (0-9, A-F)")]
     InvalidIdentity {},
 }
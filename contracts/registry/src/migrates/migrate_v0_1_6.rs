@@ -1,6 +1,6 @@
 use crate::error::ContractError;
 use crate::migrates::migrate_v0_1_5::migrate_v0_1_5;
-use crate::state::{DelayConfig, FeeConfig, DELAY_CONFIG, FEE_CONFIG};
+use crate::state::{default_config, DelayConfig, FeeConfig, CONFIG, DELAY_CONFIG, FEE_CONFIG};
 use cosmwasm_std::{Attribute, DepsMut, Response, Uint128};
 
 pub fn migrate_v0_1_6(mut deps: DepsMut) -> Result<Response, ContractError> {
@@ -29,12 +29,17 @@ pub fn migrate_v0_1_6(mut deps: DepsMut) -> Result<Response, ContractError> {
         DELAY_CONFIG.save(deps.storage, &delay_config)?;
     }
 
+    // Initialize CONFIG (operator bond / slash) if not present
+    if CONFIG.may_load(deps.storage)?.is_none() {
+        CONFIG.save(deps.storage, &default_config())?;
+    }
+
     let attributes: Vec<Attribute> = vec![
         Attribute::new("action", "migrate"),
         Attribute::new("version", "0.1.6"),
         Attribute::new(
             "changes",
-            "initialize_fee_config,initialize_delay_config",
+            "initialize_fee_config,initialize_delay_config,initialize_config",
         ),
     ];
 
@@ -6,7 +6,7 @@ use cw_amaci::{
     state::{PubKey, RoundInfo, VoiceCreditMode, VotingTime},
 };
 
-use crate::state::{CircuitChargeConfig, DelayConfig, FeeConfig, ValidatorSet};
+use crate::state::{CircuitChargeConfig, Config, DelayConfig, FeeConfig, ValidatorSet};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -79,6 +79,18 @@ pub enum ExecuteMsg {
     UpdateDelayConfig {
         config: DelayConfig,
     },
+    /// Replaces the operator bond / slash config. Admin permission. The denom can't
+    /// change while any operator still has a bond.
+    UpdateConfig {
+        config: Config,
+    },
+    /// MACI operator adds the sent funds to its bond.
+    Bond {},
+    /// Slashes the round operator's bond to admin if the round recorded delays.
+    /// Operator permission.
+    SlashOperator {
+        round: Addr,
+    },
 }
 
 #[cw_serde]
@@ -105,7 +117,7 @@ pub enum QueryMsg {
     #[returns(Addr)]
     GetValidatorOperator { address: Addr },
 
-    #[returns(PubKey)]
+    #[returns(Option<PubKey>)]
     GetMaciOperatorPubkey { address: Addr },
 
     #[returns(String)]
@@ -123,6 +135,10 @@ pub enum QueryMsg {
     #[returns(DelayConfig)]
     GetDelayConfig {},
 
+    /// Returns the operator bond / slash config.
+    #[returns(Config)]
+    GetConfig {},
+
     #[returns(u64)]
     GetPollId { address: Addr },
 
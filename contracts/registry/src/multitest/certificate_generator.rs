@@ -1,5 +1,6 @@
 use base64::{engine::general_purpose, Engine as _};
-use cosmwasm_std::{Addr, Uint256};
+use cosmwasm_std::Addr;
+use maci_utils::address_to_uint256;
 use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
 use serde_json;
 use sha2::{Digest, Sha256};
@@ -10,29 +11,6 @@ const PRIVATE_KEY_HEX: &str = "84d85037a14db4a7a1424084cca70211685ad65f7325c4d26
 // Pubkey (base64) - Keep consistent with api-maci
 const PUBKEY_B64: &str = "A9ekxvWjYNpnHTasS008PG+EuF2ssIkUPaDdnn8ZdzTb";
 
-/// Convert a contract address to Uint256 format
-/// This function takes the address bytes and converts them to a Uint256
-fn address_to_uint256(address: &Addr) -> Uint256 {
-    let address_bytes = address.as_bytes();
-
-    // Use SHA256 hash to convert the address to a fixed-length 32-byte format
-    let mut hasher = Sha256::new();
-    hasher.update(address_bytes);
-    let hash_result = hasher.finalize();
-
-    // Convert the hash bytes to Uint256
-    let mut bytes = [0u8; 32];
-    bytes.copy_from_slice(&hash_result[..]);
-
-    // Convert bytes to Uint256 (big-endian)
-    let mut uint256_bytes = [0u8; 32];
-    for (i, &byte) in bytes.iter().enumerate() {
-        uint256_bytes[31 - i] = byte; // Reverse for little-endian to big-endian conversion
-    }
-
-    Uint256::from_be_bytes(uint256_bytes)
-}
-
 /// Generate certificate for given user pubkey and amount (for registry oracle mode)
 pub fn generate_certificate_for_pubkey(
     contract_address: &str,
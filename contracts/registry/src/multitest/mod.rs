@@ -8,7 +8,7 @@ use anyhow::Result as AnyResult;
 use crate::{
     contract::{execute, instantiate, migrate, query, reply},
     msg::*,
-    state::{CircuitChargeConfig, ValidatorSet},
+    state::{CircuitChargeConfig, Config, ValidatorSet},
 };
 use cosmwasm_std::testing::MockApi;
 use cosmwasm_std::{Addr, Coin, StdResult, Timestamp, Uint256};
@@ -61,18 +61,7 @@ pub fn uint256_from_hex_string(hex_string: &str) -> Uint256 {
 }
 
 pub fn hex_to_decimal(hex_bytes: &str) -> [u8; 32] {
-    let bytes = hex::decode(hex_bytes).unwrap_or_else(|_| vec![]);
-    let decimal_values: Vec<u8> = bytes.iter().cloned().collect();
-
-    let mut array: [u8; 32] = [0; 32];
-
-    if decimal_values.len() >= 32 {
-        array.copy_from_slice(&decimal_values[..32]);
-    } else {
-        array[..decimal_values.len()].copy_from_slice(&decimal_values);
-    }
-
-    array
+    maci_utils::hex_to_decimal_checked(hex_bytes).expect("Invalid hex string")
 }
 
 #[derive(Clone, Debug, Copy)]
@@ -191,11 +180,11 @@ impl AmaciRegistryContract {
             operator,
             round_info,
             vote_option_map: vec![
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
+                "Option 1".to_string(),
+                "Option 2".to_string(),
+                "Option 3".to_string(),
+                "Option 4".to_string(),
+                "Option 5".to_string(),
             ],
             voting_time: VotingTime {
                 start_time,
@@ -257,11 +246,11 @@ impl AmaciRegistryContract {
             operator,
             round_info,
             vote_option_map: vec![
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
+                "Option 1".to_string(),
+                "Option 2".to_string(),
+                "Option 3".to_string(),
+                "Option 4".to_string(),
+                "Option 5".to_string(),
             ],
             voting_time: VotingTime {
                 start_time,
@@ -321,11 +310,11 @@ impl AmaciRegistryContract {
             operator,
             round_info,
             vote_option_map: vec![
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
+                "Option 1".to_string(),
+                "Option 2".to_string(),
+                "Option 3".to_string(),
+                "Option 4".to_string(),
+                "Option 5".to_string(),
             ],
             voting_time: VotingTime {
                 start_time,
@@ -369,11 +358,11 @@ impl AmaciRegistryContract {
             operator,
             round_info,
             vote_option_map: vec![
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
+                "Option 1".to_string(),
+                "Option 2".to_string(),
+                "Option 3".to_string(),
+                "Option 4".to_string(),
+                "Option 5".to_string(),
             ],
             voting_time: VotingTime {
                 start_time,
@@ -418,11 +407,11 @@ impl AmaciRegistryContract {
             operator,
             round_info,
             vote_option_map: vec![
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
+                "Option 1".to_string(),
+                "Option 2".to_string(),
+                "Option 3".to_string(),
+                "Option 4".to_string(),
+                "Option 5".to_string(),
             ],
             voting_time: VotingTime {
                 start_time,
@@ -470,11 +459,11 @@ impl AmaciRegistryContract {
             operator,
             round_info,
             vote_option_map: vec![
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
-                "".to_string(),
+                "Option 1".to_string(),
+                "Option 2".to_string(),
+                "Option 3".to_string(),
+                "Option 4".to_string(),
+                "Option 5".to_string(),
             ],
             voting_time: VotingTime {
                 start_time,
@@ -569,11 +558,51 @@ impl AmaciRegistryContract {
         )
     }
 
+    #[track_caller]
+    pub fn update_config(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        config: Config,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::UpdateConfig { config },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn bond(&self, app: &mut App, sender: Addr, send_funds: &[Coin]) -> AnyResult<AppResponse> {
+        app.execute_contract(sender, self.addr(), &ExecuteMsg::Bond {}, send_funds)
+    }
+
+    #[track_caller]
+    pub fn slash_operator(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        round: Addr,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SlashOperator { round },
+            &[],
+        )
+    }
+
     pub fn get_admin(&self, app: &App) -> StdResult<AdminResponse> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::Admin {})
     }
 
+    pub fn get_config(&self, app: &App) -> StdResult<Config> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetConfig {})
+    }
+
     pub fn is_maci_operator(&self, app: &App, address: Addr) -> StdResult<bool> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::IsMaciOperator { address })
@@ -603,7 +632,7 @@ impl AmaciRegistryContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetValidatorOperator { address })
     }
 
-    pub fn get_operator_pubkey(&self, app: &App, address: Addr) -> StdResult<PubKey> {
+    pub fn get_operator_pubkey(&self, app: &App, address: Addr) -> StdResult<Option<PubKey>> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::GetMaciOperatorPubkey { address })
     }
@@ -463,7 +463,7 @@ fn create_round_with_reward_should_works() {
     _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
 
     let user1_operator_pubkey = contract.get_operator_pubkey(&app, operator()).unwrap();
-    assert_eq!(operator_pubkey1(), user1_operator_pubkey);
+    assert_eq!(Some(operator_pubkey1()), user1_operator_pubkey);
 
     // _ = contract.migrate_v1(&mut app, owner(), amaci_code_id.id()).unwrap();
 
@@ -677,7 +677,7 @@ fn create_round_with_voting_time_qv_amaci_should_works() {
     _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
 
     let user1_operator_pubkey = contract.get_operator_pubkey(&app, operator()).unwrap();
-    assert_eq!(operator_pubkey1(), user1_operator_pubkey);
+    assert_eq!(Some(operator_pubkey1()), user1_operator_pubkey);
 
     // _ = contract.migrate_v1(&mut app, owner(), amaci_code_id.id()).unwrap();
 
@@ -747,7 +747,10 @@ fn create_round_with_voting_time_qv_amaci_should_works() {
 
     let vote_option_map = maci_contract.amaci_vote_option_map(&app).unwrap();
     let max_vote_options = maci_contract.amaci_max_vote_options(&app).unwrap();
-    assert_eq!(vote_option_map, vec!["", "", "", "", ""]);
+    assert_eq!(
+        vote_option_map,
+        vec!["Option 1", "Option 2", "Option 3", "Option 4", "Option 5"]
+    );
     assert_eq!(max_vote_options, Uint256::from_u128(5u128));
     _ = maci_contract.amaci_set_vote_option_map(&mut app, creator());
     let new_vote_option_map = maci_contract.amaci_vote_option_map(&app).unwrap();
@@ -1342,7 +1345,7 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
     _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
 
     let user1_operator_pubkey = contract.get_operator_pubkey(&app, operator()).unwrap();
-    assert_eq!(operator_pubkey1(), user1_operator_pubkey);
+    assert_eq!(Some(operator_pubkey1()), user1_operator_pubkey);
 
     // _ = contract.migrate_v1(&mut app, owner(), amaci_code_id.id()).unwrap();
 
@@ -1407,7 +1410,10 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
 
     let vote_option_map = maci_contract.amaci_vote_option_map(&app).unwrap();
     let max_vote_options = maci_contract.amaci_max_vote_options(&app).unwrap();
-    assert_eq!(vote_option_map, vec!["", "", "", "", ""]);
+    assert_eq!(
+        vote_option_map,
+        vec!["Option 1", "Option 2", "Option 3", "Option 4", "Option 5"]
+    );
     assert_eq!(max_vote_options, Uint256::from_u128(5u128));
     _ = maci_contract.amaci_set_vote_option_map(&mut app, creator());
     let new_vote_option_map = maci_contract.amaci_vote_option_map(&app).unwrap();
@@ -1993,7 +1999,7 @@ fn create_round_with_qv_oracle_mode_amaci_should_works() {
     _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
 
     let user1_operator_pubkey = contract.get_operator_pubkey(&app, operator()).unwrap();
-    assert_eq!(operator_pubkey1(), user1_operator_pubkey);
+    assert_eq!(Some(operator_pubkey1()), user1_operator_pubkey);
 
     let small_base_payamount = 30_000_000_000_000_000_000u128; // 30 DORA
 
@@ -2051,7 +2057,10 @@ fn create_round_with_qv_oracle_mode_amaci_should_works() {
 
     let vote_option_map = maci_contract.amaci_vote_option_map(&app).unwrap();
     let max_vote_options = maci_contract.amaci_max_vote_options(&app).unwrap();
-    assert_eq!(vote_option_map, vec!["", "", "", "", ""]);
+    assert_eq!(
+        vote_option_map,
+        vec!["Option 1", "Option 2", "Option 3", "Option 4", "Option 5"]
+    );
     assert_eq!(max_vote_options, Uint256::from_u128(5u128));
 
     _ = maci_contract.amaci_set_vote_option_map(&mut app, creator());
@@ -4402,6 +4411,11 @@ fn setup_registry_with_operator() -> (cw_multi_test::App, super::AmaciRegistryCo
                 .bank
                 .init_balance(storage, &admin(), coins(1_000_000_000_000_000_000_000u128, DORA_DEMON))
                 .unwrap();
+            // 200 DORA for the round creation deposit
+            router
+                .bank
+                .init_balance(storage, &creator(), coins(200_000_000_000_000_000_000u128, DORA_DEMON))
+                .unwrap();
         });
 
     let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
@@ -4517,6 +4531,396 @@ fn set_maci_operator_identity_empty_should_fail() {
 
 // ─── end of set_maci_operator_identity tests ─────────────────────────────────
 
+#[test]
+fn slash_operator_for_delayed_round_should_work() {
+    use crate::error::ContractError;
+
+    let creator_coin_amount = 200_000_000_000_000_000_000u128; // 200 DORA
+    let bond_amount = 20_000_000_000_000_000_000u128; // 20 DORA
+    let slash_amount = 10_000_000_000_000_000_000u128; // 10 DORA
+
+    let mut app = AppBuilder::new()
+        .with_api(dora_mock_api())
+        .build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
+                .unwrap();
+            router
+                .bank
+                .init_balance(storage, &operator(), coins(bond_amount, DORA_DEMON))
+                .unwrap();
+        });
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(&mut app, creator(), amaci_code_id.id(), "Registry")
+        .unwrap();
+
+    _ = contract.set_validators(&mut app, admin());
+    _ = contract.set_maci_operator(&mut app, user1(), operator());
+    _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+    contract
+        .bond(&mut app, operator(), &coins(bond_amount, DORA_DEMON))
+        .unwrap();
+
+    let resp = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(1u128),
+            Uint256::from_u128(0u128),
+            &coins(30_000_000_000_000_000_000u128, DORA_DEMON),
+        )
+        .unwrap();
+    let round: InstantiationData = from_json(&resp.data.unwrap()).unwrap();
+    let maci_contract = MaciContract::new(round.addr.clone());
+
+    // No delays recorded yet: nothing to slash
+    let err = contract
+        .slash_operator(&mut app, admin(), round.addr.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NoOperatorDelay {
+            operator: operator().to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // Tally an empty round an hour after voting ends, well past the allowed delay
+    app.update_block(|block| {
+        block.time = Timestamp::from_nanos(1571797424879000000)
+            .plus_minutes(11)
+            .plus_seconds(3600);
+    });
+    maci_contract
+        .amaci_start_process(&mut app, operator())
+        .unwrap();
+    maci_contract
+        .amaci_stop_processing(&mut app, operator())
+        .unwrap();
+    maci_contract
+        .amaci_stop_tallying(
+            &mut app,
+            operator(),
+            vec![Uint256::zero(); 5],
+            Uint256::zero(),
+        )
+        .unwrap();
+
+    let performance = maci_contract
+        .amaci_query_operator_performance(&app)
+        .unwrap();
+    assert_eq!(performance.delay_tally_count, Uint256::from_u128(1u128));
+    assert_eq!(performance.miss_rate, Uint256::from_u128(50u128));
+
+    // Only admin/operator may slash
+    let err = contract
+        .slash_operator(&mut app, user2(), round.addr.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    contract
+        .slash_operator(&mut app, admin(), round.addr.clone())
+        .unwrap();
+    assert_eq!(
+        contract
+            .balance_of(&app, admin().to_string(), DORA_DEMON.to_string())
+            .unwrap()
+            .amount,
+        Uint128::from(slash_amount)
+    );
+    assert_eq!(
+        contract
+            .balance_of(&app, contract.addr().to_string(), DORA_DEMON.to_string())
+            .unwrap()
+            .amount,
+        Uint128::from(bond_amount - slash_amount)
+    );
+
+    // A round can only be slashed once
+    let err = contract
+        .slash_operator(&mut app, admin(), round.addr.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::AlreadySlashed {
+            round: round.addr.to_string()
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn slash_operator_unknown_round_should_fail() {
+    use crate::error::ContractError;
+    let (mut app, contract) = setup_registry_with_operator();
+
+    let err = contract
+        .slash_operator(&mut app, admin(), user3())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UnknownRound {
+            round: user3().to_string()
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn get_maci_operator_pubkey_before_and_after_set() {
+    let (mut app, contract) = setup_registry_with_operator();
+
+    // Not set yet: None rather than an error or a zero key
+    assert_eq!(
+        None,
+        contract.get_operator_pubkey(&app, operator()).unwrap()
+    );
+
+    contract
+        .set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1())
+        .unwrap();
+    assert_eq!(
+        Some(operator_pubkey1()),
+        contract.get_operator_pubkey(&app, operator()).unwrap()
+    );
+
+    // Unknown addresses also report None
+    assert_eq!(
+        None,
+        contract.get_operator_pubkey(&app, operator2()).unwrap()
+    );
+}
+
+#[test]
+fn set_maci_operator_pubkey_off_curve_should_fail() {
+    use crate::error::ContractError;
+    let (mut app, contract) = setup_registry_with_operator();
+
+    let off_curve = PubKey {
+        x: Uint256::from_u128(1u128),
+        y: Uint256::from_u128(2u128),
+    };
+    let err = contract
+        .set_maci_operator_pubkey(&mut app, operator(), off_curve)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidOperatorPubkey {},
+        err.downcast().unwrap()
+    );
+    assert_eq!(
+        None,
+        contract.get_operator_pubkey(&app, operator()).unwrap()
+    );
+}
+
+fn setup_registry_with_operator_pubkey() -> (App, super::AmaciRegistryContract) {
+    let (mut app, contract) = setup_registry_with_operator();
+    _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+
+    (app, contract)
+}
+
+#[test]
+fn create_round_insufficient_deposit_should_fail() {
+    use crate::error::ContractError;
+    let (mut app, contract) = setup_registry_with_operator_pubkey();
+
+    let min_deposit = 30_000_000_000_000_000_000u128; // 30 DORA
+    let deposit = 10_000_000_000_000_000_000u128; // 10 DORA
+
+    let err = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(1u128),
+            Uint256::from_u128(0u128),
+            &coins(deposit, DORA_DEMON),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientDeposit {
+            required: Uint128::from(min_deposit),
+            provided: Uint128::from(deposit),
+        },
+        err.downcast().unwrap()
+    );
+
+    // Sending no funds reports a zero deposit
+    let err = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(1u128),
+            Uint256::from_u128(0u128),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientDeposit {
+            required: Uint128::from(min_deposit),
+            provided: Uint128::zero(),
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn create_round_sufficient_deposit_should_work() {
+    let (mut app, contract) = setup_registry_with_operator_pubkey();
+
+    let deposit = 30_000_000_000_000_000_000u128; // 30 DORA
+    let resp = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(1u128),
+            Uint256::from_u128(0u128),
+            &coins(deposit, DORA_DEMON),
+        )
+        .unwrap();
+
+    let round: InstantiationData = from_json(&resp.data.unwrap()).unwrap();
+    assert_eq!(
+        contract
+            .balance_of(&app, round.addr.to_string(), DORA_DEMON.to_string())
+            .unwrap()
+            .amount,
+        Uint128::from(deposit)
+    );
+}
+
+#[test]
+fn update_config_changes_min_deposit() {
+    use crate::error::ContractError;
+    use crate::state::{default_config, Config};
+    let (mut app, contract) = setup_registry_with_operator_pubkey();
+
+    let min_deposit = 40_000_000_000_000_000_000u128; // 40 DORA
+    let config = Config {
+        min_deposit_amount: Uint128::from(min_deposit),
+        ..default_config()
+    };
+
+    // Only admin may change the bond / slash config
+    let err = contract
+        .update_config(&mut app, user2(), config.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    contract.update_config(&mut app, admin(), config.clone()).unwrap();
+    assert_eq!(config, contract.get_config(&app).unwrap());
+
+    let deposit = 30_000_000_000_000_000_000u128; // 30 DORA
+    let err = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(1u128),
+            Uint256::from_u128(0u128),
+            &coins(deposit, DORA_DEMON),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientDeposit {
+            required: Uint128::from(min_deposit),
+            provided: Uint128::from(deposit),
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn update_config_rejects_invalid_config() {
+    use crate::error::ContractError;
+    use crate::state::{default_config, Config};
+    let (mut app, contract) = setup_registry_with_operator();
+
+    let invalid = [
+        Config {
+            denom: "".to_string(),
+            ..default_config()
+        },
+        Config {
+            min_deposit_amount: Uint128::zero(),
+            ..default_config()
+        },
+        Config {
+            slash_amount: Uint128::zero(),
+            ..default_config()
+        },
+        Config {
+            slash_amount: default_config().min_deposit_amount + Uint128::one(),
+            ..default_config()
+        },
+    ];
+    for config in invalid {
+        let err = contract
+            .update_config(&mut app, admin(), config)
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            ContractError::InvalidConfig { .. }
+        ));
+    }
+    assert_eq!(default_config(), contract.get_config(&app).unwrap());
+}
+
+#[test]
+fn update_config_keeps_denom_while_bonded() {
+    use crate::error::ContractError;
+    use crate::state::{default_config, Config};
+    use cw_multi_test::{BankSudo, SudoMsg};
+    let (mut app, contract) = setup_registry_with_operator();
+
+    let config = Config {
+        denom: "uatom".to_string(),
+        ..default_config()
+    };
+
+    // No bonds yet: the denom can still change
+    contract
+        .update_config(&mut app, admin(), config.clone())
+        .unwrap();
+    contract
+        .update_config(&mut app, admin(), default_config())
+        .unwrap();
+
+    let bond_amount = 10_000_000_000_000_000_000u128; // 10 DORA
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: operator().to_string(),
+        amount: coins(bond_amount, DORA_DEMON),
+    }))
+    .unwrap();
+    contract
+        .bond(&mut app, operator(), &coins(bond_amount, DORA_DEMON))
+        .unwrap();
+
+    let err = contract
+        .update_config(&mut app, admin(), config)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DenomChangeWithBonds {},
+        err.downcast().unwrap()
+    );
+    assert_eq!(default_config(), contract.get_config(&app).unwrap());
+
+    // Other fields may still change while bonded
+    let config = Config {
+        slash_amount: Uint128::from(5_000_000_000_000_000_000u128), // 5 DORA
+        ..default_config()
+    };
+    contract
+        .update_config(&mut app, admin(), config.clone())
+        .unwrap();
+    assert_eq!(config, contract.get_config(&app).unwrap());
+}
+
 /// Test: publish_message_batch accumulates fees correctly across multiple batches.
 #[test]
 fn test_publish_message_batch_fee_accumulation() {
@@ -11,6 +11,16 @@ pub struct Config {
     pub slash_amount: Uint128,
 }
 
+/// Operator bond / slash config a new or migrated registry starts with.
+/// The admin can change it with UpdateConfig.
+pub fn default_config() -> Config {
+    Config {
+        denom: "peaka".to_string(),
+        min_deposit_amount: Uint128::new(30_000_000_000_000_000_000), // 30 DORA
+        slash_amount: Uint128::new(10_000_000_000_000_000_000),       // 10 DORA
+    }
+}
+
 #[cw_serde]
 pub struct Admin {
     pub admin: Addr,
@@ -40,7 +50,7 @@ impl ValidatorSet {
 
 pub const ADMIN: Item<Admin> = Item::new("admin");
 pub const OPERATOR: Item<Addr> = Item::new("operator");
-// pub const CONFIG: Item<Config> = Item::new("config");
+pub const CONFIG: Item<Config> = Item::new("config");
 
 // AMACI code ID (unified MACI contract)
 pub const AMACI_CODE_ID: Item<u64> = Item::new("amaci_code_id");
@@ -54,6 +64,9 @@ pub const COORDINATOR_PUBKEY_MAP: Map<&(Vec<u8>, Vec<u8>), u64> =
     Map::new("coordinator_pubkey_map");
 pub const MACI_OPERATOR_IDENTITY: Map<&Addr, String> = Map::new("maci_operator_identity");
 
+// round address -> amount slashed from its operator's bond
+pub const SLASHED_ROUNDS: Map<&Addr, Uint128> = Map::new("slashed_rounds");
+
 /// ORIGINAL deployed state — DO NOT rename the storage key.
 /// Managed by ChangeChargeConfig (operator permission).
 #[cw_serde]
@@ -1,19 +1,21 @@
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, HashOperation, InstantiateMsg, InstantiationData, QueryMsg};
+use crate::msg::{
+    ExecuteMsg, HashOperation, InstantiateMsg, InstantiationData, MigrateMsg, QueryMsg,
+};
 use crate::state::{
-    MessageData, PubKey, QuinaryTreeRoot, StateLeaf, ACTIVE_BATCH_ID, BATCH_HASH_COUNT,
-    BATCH_HASH_RESULTS, LEAF_IDX_0, MACIPARAMETERS, MAX_LEAVES_COUNT, MSG_CHAIN_LENGTH, MSG_HASHES,
-    NODES, NODES_NO_HASH, NUMSIGNUPS, NUMSIGNUPS_NO_HASH, QTR_LIB, SIGNUPED, SIGNUPED_NO_HASH,
-    VOICE_CREDIT_AMOUNT, ZEROS, ZEROS_H10, ZEROS_H10_NO_HASH,
+    Admin, MessageData, PubKey, QuinaryTreeRoot, StateLeaf, ACTIVE_BATCH_ID, ADMIN,
+    BATCH_HASH_COUNT, BATCH_HASH_RESULTS, LEAF_IDX_0, MACIPARAMETERS, MAX_LEAVES_COUNT,
+    MSG_CHAIN_LENGTH, MSG_HASHES, NODES, NODES_NO_HASH, NUMSIGNUPS, NUMSIGNUPS_NO_HASH, QTR_LIB,
+    SIGNUPED, SIGNUPED_NO_HASH, VOICE_CREDIT_AMOUNT, ZEROS, ZEROS_H10, ZEROS_H10_NO_HASH,
 };
 use cosmwasm_std::entry_point;
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, SubMsg,
-    Uint256, WasmMsg,
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdResult,
+    SubMsg, Uint256, WasmMsg,
 };
-use maci_utils::{hash2, hash5, uint256_from_hex_string};
+use maci_utils::{hash, hash2, hash5, uint256_from_hex_string, uint256_to_fr};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw-amaci";
@@ -31,6 +33,14 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // The instantiator may reset the tree between benchmark runs
+    ADMIN.save(
+        deps.storage,
+        &Admin {
+            admin: info.sender.clone(),
+        },
+    )?;
+
     // Save the MACI parameters to storage
     MACIPARAMETERS.save(deps.storage, &msg.parameters)?;
     // Initialize QTR_LIB for merkle tree operations
@@ -183,6 +193,9 @@ pub fn execute(
         ExecuteMsg::TestSignupNoHash { pubkey } => {
             execute_test_signup_no_hash(deps, env, info, pubkey)
         }
+        ExecuteMsg::TestSignupBatchNoHash { pubkeys } => {
+            execute_test_signup_batch_no_hash(deps, env, info, pubkeys)
+        }
         ExecuteMsg::TestSignupWithHash { pubkey } => {
             execute_test_signup_with_hash(deps, env, info, pubkey)
         }
@@ -190,9 +203,14 @@ pub fn execute(
             message,
             enc_pub_key,
         } => execute_test_publish_message(deps, env, info, message, enc_pub_key),
+        ExecuteMsg::TestMessageHashCost {
+            message,
+            enc_pub_key,
+        } => execute_test_message_hash_cost(deps, env, info, message, enc_pub_key),
         ExecuteMsg::TestHash2 { data } => execute_test_hash2(deps, env, info, data),
         ExecuteMsg::TestHash5 { data } => execute_test_hash5(deps, env, info, data),
         ExecuteMsg::TestHashUint256 { data } => execute_test_hash_uint256(deps, env, info, data),
+        ExecuteMsg::TestHashN { data } => execute_test_hash_n(deps, env, info, data),
         ExecuteMsg::TestHashOnce { data } => execute_test_hash_once(deps, env, info, data),
         ExecuteMsg::TestHashMultiple { data, count } => {
             execute_test_hash_multiple(deps, env, info, data, count)
@@ -204,6 +222,7 @@ pub fn execute(
         ExecuteMsg::TestBatchHash { operations } => {
             execute_test_batch_hash(deps, env, info, operations)
         }
+        ExecuteMsg::ResetTree {} => execute_reset_tree(deps, env, info),
     }
 }
 
@@ -521,6 +540,52 @@ pub fn execute_test_signup_no_hash(
         .add_attribute("pubkey_y", pubkey.y.to_string()))
 }
 
+/// Test function for bulk signup without hash calculation
+/// Measures gas cost of inserting many leaves in one transaction
+/// Shares NUMSIGNUPS_NO_HASH with TestSignupNoHash, so GetNumSignUpNoHash counts both
+pub fn execute_test_signup_batch_no_hash(
+    mut deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    pubkeys: Vec<PubKey>,
+) -> Result<Response, ContractError> {
+    let mut num_sign_ups = NUMSIGNUPS_NO_HASH.load(deps.storage)?;
+    let voice_credit_amount = VOICE_CREDIT_AMOUNT.load(deps.storage)?;
+    let max_leaves_count = MAX_LEAVES_COUNT.load(deps.storage)?;
+
+    let first_state_index = num_sign_ups;
+    let total = num_sign_ups + Uint256::from_u128(pubkeys.len() as u128);
+    if total > max_leaves_count {
+        return Err(ContractError::MaxVoterExceeded {
+            current: total,
+            max_allowed: max_leaves_count,
+        });
+    }
+
+    for pubkey in pubkeys.iter() {
+        state_enqueue_no_hash(&mut deps, voice_credit_amount)?;
+
+        SIGNUPED_NO_HASH.save(
+            deps.storage,
+            &(
+                pubkey.x.to_be_bytes().to_vec(),
+                pubkey.y.to_be_bytes().to_vec(),
+            ),
+            &num_sign_ups,
+        )?;
+
+        // state_enqueue_no_hash places the next leaf from this counter
+        num_sign_ups += Uint256::from_u128(1u128);
+        NUMSIGNUPS_NO_HASH.save(deps.storage, &num_sign_ups)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "test_signup_batch_no_hash")
+        .add_attribute("first_state_idx", first_state_index.to_string())
+        .add_attribute("count", pubkeys.len().to_string())
+        .add_attribute("num_sign_ups", num_sign_ups.to_string()))
+}
+
 /// Test function for signup with full hash calculation
 /// Measures gas cost of complete merkle tree update with Poseidon hash
 pub fn execute_test_signup_with_hash(
@@ -613,6 +678,29 @@ pub fn execute_test_publish_message(
         .add_attribute("enc_pub_key_y", enc_pub_key.y.to_string()))
 }
 
+/// Test function for the message hash of a publish
+/// Measures gas cost of hash_message_and_enc_pub_key alone, without the
+/// storage reads and writes of TestPublishMessage
+pub fn execute_test_message_hash_cost(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    message: MessageData,
+    enc_pub_key: PubKey,
+) -> Result<Response, ContractError> {
+    let message_hash = hash_message_and_enc_pub_key(message, enc_pub_key.clone(), Uint256::zero());
+
+    // Two hash5 over the message halves plus the final hash5 chaining them
+    // with the key and previous hash
+    Ok(Response::new()
+        .add_attribute("action", "test_message_hash_cost")
+        .add_attribute("permutations", "3")
+        .add_attribute("input_count", "15")
+        .add_attribute("message_hash", message_hash.to_string())
+        .add_attribute("enc_pub_key_x", enc_pub_key.x.to_string())
+        .add_attribute("enc_pub_key_y", enc_pub_key.y.to_string()))
+}
+
 /// Test function for hash2
 /// Measures gas cost of hash2 function from maci-utils
 pub fn execute_test_hash2(
@@ -658,6 +746,33 @@ pub fn execute_test_hash_uint256(
         .add_attribute("result", result.to_string()))
 }
 
+/// Test function for Poseidon with a runtime arity
+/// Uses the same hash2/hash5 paths as the fixed-arity handlers where they exist
+pub fn execute_test_hash_n(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    data: Vec<Uint256>,
+) -> Result<Response, ContractError> {
+    let result = match data.as_slice() {
+        [a, b] => hash2([*a, *b]),
+        [a, b, c, d, e] => hash5([*a, *b, *c, *d, *e]),
+        inputs if (1..=4).contains(&inputs.len()) => {
+            hash(inputs.iter().map(uint256_to_fr).collect())
+        }
+        inputs => {
+            return Err(ContractError::UnsupportedHashArity {
+                arity: inputs.len(),
+            })
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "test_hash_n")
+        .add_attribute("arity", data.len().to_string())
+        .add_attribute("result", result.to_string()))
+}
+
 /// Test function for single hash5 call
 /// Measures gas cost of one hash5 call
 pub fn execute_test_hash_once(
@@ -689,9 +804,13 @@ pub fn execute_test_hash_multiple(
         last_result = hash5(data);
     }
 
+    // Each hash5 is a single Poseidon permutation over 5 inputs; dividing the
+    // tx gas by `permutations` gives the per-hash cost
     Ok(Response::new()
         .add_attribute("action", "test_hash_multiple")
         .add_attribute("count", count.to_string())
+        .add_attribute("permutations", count.to_string())
+        .add_attribute("input_count", (u64::from(count) * 5).to_string())
         .add_attribute("last_result", last_result.to_string()))
 }
 
@@ -713,6 +832,8 @@ pub fn execute_test_hash_batch(
     Ok(Response::new()
         .add_attribute("action", "test_hash_batch")
         .add_attribute("hash_count", data.len().to_string())
+        .add_attribute("permutations", data.len().to_string())
+        .add_attribute("input_count", (data.len() * 5).to_string())
         .add_attribute("last_result", last_result.to_string()))
 }
 
@@ -809,6 +930,88 @@ pub fn execute_test_batch_hash(
     Ok(response)
 }
 
+// ============================================================================
+// Benchmark Maintenance
+// ============================================================================
+
+/// Clears both state trees, their signup records and the message chain so
+/// one instance can be reused across benchmark runs. Parameters, zeros and
+/// the voice credit amount are kept.
+///
+/// Instances created before ADMIN was stored at instantiate fall back to the
+/// wasm contract admin, so they need no migration to use this.
+pub fn execute_reset_tree(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let authorized = match ADMIN.may_load(deps.storage)? {
+        Some(admin) => admin.is_admin(&info.sender),
+        None => deps
+            .querier
+            .query_wasm_contract_info(&env.contract.address)?
+            .admin
+            .is_some_and(|admin| admin == info.sender.as_str()),
+    };
+    if !authorized {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let nodes: Vec<Vec<u8>> = NODES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for key in nodes {
+        NODES.remove(deps.storage, key);
+    }
+    let nodes_no_hash: Vec<Vec<u8>> = NODES_NO_HASH
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for key in nodes_no_hash {
+        NODES_NO_HASH.remove(deps.storage, key);
+    }
+    let signuped: Vec<(Vec<u8>, Vec<u8>)> = SIGNUPED
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for key in signuped {
+        SIGNUPED.remove(deps.storage, &key);
+    }
+    let signuped_no_hash: Vec<(Vec<u8>, Vec<u8>)> = SIGNUPED_NO_HASH
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for key in signuped_no_hash {
+        SIGNUPED_NO_HASH.remove(deps.storage, &key);
+    }
+    let msg_hashes: Vec<Vec<u8>> = MSG_HASHES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for key in msg_hashes {
+        MSG_HASHES.remove(deps.storage, key);
+    }
+
+    // Restore the state right after instantiate
+    let parameters = MACIPARAMETERS.load(deps.storage)?;
+    let zeros_h10 = ZEROS_H10.load(deps.storage)?;
+    NODES.save(
+        deps.storage,
+        Uint256::zero().to_be_bytes().to_vec(),
+        &zeros_h10[parameters
+            .state_tree_depth
+            .to_string()
+            .parse::<usize>()
+            .unwrap()],
+    )?;
+    MSG_HASHES.save(
+        deps.storage,
+        Uint256::zero().to_be_bytes().to_vec(),
+        &Uint256::zero(),
+    )?;
+    NUMSIGNUPS.save(deps.storage, &Uint256::zero())?;
+    NUMSIGNUPS_NO_HASH.save(deps.storage, &Uint256::zero())?;
+    MSG_CHAIN_LENGTH.save(deps.storage, &Uint256::zero())?;
+
+    Ok(Response::new().add_attribute("action", "reset_tree"))
+}
+
 // ============================================================================
 // Reply Handler
 // ============================================================================
@@ -923,10 +1126,108 @@ fn handle_batch_hash_reply(deps: DepsMut, msg: Reply) -> Result<Response, Contra
     }
 }
 
+// ============================================================================
+// Migration
+// ============================================================================
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigrationContract {
+            expected: CONTRACT_NAME.to_string(),
+            actual: stored.contract,
+        });
+    }
+
+    let stored_version = parse_version(&stored.version)?;
+    if stored_version > parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::MigrationDowngrade {
+            stored: stored.version,
+            current: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // The tree setup indexes ZEROS_H10 by state_tree_depth, so a layout change
+    // must keep the stored parameters readable and within that table
+    let parameters = MACIPARAMETERS.load(deps.storage).map_err(|err| {
+        ContractError::IncompatibleMaciParameters {
+            reason: err.to_string(),
+        }
+    })?;
+    let zeros_h10 = ZEROS_H10.load(deps.storage)?;
+    if parameters.state_tree_depth >= Uint256::from_u128(zeros_h10.len() as u128) {
+        return Err(ContractError::IncompatibleMaciParameters {
+            reason: format!(
+                "state_tree_depth {} exceeds the supported maximum {}",
+                parameters.state_tree_depth,
+                zeros_h10.len() - 1
+            ),
+        });
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+// Parses a `major.minor.patch` version, ignoring any pre-release suffix
+fn parse_version(version: &str) -> Result<(u64, u64, u64), ContractError> {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let mut parts = core.split('.').map(|part| part.parse::<u64>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Ok((major, minor, patch)),
+        _ => Err(ContractError::InvalidContractVersion {
+            version: version.to_string(),
+        }),
+    }
+}
+
 // ============================================================================
 // Query Functions
 // ============================================================================
 
+// Recomputes the state root from a leaf and its quinary siblings, hashing each
+// level with hash5 exactly as state_update_at does
+fn verify_inclusion(
+    deps: Deps,
+    leaf: Uint256,
+    leaf_index: Uint256,
+    proof: Vec<[Uint256; 4]>,
+) -> StdResult<bool> {
+    let parameters = MACIPARAMETERS.load(deps.storage)?;
+    let max_leaves_count = MAX_LEAVES_COUNT.load(deps.storage)?;
+    if Uint256::from_u128(proof.len() as u128) != parameters.state_tree_depth
+        || leaf_index >= max_leaves_count
+    {
+        return Ok(false);
+    }
+
+    let five = Uint256::from_u128(5u128);
+    let mut idx = leaf_index;
+    let mut current = leaf;
+    for siblings in proof {
+        let position: usize = (idx % five).to_string().parse().unwrap();
+        let mut inputs = [Uint256::zero(); 5];
+        let mut siblings = siblings.into_iter();
+        for (i, input) in inputs.iter_mut().enumerate() {
+            *input = if i == position {
+                current
+            } else {
+                siblings.next().unwrap()
+            };
+        }
+        current = hash5(inputs);
+        idx /= five;
+    }
+
+    let root = NODES.may_load(deps.storage, Uint256::zero().to_be_bytes().to_vec())?;
+    Ok(root == Some(current))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -961,6 +1262,11 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let root = NODES.may_load(deps.storage, Uint256::zero().to_be_bytes().to_vec())?;
             to_json_binary(&root.unwrap_or(Uint256::zero()))
         }
+        QueryMsg::VerifyInclusion {
+            leaf,
+            leaf_index,
+            proof,
+        } => to_json_binary(&verify_inclusion(deps, leaf, leaf_index, proof)?),
         QueryMsg::Signuped { pubkey } => {
             let state_idx = SIGNUPED.may_load(
                 deps.storage,
@@ -162,4 +162,19 @@ pub enum ContractError {
 
     #[error("Voting power is zero")]
     VotingPowerIsZero {},
+
+    #[error("Cannot migrate from contract {actual}, expected {expected}")]
+    InvalidMigrationContract { expected: String, actual: String },
+
+    #[error("Cannot migrate from version {stored} to older version {current}")]
+    MigrationDowngrade { stored: String, current: String },
+
+    #[error("Invalid contract version {version}")]
+    InvalidContractVersion { version: String },
+
+    #[error("Stored MACI parameters are incompatible: {reason}")]
+    IncompatibleMaciParameters { reason: String },
+
+    #[error("Unsupported hash arity {arity}, expected between 1 and 5 inputs")]
+    UnsupportedHashArity { arity: usize },
 }
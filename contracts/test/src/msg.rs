@@ -40,6 +40,9 @@ pub enum ExecuteMsg {
     TestSignupNoHash {
         pubkey: PubKey,
     },
+    TestSignupBatchNoHash {
+        pubkeys: Vec<PubKey>,
+    },
     TestSignupWithHash {
         pubkey: PubKey,
     },
@@ -47,6 +50,10 @@ pub enum ExecuteMsg {
         message: MessageData,
         enc_pub_key: PubKey,
     },
+    TestMessageHashCost {
+        message: MessageData,
+        enc_pub_key: PubKey,
+    },
     // Hash function tests
     TestHash2 {
         data: [Uint256; 2],
@@ -57,6 +64,10 @@ pub enum ExecuteMsg {
     TestHashUint256 {
         data: Uint256,
     },
+    /// Poseidon hash with the arity taken from `data.len()` (1 to 5 inputs)
+    TestHashN {
+        data: Vec<Uint256>,
+    },
     // Multiple hash tests
     TestHashOnce {
         data: [Uint256; 5],
@@ -76,6 +87,8 @@ pub enum ExecuteMsg {
     TestBatchHash {
         operations: Vec<HashOperation>,
     },
+    // Benchmark maintenance (admin only)
+    ResetTree {},
 }
 
 #[cw_serde]
@@ -142,6 +155,16 @@ pub enum QueryMsg {
     #[returns(Uint256)]
     GetNode { index: Uint256 },
 
+    /// Checks `leaf` at state index `leaf_index` against the state tree root.
+    /// `proof` lists the 4 siblings at each level from the leaves up, in
+    /// child order with the path node left out.
+    #[returns(bool)]
+    VerifyInclusion {
+        leaf: Uint256,
+        leaf_index: Uint256,
+        proof: Vec<[Uint256; 4]>,
+    },
+
     #[returns(Uint256)]
     GetResult { index: Uint256 },
 
@@ -238,6 +261,9 @@ pub struct TallyDelayInfo {
     pub calculated_hours: u64,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub struct InstantiationData {
     pub caller: Addr,
@@ -6,7 +6,7 @@ use anyhow::Result as AnyResult;
 use crate::msg::HashOperation;
 use crate::state::{MaciParameters, MessageData, PubKey};
 use crate::{
-    contract::{execute, instantiate, query, reply},
+    contract::{execute, instantiate, migrate, query, reply},
     msg::*,
 };
 use maci_utils::uint256_from_hex_string;
@@ -64,7 +64,9 @@ impl MaciCodeId {
     }
 
     pub fn store_code(app: &mut App) -> Self {
-        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+        let contract = ContractWrapper::new(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_reply(reply);
         let code_id = app.store_code(Box::new(contract));
         Self(code_id)
     }
@@ -197,11 +199,11 @@ impl TestContract {
 
         app.instantiate_contract(
             code_id.0,
-            Addr::unchecked(sender),
+            Addr::unchecked(sender.clone()),
             &init_msg,
             &[],
             label,
-            None,
+            Some(sender.to_string()),
         )
         .map(Self::from)
     }
@@ -267,6 +269,21 @@ impl TestContract {
         )
     }
 
+    #[track_caller]
+    pub fn test_signup_batch_no_hash(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        pubkeys: Vec<PubKey>,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::TestSignupBatchNoHash { pubkeys },
+            &[],
+        )
+    }
+
     #[track_caller]
     pub fn test_signup_with_hash(
         &self,
@@ -301,6 +318,25 @@ impl TestContract {
         )
     }
 
+    #[track_caller]
+    pub fn test_message_hash_cost(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        message: MessageData,
+        enc_pub_key: PubKey,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::TestMessageHashCost {
+                message,
+                enc_pub_key,
+            },
+            &[],
+        )
+    }
+
     #[track_caller]
     pub fn test_hash2(
         &self,
@@ -321,6 +357,16 @@ impl TestContract {
         app.execute_contract(sender, self.addr(), &ExecuteMsg::TestHash5 { data }, &[])
     }
 
+    #[track_caller]
+    pub fn test_hash_n(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        data: Vec<Uint256>,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(sender, self.addr(), &ExecuteMsg::TestHashN { data }, &[])
+    }
+
     #[track_caller]
     pub fn test_hash_uint256(
         &self,
@@ -408,6 +454,21 @@ impl TestContract {
         )
     }
 
+    #[track_caller]
+    pub fn reset_tree(&self, app: &mut App, sender: Addr) -> AnyResult<AppResponse> {
+        app.execute_contract(sender, self.addr(), &ExecuteMsg::ResetTree {}, &[])
+    }
+
+    #[track_caller]
+    pub fn migrate(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        code_id: MaciCodeId,
+    ) -> AnyResult<AppResponse> {
+        app.migrate_contract(sender, self.addr(), &MigrateMsg {}, code_id.id())
+    }
+
     // Query methods
     pub fn get_num_sign_up(&self, app: &App) -> StdResult<Uint256> {
         app.wrap()
@@ -424,6 +485,23 @@ impl TestContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetStateTreeRoot {})
     }
 
+    pub fn verify_inclusion(
+        &self,
+        app: &App,
+        leaf: Uint256,
+        leaf_index: Uint256,
+        proof: Vec<[Uint256; 4]>,
+    ) -> StdResult<bool> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::VerifyInclusion {
+                leaf,
+                leaf_index,
+                proof,
+            },
+        )
+    }
+
     pub fn signuped(&self, app: &App, pubkey: PubKey) -> StdResult<Option<Uint256>> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::Signuped { pubkey })
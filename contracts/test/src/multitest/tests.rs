@@ -4,7 +4,8 @@ mod test {
     use crate::multitest::{
         create_app, owner, test_pubkey1, test_pubkey2, user1, user2, MaciCodeId, TestContract,
     };
-    use crate::state::{MaciParameters, MessageData, PubKey};
+    use crate::state::{MaciParameters, MessageData, PubKey, StateLeaf, ADMIN, MACIPARAMETERS};
+    use crate::ContractError;
     use cosmwasm_std::Uint256;
 
     #[test]
@@ -56,6 +57,86 @@ mod test {
         assert_ne!(state_root, Uint256::zero());
     }
 
+    #[test]
+    fn test_verify_inclusion() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+
+        let pubkeys = [test_pubkey1(), test_pubkey2()];
+        for pubkey in pubkeys.iter() {
+            contract.sign_up(&mut app, user1(), pubkey.clone()).unwrap();
+        }
+
+        // Rebuild the depth-2 state tree off-chain: 25 leaves padded with the
+        // blank leaf hash10([0; 10]), each level hashed in groups of five
+        let mut leaves = vec![maci_utils::hash10([Uint256::zero(); 10]); 25];
+        for (i, pubkey) in pubkeys.iter().enumerate() {
+            leaves[i] = StateLeaf {
+                pub_key: pubkey.clone(),
+                voice_credit_balance: Uint256::from_u128(100u128),
+                vote_option_tree_root: Uint256::zero(),
+                nonce: Uint256::zero(),
+            }
+            .hash_decativate_state_leaf();
+        }
+        let hash_level = |nodes: &[Uint256]| -> Vec<Uint256> {
+            nodes
+                .chunks(5)
+                .map(|c| maci_utils::hash5([c[0], c[1], c[2], c[3], c[4]]))
+                .collect()
+        };
+        let level1 = hash_level(&leaves);
+        let root = hash_level(&level1)[0];
+        assert_eq!(contract.get_state_tree_root(&app).unwrap(), root);
+
+        let siblings = |nodes: &[Uint256], index: usize| -> [Uint256; 4] {
+            let start = index / 5 * 5;
+            let group: Vec<Uint256> = (start..start + 5)
+                .filter(|i| *i != index)
+                .map(|i| nodes[i])
+                .collect();
+            [group[0], group[1], group[2], group[3]]
+        };
+        let leaf_index = 1usize;
+        let proof = vec![
+            siblings(&leaves, leaf_index),
+            siblings(&level1, leaf_index / 5),
+        ];
+
+        assert!(contract
+            .verify_inclusion(
+                &app,
+                leaves[leaf_index],
+                Uint256::from_u128(leaf_index as u128),
+                proof.clone()
+            )
+            .unwrap());
+
+        // Wrong leaf, wrong index or a truncated proof must all fail
+        assert!(!contract
+            .verify_inclusion(
+                &app,
+                leaves[0],
+                Uint256::from_u128(leaf_index as u128),
+                proof.clone()
+            )
+            .unwrap());
+        assert!(!contract
+            .verify_inclusion(&app, leaves[leaf_index], Uint256::zero(), proof.clone())
+            .unwrap());
+        assert!(!contract
+            .verify_inclusion(
+                &app,
+                leaves[leaf_index],
+                Uint256::from_u128(leaf_index as u128),
+                proof[..1].to_vec()
+            )
+            .unwrap());
+    }
+
     #[test]
     fn test_multiple_signups() {
         let mut app = create_app();
@@ -129,6 +210,51 @@ mod test {
         assert_eq!(msg_chain_length, Uint256::from_u128(1u128));
     }
 
+    #[test]
+    fn test_signup_batch_no_hash() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+
+        let pubkeys: Vec<PubKey> = (0..10u128)
+            .map(|i| PubKey {
+                x: Uint256::from_u128(1000 + i),
+                y: Uint256::from_u128(2000 + i),
+            })
+            .collect();
+
+        contract
+            .test_signup_batch_no_hash(&mut app, user1(), pubkeys.clone())
+            .unwrap();
+
+        assert_eq!(
+            contract.get_num_sign_up_no_hash(&app).unwrap(),
+            Uint256::from_u128(10u128)
+        );
+        for (i, pubkey) in pubkeys.into_iter().enumerate() {
+            assert_eq!(
+                contract.signuped_no_hash(&app, pubkey).unwrap(),
+                Some(Uint256::from_u128(i as u128))
+            );
+        }
+        // Parents are plain sums, so the root is 10 leaves of 100 credits
+        assert_eq!(
+            contract.get_state_tree_root_no_hash(&app).unwrap(),
+            Uint256::from_u128(1000u128)
+        );
+
+        // A single no-hash signup continues from the batch counter
+        contract
+            .test_signup_no_hash(&mut app, user1(), test_pubkey1())
+            .unwrap();
+        assert_eq!(
+            contract.get_num_sign_up_no_hash(&app).unwrap(),
+            Uint256::from_u128(11u128)
+        );
+    }
+
     #[test]
     fn test_signup_no_hash() {
         let mut app = create_app();
@@ -398,6 +524,110 @@ mod test {
         }));
     }
 
+    #[test]
+    fn test_hash_n() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+
+        let result = |response: &cw_multi_test::AppResponse| -> String {
+            response
+                .events
+                .iter()
+                .flat_map(|e| &e.attributes)
+                .find(|attr| attr.key == "result")
+                .map(|attr| attr.value.clone())
+                .unwrap()
+        };
+        let data: Vec<Uint256> = (1..=5u128).map(Uint256::from_u128).collect();
+
+        // Arities with a fixed-arity handler must agree with it
+        let hash2 = contract
+            .test_hash2(&mut app, user1(), [data[0], data[1]])
+            .unwrap();
+        let hash_n = contract
+            .test_hash_n(&mut app, user1(), data[..2].to_vec())
+            .unwrap();
+        assert_eq!(result(&hash_n), result(&hash2));
+
+        let hash5 = contract
+            .test_hash5(
+                &mut app,
+                user1(),
+                [data[0], data[1], data[2], data[3], data[4]],
+            )
+            .unwrap();
+        let hash_n = contract
+            .test_hash_n(&mut app, user1(), data.clone())
+            .unwrap();
+        assert_eq!(result(&hash_n), result(&hash5));
+
+        // Arities 3 and 4 match maci-utils' generic Poseidon
+        for arity in [3usize, 4] {
+            let expected = maci_utils::hash(
+                data[..arity]
+                    .iter()
+                    .map(maci_utils::uint256_to_fr)
+                    .collect(),
+            );
+            let hash_n = contract
+                .test_hash_n(&mut app, user1(), data[..arity].to_vec())
+                .unwrap();
+            assert_eq!(result(&hash_n), expected.to_string());
+        }
+
+        for data in [vec![], vec![Uint256::one(); 6]] {
+            let arity = data.len();
+            let err = contract.test_hash_n(&mut app, user1(), data).unwrap_err();
+            assert_eq!(
+                ContractError::UnsupportedHashArity { arity },
+                err.downcast().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_benchmark_attributes() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+
+        let data = [
+            Uint256::from_u128(1u128),
+            Uint256::from_u128(2u128),
+            Uint256::from_u128(3u128),
+            Uint256::from_u128(4u128),
+            Uint256::from_u128(5u128),
+        ];
+        let attr = |response: &cw_multi_test::AppResponse, key: &str| -> String {
+            response
+                .events
+                .iter()
+                .flat_map(|e| &e.attributes)
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+                .unwrap()
+        };
+
+        for count in [0u32, 1, 7] {
+            let response = contract
+                .test_hash_multiple(&mut app, user1(), data, count)
+                .unwrap();
+            assert_eq!(attr(&response, "permutations"), count.to_string());
+            assert_eq!(attr(&response, "input_count"), (count * 5).to_string());
+        }
+
+        let response = contract
+            .test_hash_batch(&mut app, user1(), vec![data; 3])
+            .unwrap();
+        assert_eq!(attr(&response, "permutations"), "3");
+        assert_eq!(attr(&response, "input_count"), "15");
+    }
+
     #[test]
     fn test_hash_composed() {
         let mut app = create_app();
@@ -1185,4 +1415,223 @@ mod test {
         println!("  If depth 2's first or max is much larger, it indicates");
         println!("  initialization overhead or lazy loading in Poseidon hash.\n");
     }
+
+    #[test]
+    fn test_migrate() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+        let version = env!("CARGO_PKG_VERSION");
+
+        // Migrating from an older release bumps the stored cw2 version
+        cw2::set_contract_version(
+            app.contract_storage_mut(&contract.addr()).as_mut(),
+            "crates.io:cw-amaci",
+            "0.0.1",
+        )
+        .unwrap();
+        let response = contract.migrate(&mut app, owner(), code_id).unwrap();
+        assert!(response.events.iter().any(|event| event
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "from_version" && attr.value == "0.0.1")));
+        let stored =
+            cw2::get_contract_version(app.contract_storage(&contract.addr()).as_ref()).unwrap();
+        assert_eq!(stored.version, version);
+
+        // State survives the migration
+        assert_eq!(
+            contract.get_voice_credit_amount(&app).unwrap(),
+            Uint256::from_u128(100u128)
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_incompatible_versions() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+
+        cw2::set_contract_version(
+            app.contract_storage_mut(&contract.addr()).as_mut(),
+            "crates.io:cw-amaci",
+            "999.0.0",
+        )
+        .unwrap();
+        let err = contract.migrate(&mut app, owner(), code_id).unwrap_err();
+        assert_eq!(
+            ContractError::MigrationDowngrade {
+                stored: "999.0.0".to_string(),
+                current: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            err.downcast().unwrap()
+        );
+
+        cw2::set_contract_version(
+            app.contract_storage_mut(&contract.addr()).as_mut(),
+            "crates.io:other-contract",
+            "0.0.1",
+        )
+        .unwrap();
+        let err = contract.migrate(&mut app, owner(), code_id).unwrap_err();
+        assert_eq!(
+            ContractError::InvalidMigrationContract {
+                expected: "crates.io:cw-amaci".to_string(),
+                actual: "crates.io:other-contract".to_string(),
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_incompatible_maci_parameters() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+
+        // A depth outside the precomputed zeros table
+        MACIPARAMETERS
+            .save(
+                app.contract_storage_mut(&contract.addr()).as_mut(),
+                &MaciParameters {
+                    state_tree_depth: Uint256::from_u128(7u128),
+                    int_state_tree_depth: Uint256::from_u128(1u128),
+                    message_batch_size: Uint256::from_u128(5u128),
+                    vote_option_tree_depth: Uint256::from_u128(1u128),
+                },
+            )
+            .unwrap();
+        let err = contract.migrate(&mut app, owner(), code_id).unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            ContractError::IncompatibleMaciParameters { .. }
+        ));
+
+        // A stored layout that no longer deserializes
+        app.contract_storage_mut(&contract.addr())
+            .set(b"maci_param", br#"{"state_tree_depth":"2"}"#);
+        let err = contract.migrate(&mut app, owner(), code_id).unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            ContractError::IncompatibleMaciParameters { .. }
+        ));
+    }
+
+    #[test]
+    fn test_reset_tree() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+        let empty_root = contract.get_state_tree_root(&app).unwrap();
+
+        contract.sign_up(&mut app, user1(), test_pubkey1()).unwrap();
+        contract
+            .test_signup_no_hash(&mut app, user1(), test_pubkey2())
+            .unwrap();
+        contract
+            .publish_message(
+                &mut app,
+                user1(),
+                MessageData {
+                    data: [Uint256::from_u128(1u128); 10],
+                },
+                test_pubkey2(),
+            )
+            .unwrap();
+        assert_ne!(contract.get_state_tree_root(&app).unwrap(), empty_root);
+
+        let err = contract.reset_tree(&mut app, user2()).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        contract.reset_tree(&mut app, owner()).unwrap();
+        assert_eq!(contract.get_num_sign_up(&app).unwrap(), Uint256::zero());
+        assert_eq!(
+            contract.get_num_sign_up_no_hash(&app).unwrap(),
+            Uint256::zero()
+        );
+        assert_eq!(
+            contract.get_msg_chain_length(&app).unwrap(),
+            Uint256::zero()
+        );
+        assert_eq!(contract.get_state_tree_root(&app).unwrap(), empty_root);
+        assert_eq!(contract.signuped(&app, test_pubkey1()).unwrap(), None);
+
+        // The instance can be reused for another run
+        contract.sign_up(&mut app, user1(), test_pubkey1()).unwrap();
+        assert_eq!(
+            contract.signuped(&app, test_pubkey1()).unwrap(),
+            Some(Uint256::zero())
+        );
+    }
+
+    #[test]
+    fn test_reset_tree_without_stored_admin() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+
+        // Instances created before ADMIN was saved at instantiate
+        ADMIN.remove(app.contract_storage_mut(&contract.addr()).as_mut());
+        contract.sign_up(&mut app, user1(), test_pubkey1()).unwrap();
+
+        let err = contract.reset_tree(&mut app, user2()).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+        // The wasm contract admin can still reset
+        contract.reset_tree(&mut app, owner()).unwrap();
+        assert_eq!(contract.get_num_sign_up(&app).unwrap(), Uint256::zero());
+    }
+
+    #[test]
+    fn test_message_hash_cost() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_default(&mut app, owner(), "test_contract")
+            .unwrap();
+
+        let data: [Uint256; 10] = core::array::from_fn(|i| Uint256::from_u128(i as u128 + 1));
+        let enc_pub_key = test_pubkey2();
+        let response = contract
+            .test_message_hash_cost(&mut app, user1(), MessageData { data }, enc_pub_key.clone())
+            .unwrap();
+        let attr = |key: &str| -> String {
+            response
+                .events
+                .iter()
+                .flat_map(|e| &e.attributes)
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+                .unwrap()
+        };
+
+        let m_hash = maci_utils::hash5([data[0], data[1], data[2], data[3], data[4]]);
+        let n_hash = maci_utils::hash5([data[5], data[6], data[7], data[8], data[9]]);
+        let expected = maci_utils::hash5([
+            m_hash,
+            n_hash,
+            enc_pub_key.x,
+            enc_pub_key.y,
+            Uint256::zero(),
+        ]);
+        assert_eq!(attr("message_hash"), expected.to_string());
+        assert_eq!(attr("permutations"), "3");
+        assert_eq!(attr("input_count"), "15");
+
+        // Nothing is written to the message chain
+        assert_eq!(
+            contract.get_msg_chain_length(&app).unwrap(),
+            Uint256::zero()
+        );
+    }
 }
@@ -77,11 +77,7 @@ pub const CURRENT_TALLY_COMMITMENT: Item<Uint256> = Item::new("current_tally_com
 pub const RESULT: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
 pub const TOTAL_RESULT: Item<Uint256> = Item::new("total_result");
 
-#[cw_serde]
-pub struct PubKey {
-    pub x: Uint256,
-    pub y: Uint256,
-}
+pub use maci_utils::PubKey;
 
 #[cw_serde]
 pub struct StateLeaf {
@@ -20,8 +20,11 @@ pub enum BabyJubjubError {
     #[error("Unpacked point is not on curve")]
     PointNotOnCurve,
 
-    #[error("Cannot compute square root: {0}")]
-    SquareRootError(String),
+    #[error("Invalid packed points: length {0} is not a multiple of 32 bytes")]
+    PackedPointsLength(usize),
+
+    #[error("Cannot compute square root: value is not a quadratic residue")]
+    NotQuadraticResidue,
 }
 
 /// Result type alias for convenience
@@ -2,13 +2,28 @@
 //!
 //! This library provides Baby Jubjub curve operations compatible with EIP-2494.
 //! Baby Jubjub is a twisted Edwards elliptic curve defined over the BN254 scalar field.
+//!
+//! The RNG helpers (`gen_random_babyjub_value`, `gen_random_fr`) sit behind the
+//! default `rand` feature. Everything else is deterministic and builds with
+//! `default-features = false`:
+//!
+//! ```
+//! use baby_jubjub::{add_point, base8, mul_point_escalar, pack_point, unpack_point, EdFr};
+//!
+//! let point = mul_point_escalar(&base8(), EdFr::from(324u64));
+//! let sum = add_point(&point, &base8());
+//! assert_eq!(unpack_point(&pack_point(&sum)).unwrap(), sum);
+//! ```
 
 mod constants;
 mod error;
+mod serde_point;
 
 pub use constants::{biguint_to_fr, fr_to_biguint, SNARK_FIELD_SIZE};
 pub use error::{BabyJubjubError, Result};
+pub use serde_point::SerdePoint;
 
+#[cfg(feature = "rand")]
 use ark_bn254::Fr;
 use ark_ec::{
     models::CurveConfig,
@@ -17,6 +32,7 @@ use ark_ec::{
 };
 use ark_ff::{BigInteger, Field, MontFp, PrimeField, Zero};
 use num_bigint::BigUint;
+#[cfg(feature = "rand")]
 use rand::Rng;
 
 // Re-export ark_ed_on_bn254 types for convenience
@@ -97,6 +113,7 @@ pub const BASE_Y: Fq =
 /// http://cvsweb.openbsd.org/cgi-bin/cvsweb/~checkout~/src/lib/libc/crypt/arc4random_uniform.c
 ///
 /// The function generates random values until it finds one that doesn't cause modulo bias
+#[cfg(feature = "rand")]
 pub fn gen_random_babyjub_value() -> BigUint {
     // Prevent modulo bias
     // const lim = 2^256
@@ -131,6 +148,7 @@ pub fn gen_random_babyjub_value() -> BigUint {
 }
 
 /// Generate a random field element using Arkworks
+#[cfg(feature = "rand")]
 pub fn gen_random_fr() -> Fr {
     let value = gen_random_babyjub_value();
     biguint_to_fr(&value)
@@ -277,9 +295,33 @@ pub fn unpack_point(packed: &BigUint) -> Result<EdwardsAffine> {
     Ok(point)
 }
 
+/// Packs several points into one buffer, 32 bytes per point in order.
+/// Each chunk uses the same layout as [`pack_point`].
+pub fn pack_points(points: &[EdwardsAffine]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(points.len() * 32);
+    for point in points {
+        let mut bytes = pack_point(point).to_bytes_le();
+        bytes.resize(32, 0);
+        buffer.extend_from_slice(&bytes);
+    }
+    buffer
+}
+
+/// Unpacks a buffer produced by [`pack_points`].
+/// The buffer length must be a multiple of 32 bytes.
+pub fn unpack_points(buffer: &[u8]) -> Result<Vec<EdwardsAffine>> {
+    if buffer.len() % 32 != 0 {
+        return Err(BabyJubjubError::PackedPointsLength(buffer.len()));
+    }
+
+    buffer
+        .chunks_exact(32)
+        .map(|chunk| unpack_point(&BigUint::from_bytes_le(chunk)))
+        .collect()
+}
+
 /// Compute square root using Tonelli-Shanks algorithm
-/// This is a simplified implementation that uses Arkworks' built-in sqrt method
-/// and handles the sign bit correctly.
+/// This uses Arkworks' built-in sqrt method and handles the sign bit correctly.
 /// The sign bit indicates whether x should be "negative" (x > p/2) in field sense.
 ///
 /// Matches TS behavior: if sign is true, negate x (Fr.neg(x))
@@ -289,30 +331,25 @@ fn tonelli_shanks(n: Fq, x_sign: bool) -> Result<Fq> {
         return Ok(Fq::zero());
     }
 
-    // Try to compute square root using Field trait's sqrt method
-    if let Some(x1) = n.sqrt() {
-        // TS behavior: if sign is true, negate x
-        // We need to check if x1 matches the expected sign
-        let x1_is_negative = is_negative_fq(&x1);
-
-        // Return the correct square root based on the sign bit
-        // If sign is true, we want a "negative" x (x > p/2)
-        // If sign is false, we want a "positive" x (x <= p/2)
-        if x1_is_negative == x_sign {
-            Ok(x1)
-        } else {
-            // Use the other square root (negate) to match the sign
-            Ok(-x1)
-        }
+    // BN254 Fq implements sqrt, so the only failure is a non-residue; the
+    // squaring check makes sure we never return a wrong root
+    let x1 = n
+        .sqrt()
+        .filter(|x| x.square() == n)
+        .ok_or(BabyJubjubError::NotQuadraticResidue)?;
+
+    // TS behavior: if sign is true, negate x
+    // We need to check if x1 matches the expected sign
+    let x1_is_negative = is_negative_fq(&x1);
+
+    // Return the correct square root based on the sign bit
+    // If sign is true, we want a "negative" x (x > p/2)
+    // If sign is false, we want a "positive" x (x <= p/2)
+    if x1_is_negative == x_sign {
+        Ok(x1)
     } else {
-        // sqrt() returned None - this means either:
-        // 1. n is not a quadratic residue
-        // 2. sqrt() is not implemented for this field
-        // TS returns null in this case
-        Err(BabyJubjubError::SquareRootError(format!(
-            "Cannot compute square root - value is either not a quadratic residue or sqrt() is not implemented for this field: {}",
-            n
-        )))
+        // Use the other square root (negate) to match the sign
+        Ok(-x1)
     }
 }
 
@@ -321,6 +358,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "rand")]
     fn test_gen_random_babyjub_value() {
         let value = gen_random_babyjub_value();
         let max = BigUint::from(2u32).pow(253);
@@ -328,6 +366,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "rand")]
     fn test_random_values_are_different() {
         let val1 = gen_random_babyjub_value();
         let val2 = gen_random_babyjub_value();
@@ -336,6 +375,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "rand")]
     fn test_gen_random_fr() {
         let fr1 = gen_random_fr();
         let fr2 = gen_random_fr();
@@ -538,4 +578,48 @@ mod tests {
         // Verify point is on curve
         assert!(in_curve(&public_key), "Point should be on curve");
     }
+
+    #[test]
+    fn test_tonelli_shanks_rejects_non_residue() {
+        // 5 generates the multiplicative group of the field, so it has no square root
+        let n = Fq::from(5u64);
+        assert!(n.legendre().is_qnr());
+        assert_eq!(
+            tonelli_shanks(n, false),
+            Err(BabyJubjubError::NotQuadraticResidue)
+        );
+    }
+
+    #[test]
+    fn test_tonelli_shanks_respects_sign() {
+        let n = Fq::from(4u64);
+        let positive = tonelli_shanks(n, false).unwrap();
+        let negative = tonelli_shanks(n, true).unwrap();
+
+        assert_eq!(positive, Fq::from(2u64));
+        assert_eq!(negative, -Fq::from(2u64));
+    }
+
+    #[test]
+    fn test_pack_unpack_points_roundtrip() {
+        let points: Vec<EdwardsAffine> = [1u64, 324u64, 1000u64]
+            .iter()
+            .map(|s| mul_point_escalar(&base8(), EdFr::from(*s)))
+            .collect();
+
+        let buffer = pack_points(&points);
+        assert_eq!(buffer.len(), 96);
+
+        let unpacked = unpack_points(&buffer).expect("Failed to unpack points");
+        assert_eq!(unpacked, points);
+    }
+
+    #[test]
+    fn test_unpack_points_rejects_partial_chunk() {
+        let buffer = pack_points(&[base8()]);
+        assert_eq!(
+            unpack_points(&buffer[..31]),
+            Err(BabyJubjubError::PackedPointsLength(31))
+        );
+    }
 }
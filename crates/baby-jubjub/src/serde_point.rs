@@ -0,0 +1,64 @@
+//! Serde support for curve points
+//!
+//! Arkworks points don't serialize to anything the MACI tooling understands,
+//! so `SerdePoint` goes through the packed form instead: the point is written
+//! as the decimal string of `pack_point`, the same value the TS SDK uses for
+//! packed public keys.
+
+use crate::{pack_point, unpack_point, EdwardsAffine};
+use num_bigint::BigUint;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// An `EdwardsAffine` point that serializes as its packed decimal string
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SerdePoint(pub EdwardsAffine);
+
+impl From<EdwardsAffine> for SerdePoint {
+    fn from(point: EdwardsAffine) -> Self {
+        SerdePoint(point)
+    }
+}
+
+impl From<SerdePoint> for EdwardsAffine {
+    fn from(point: SerdePoint) -> Self {
+        point.0
+    }
+}
+
+impl Serialize for SerdePoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&pack_point(&self.0).to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for SerdePoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let packed = String::deserialize(deserializer)?;
+        let packed = BigUint::parse_bytes(packed.as_bytes(), 10)
+            .ok_or_else(|| de::Error::custom(format!("invalid packed point: {}", packed)))?;
+        unpack_point(&packed)
+            .map(SerdePoint)
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base8, mul_point_escalar, EdFr};
+
+    #[test]
+    fn test_serde_point_json_roundtrip() {
+        let point = mul_point_escalar(&base8(), EdFr::from(324u64));
+        let json = serde_json::to_string(&SerdePoint::from(point)).unwrap();
+        assert_eq!(json, format!("\"{}\"", pack_point(&point)));
+
+        let decoded: SerdePoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(EdwardsAffine::from(decoded), point);
+    }
+
+    #[test]
+    fn test_serde_point_rejects_non_decimal() {
+        assert!(serde_json::from_str::<SerdePoint>("\"0xabc\"").is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use ark_ff::{BigInteger, PrimeField};
-use baby_jubjub::{add_point, base8, in_curve, mul_point_escalar, pack_point, unpack_point, EdFr};
+use baby_jubjub::{
+    add_point, base8, in_curve, mul_point_escalar, pack_point, unpack_point, EdFr, SNARK_FIELD_SIZE,
+};
 use crypto_test_gen::{BabyJubjubData, BabyJubjubTestVector, PointJson};
 use num_bigint::BigUint;
 use serde_json;
@@ -160,9 +162,40 @@ fn generate_vectors() -> Result<Vec<BabyJubjubTestVector>> {
         },
     });
 
+    vectors.extend(generate_invalid_pack_vectors());
+
     Ok(vectors)
 }
 
+/// Packed points that must fail to unpack
+fn generate_invalid_pack_vectors() -> Vec<BabyJubjubTestVector> {
+    // y = r + 1 fits in 255 bits but is outside the field
+    let y_out_of_range = &*SNARK_FIELD_SIZE + 1u32;
+    // For y = 2, (1 - y^2) / (a - d*y^2) is not a square, so no x exists
+    let off_curve = BigUint::from(2u32);
+
+    vec![
+        BabyJubjubTestVector {
+            name: "invalidPackUnpack_y_out_of_range".to_string(),
+            description: "Packed y coordinate greater than the field modulus".to_string(),
+            vector_type: "invalidPackUnpack".to_string(),
+            data: BabyJubjubData::InvalidPackUnpack {
+                packed: y_out_of_range.to_string(),
+                reason: "y coordinate out of range".to_string(),
+            },
+        },
+        BabyJubjubTestVector {
+            name: "invalidPackUnpack_off_curve".to_string(),
+            description: "Packed y = 2, which has no matching x on the curve".to_string(),
+            vector_type: "invalidPackUnpack".to_string(),
+            data: BabyJubjubData::InvalidPackUnpack {
+                packed: off_curve.to_string(),
+                reason: "point not on curve".to_string(),
+            },
+        },
+    ]
+}
+
 fn main() -> Result<()> {
     println!("Generating Baby Jubjub test vectors...");
 
@@ -184,3 +217,21 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_pack_vectors_fail_to_unpack() {
+        let vectors = generate_invalid_pack_vectors();
+        assert_eq!(vectors.len(), 2);
+
+        for vector in vectors {
+            let BabyJubjubData::InvalidPackUnpack { packed, .. } = vector.data else {
+                panic!("unexpected vector data for {}", vector.name);
+            };
+            let packed = BigUint::parse_bytes(packed.as_bytes(), 10).unwrap();
+            assert!(unpack_point(&packed).is_err(), "{} unpacked", vector.name);
+        }
+    }
+}
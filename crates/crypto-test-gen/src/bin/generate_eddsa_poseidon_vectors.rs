@@ -4,7 +4,9 @@ use eddsa_poseidon::{
     derive_public_key, derive_secret_scalar, pack_public_key, pack_signature, sign_message,
     unpack_public_key, unpack_signature, verify_signature, HashingAlgorithm,
 };
-use crypto_test_gen::{EdDSAData, EdDSAPoseidonTestVector, PointJson, SignatureJson};
+use crypto_test_gen::{
+    hashing_algorithm_tag, EdDSAData, EdDSAPoseidonTestVector, PointJson, SignatureJson,
+};
 use num_bigint::BigUint;
 use serde_json;
 use std::fs;
@@ -26,12 +28,9 @@ fn bytes_to_hex(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
-fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
+fn generate_vectors(algorithm: HashingAlgorithm) -> Result<Vec<EdDSAPoseidonTestVector>> {
     let mut vectors = Vec::new();
 
-    // Use Blake512 as default (matching zk-kit default)
-    let algorithm = HashingAlgorithm::Blake512;
-
     // Test 1: Derive public key from string "secret"
     let priv_key_str = "secret";
     let priv_key_bytes = priv_key_str.as_bytes();
@@ -49,6 +48,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             private_key_bytes: bytes_to_hex(priv_key_bytes),
             secret_scalar: secret_scalar.to_string(),
             public_key: point_to_json(&pub_key),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -73,6 +73,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
                 s: signature.s.to_string(),
             },
             valid,
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -113,6 +114,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             },
             valid: verify_signature(&message_numeric, &sig_numeric, &pub_key)
                 .map_err(|e| anyhow::anyhow!(e))?,
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -136,6 +138,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             },
             valid: verify_signature(&message_hex, &sig_hex, &pub_key)
                 .map_err(|e| anyhow::anyhow!(e))?,
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -161,6 +164,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             },
             valid: verify_signature(&msg_as_bigint, &sig_str, &pub_key)
                 .map_err(|e| anyhow::anyhow!(e))?,
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -180,6 +184,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             private_key_bytes: bytes_to_hex(&priv_key_buffer),
             secret_scalar: secret_scalar_buffer.to_string(),
             public_key: point_to_json(&pub_key_buffer),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -199,6 +204,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             private_key_bytes: bytes_to_hex(&priv_key_u8arr),
             secret_scalar: secret_scalar_u8arr.to_string(),
             public_key: point_to_json(&pub_key_u8arr),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -215,6 +221,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             private_key_bytes: bytes_to_hex(priv_key_bytes),
             secret_scalar: secret_scalar.to_string(),
             public_key: point_to_json(&pub_key),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -255,6 +262,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             formatted_priv_key: formatted_priv_key_1.to_string(),
             pub_key: point_to_json(&pub_key_1),
             packed_pub_key: packed_pub_key_1.to_string(),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -285,6 +293,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             formatted_priv_key: formatted_priv_key_2.to_string(),
             pub_key: point_to_json(&pub_key_2),
             packed_pub_key: packed_pub_key_2.to_string(),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -315,6 +324,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             formatted_priv_key: formatted_priv_key_3.to_string(),
             pub_key: point_to_json(&pub_key_3),
             packed_pub_key: packed_pub_key_3.to_string(),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -345,6 +355,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             formatted_priv_key: formatted_priv_key_4.to_string(),
             pub_key: point_to_json(&pub_key_4),
             packed_pub_key: packed_pub_key_4.to_string(),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -371,6 +382,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             formatted_priv_key: formatted_priv_key_5.to_string(),
             pub_key: point_to_json(&pub_key_5),
             packed_pub_key: packed_pub_key_5.to_string(),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -418,6 +430,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             secret_scalar: keypair_secret_scalar_1.to_string(),
             pub_key: point_to_json(&keypair_pub_key_1),
             commitment: keypair_commitment_1.to_string(),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
     
@@ -446,6 +459,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             secret_scalar: keypair_secret_scalar_2.to_string(),
             pub_key: point_to_json(&keypair_pub_key_2),
             commitment: keypair_commitment_2.to_string(),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
     
@@ -470,6 +484,7 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
             secret_scalar: keypair_secret_scalar_3.to_string(),
             pub_key: point_to_json(&keypair_pub_key_3),
             commitment: keypair_commitment_3.to_string(),
+            hashing_algorithm: hashing_algorithm_tag(algorithm),
         },
     });
 
@@ -479,7 +494,8 @@ fn generate_vectors() -> Result<Vec<EdDSAPoseidonTestVector>> {
 fn main() -> Result<()> {
     println!("Generating EdDSA-Poseidon test vectors...");
 
-    let vectors = generate_vectors()?;
+    // Use Blake512 as default (matching zk-kit default)
+    let vectors = generate_vectors(HashingAlgorithm::Blake512)?;
 
     println!("Generated {} test vectors", vectors.len());
 
@@ -496,3 +512,45 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded_tags(vectors: &[EdDSAPoseidonTestVector]) -> Vec<String> {
+        vectors
+            .iter()
+            .filter_map(|v| match &v.data {
+                EdDSAData::DerivePublicKey {
+                    hashing_algorithm, ..
+                }
+                | EdDSAData::SignVerify {
+                    hashing_algorithm, ..
+                }
+                | EdDSAData::SdkKeys {
+                    hashing_algorithm, ..
+                }
+                | EdDSAData::KeypairModule {
+                    hashing_algorithm, ..
+                } => Some(hashing_algorithm.clone()),
+                EdDSAData::PackSignature { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_vectors_record_hashing_algorithm() {
+        for (algorithm, tag) in [
+            (HashingAlgorithm::Blake512, "blake512"),
+            (HashingAlgorithm::Blake2b, "blake2b"),
+        ] {
+            let tags = recorded_tags(&generate_vectors(algorithm).unwrap());
+            assert_eq!(tags.len(), 16);
+            assert!(
+                tags.iter().all(|t| t == tag),
+                "wrong tag for {:?}",
+                algorithm
+            );
+        }
+    }
+}
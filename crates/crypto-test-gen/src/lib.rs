@@ -4,6 +4,7 @@
 //! - Baby Jubjub curve operations
 //! - EdDSA-Poseidon signatures
 
+use eddsa_poseidon::HashingAlgorithm;
 use serde::{Deserialize, Serialize};
 
 /// Point on the Baby Jubjub curve
@@ -39,6 +40,11 @@ pub enum BabyJubjubData {
         point: PointJson,
         packed: String,
     },
+    /// Malformed packed point that every implementation must refuse to unpack
+    InvalidPackUnpack {
+        packed: String,
+        reason: String,
+    },
     InCurve {
         point: PointJson,
         on_curve: bool,
@@ -62,6 +68,7 @@ pub enum EdDSAData {
         private_key_bytes: String,
         secret_scalar: String,
         public_key: PointJson,
+        hashing_algorithm: String,
     },
     SignVerify {
         private_key: String,
@@ -70,6 +77,7 @@ pub enum EdDSAData {
         public_key: PointJson,
         signature: SignatureJson,
         valid: bool,
+        hashing_algorithm: String,
     },
     PackSignature {
         signature: SignatureJson,
@@ -81,6 +89,7 @@ pub enum EdDSAData {
         formatted_priv_key: String,
         pub_key: PointJson,
         packed_pub_key: String,
+        hashing_algorithm: String,
     },
     KeypairModule {
         priv_key: String,
@@ -88,9 +97,18 @@ pub enum EdDSAData {
         secret_scalar: String,
         pub_key: PointJson,
         commitment: String,
+        hashing_algorithm: String,
     },
 }
 
+/// Tag recorded in EdDSA vectors for the key-derivation hash
+pub fn hashing_algorithm_tag(algorithm: HashingAlgorithm) -> String {
+    match algorithm {
+        HashingAlgorithm::Blake512 => "blake512".to_string(),
+        HashingAlgorithm::Blake2b => "blake2b".to_string(),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignatureJson {
     pub r8: PointJson,
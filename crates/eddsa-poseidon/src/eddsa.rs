@@ -73,6 +73,14 @@ fn subgroup_order_biguint() -> BigUint {
     .expect("Failed to parse subgroup order")
 }
 
+/// Rejects an empty or all-zero private key
+fn check_private_key(private_key: &[u8]) -> Result<(), String> {
+    if private_key.iter().all(|b| *b == 0) {
+        return Err("Invalid private key: must be nonzero".to_string());
+    }
+    Ok(())
+}
+
 /// Derives a secret scalar from a given private key.
 /// Direct translation of TypeScript deriveSecretScalar().
 ///
@@ -83,10 +91,15 @@ fn subgroup_order_biguint() -> BigUint {
 /// 4. Convert to BigUint (little-endian)
 /// 5. Shift right by 3
 /// 6. Modulo subgroup order
+///
+/// Fails for a zero private key, or if the derived scalar is zero, since
+/// either would give a degenerate key.
 pub fn derive_secret_scalar(
     private_key: &[u8],
     algorithm: HashingAlgorithm,
 ) -> Result<BigUint, String> {
+    check_private_key(private_key)?;
+
     // Hash the private key
     let hash = hash_input(private_key, algorithm);
 
@@ -102,7 +115,12 @@ pub fn derive_secret_scalar(
     let s_shifted = s >> 3;
     let sub_order = subgroup_order_biguint();
 
-    Ok(s_shifted % sub_order)
+    let scalar = s_shifted % sub_order;
+    if scalar == BigUint::from(0u32) {
+        return Err("Invalid private key: derived secret scalar is zero".to_string());
+    }
+
+    Ok(scalar)
 }
 
 /// Derives a public key from a given private key.
@@ -140,6 +158,8 @@ pub fn sign_message(
     message: &BigUint,
     algorithm: HashingAlgorithm,
 ) -> Result<Signature, String> {
+    check_private_key(private_key)?;
+
     // Hash the private key
     let hash = hash_input(private_key, algorithm);
 
@@ -302,6 +322,18 @@ pub fn unpack_signature(packed: &[u8]) -> Result<Signature, String> {
     Ok(Signature { r8, s })
 }
 
+/// Signs a message and packs the signature in one step.
+/// Returns the 64-byte `pack_signature` buffer read as a little-endian integer.
+pub fn sign_and_pack(
+    private_key: &[u8],
+    message: &BigUint,
+    algorithm: HashingAlgorithm,
+) -> Result<BigUint, String> {
+    let signature = sign_message(private_key, message, algorithm)?;
+    let packed = pack_signature(&signature)?;
+    Ok(bytes_to_biguint_le(&packed))
+}
+
 /// EdDSAPoseidon struct - encapsulates key management and signing/verification.
 /// Direct translation of TypeScript EdDSAPoseidon class.
 pub struct EdDSAPoseidon {
@@ -365,6 +397,18 @@ mod tests {
         assert!(scalar.is_ok());
     }
 
+    #[test]
+    fn test_zero_private_key_rejected() {
+        let message = BigUint::from(1u64);
+        for private_key in [&[][..], &[0u8][..], &[0u8; 32][..]] {
+            assert!(derive_secret_scalar(private_key, HashingAlgorithm::Blake512).is_err());
+            assert!(derive_public_key(private_key, HashingAlgorithm::Blake512).is_err());
+            assert!(sign_message(private_key, &message, HashingAlgorithm::Blake512).is_err());
+        }
+
+        assert!(derive_secret_scalar(&[1u8], HashingAlgorithm::Blake512).is_ok());
+    }
+
     #[test]
     fn test_derive_public_key() {
         let private_key = b"test_private_key";
@@ -415,6 +459,18 @@ mod tests {
         assert_eq!(unpacked.s, signature.s);
     }
 
+    #[test]
+    fn test_sign_and_pack_matches_pack_signature() {
+        let private_key = b"test_private_key";
+        let message = BigUint::from(12345u64);
+
+        let packed = sign_and_pack(private_key, &message, HashingAlgorithm::Blake512).unwrap();
+        let signature = sign_message(private_key, &message, HashingAlgorithm::Blake512).unwrap();
+        let expected = pack_signature(&signature).unwrap();
+
+        assert_eq!(packed, BigUint::from_bytes_le(&expected));
+    }
+
     #[test]
     fn test_eddsa_poseidon_struct() {
         let eddsa =
@@ -15,8 +15,8 @@ mod types;
 mod utils;
 
 pub use eddsa::{
-    derive_public_key, derive_secret_scalar, pack_public_key, pack_signature, sign_message,
-    unpack_public_key, unpack_signature, verify_signature, EdDSAPoseidon,
+    derive_public_key, derive_secret_scalar, pack_public_key, pack_signature, sign_and_pack,
+    sign_message, unpack_public_key, unpack_signature, verify_signature, EdDSAPoseidon,
 };
 pub use types::{HashingAlgorithm, Signature};
 
@@ -0,0 +1,293 @@
+//! Poseidon-based message encryption
+//!
+//! MACI voters encrypt their commands with the Poseidon duplex-sponge cipher
+//! (`@zk-kit/poseidon-cipher`) keyed by the ECDH shared key between the
+//! voter's ephemeral key and the coordinator key. A 7-element command is
+//! padded to 9 elements and encrypted to 10 field elements, which is the
+//! `MessageData` layout stored on-chain.
+
+use crate::constants::{biguint_to_fr, fr_to_biguint, SNARK_FIELD_SIZE};
+use crate::error::{CryptoError, Result};
+use crate::keys::EcdhSharedKey;
+use ark_bn254::Fr;
+use ark_ff::{Field, Zero};
+use light_poseidon::parameters::bn254_x5::get_poseidon_parameters;
+use num_bigint::BigUint;
+use once_cell::sync::Lazy;
+
+/// Number of field elements in a MACI command
+pub const COMMAND_LENGTH: usize = 7;
+
+/// Number of field elements in an encrypted MACI message
+pub const MESSAGE_DATA_LENGTH: usize = 10;
+
+/// An encrypted MACI message as published on-chain
+pub type MessageData = [BigUint; MESSAGE_DATA_LENGTH];
+
+/// Nonce MACI uses for every message; each message has a fresh shared key
+const MESSAGE_NONCE: u32 = 0;
+
+/// Sponge width: one capacity element plus a rate of three
+const STATE_WIDTH: usize = 4;
+
+type State = [Fr; STATE_WIDTH];
+
+/// Circom-compatible Poseidon parameters for width 4
+static PERM_PARAMS: Lazy<light_poseidon::PoseidonParameters<Fr>> = Lazy::new(|| {
+    get_poseidon_parameters::<Fr>(STATE_WIDTH as u8)
+        .expect("Poseidon parameters for width 4 should always exist")
+});
+
+/// Full Poseidon permutation over a width-4 state (circomlibjs `poseidonPerm`)
+fn poseidon_perm(mut state: State) -> State {
+    let params = &*PERM_PARAMS;
+    let half_full = params.full_rounds / 2;
+    let total_rounds = params.full_rounds + params.partial_rounds;
+
+    for round in 0..total_rounds {
+        for (i, s) in state.iter_mut().enumerate() {
+            *s += params.ark[round * STATE_WIDTH + i];
+        }
+
+        if round < half_full || round >= half_full + params.partial_rounds {
+            for s in state.iter_mut() {
+                *s = s.pow([params.alpha]);
+            }
+        } else {
+            state[0] = state[0].pow([params.alpha]);
+        }
+
+        let mut mixed = [Fr::zero(); STATE_WIDTH];
+        for (i, m) in mixed.iter_mut().enumerate() {
+            for (j, s) in state.iter().enumerate() {
+                *m += params.mds[i][j] * s;
+            }
+        }
+        state = mixed;
+    }
+
+    state
+}
+
+fn initial_state(key: &EcdhSharedKey, nonce: &BigUint, length: usize) -> Result<State> {
+    let two_128 = BigUint::from(1u8) << 128;
+    if nonce >= &two_128 {
+        return Err(CryptoError::InvalidFieldElement(
+            "cipher nonce must be less than 2^128".to_string(),
+        ));
+    }
+
+    let domain = nonce + BigUint::from(length) * two_128;
+    Ok([
+        Fr::zero(),
+        biguint_to_fr(&(&key[0] % &*SNARK_FIELD_SIZE)),
+        biguint_to_fr(&(&key[1] % &*SNARK_FIELD_SIZE)),
+        biguint_to_fr(&domain),
+    ])
+}
+
+/// Encrypt `plaintext` with the Poseidon cipher
+///
+/// The plaintext is zero-padded to a multiple of three and the output has
+/// `padded_len + 1` elements, the last one authenticating the ciphertext.
+pub fn poseidon_encrypt(
+    plaintext: &[BigUint],
+    key: &EcdhSharedKey,
+    nonce: &BigUint,
+) -> Result<Vec<BigUint>> {
+    let mut state = initial_state(key, nonce, plaintext.len())?;
+
+    let mut message: Vec<Fr> = plaintext
+        .iter()
+        .map(|m| biguint_to_fr(&(m % &*SNARK_FIELD_SIZE)))
+        .collect();
+    while message.len() % 3 != 0 {
+        message.push(Fr::zero());
+    }
+
+    let mut ciphertext = Vec::with_capacity(message.len() + 1);
+    for chunk in message.chunks(3) {
+        state = poseidon_perm(state);
+        for (k, m) in chunk.iter().enumerate() {
+            state[k + 1] += m;
+            ciphertext.push(fr_to_biguint(&state[k + 1]));
+        }
+    }
+
+    state = poseidon_perm(state);
+    ciphertext.push(fr_to_biguint(&state[1]));
+
+    Ok(ciphertext)
+}
+
+/// Decrypt a Poseidon cipher `ciphertext` into a plaintext of `length` elements
+///
+/// Fails if the ciphertext does not authenticate under `key`/`nonce`, or if the
+/// padding elements do not decrypt to zero.
+pub fn poseidon_decrypt(
+    ciphertext: &[BigUint],
+    key: &EcdhSharedKey,
+    nonce: &BigUint,
+    length: usize,
+) -> Result<Vec<BigUint>> {
+    let padded_len = length.div_ceil(3) * 3;
+    if ciphertext.len() != padded_len + 1 {
+        return Err(CryptoError::DecryptionFailed(format!(
+            "expected {} ciphertext elements for length {}, got {}",
+            padded_len + 1,
+            length,
+            ciphertext.len()
+        )));
+    }
+
+    let mut state = initial_state(key, nonce, length)?;
+    let ciphertext: Vec<Fr> = ciphertext
+        .iter()
+        .map(|c| biguint_to_fr(&(c % &*SNARK_FIELD_SIZE)))
+        .collect();
+
+    let mut message = Vec::with_capacity(padded_len);
+    for chunk in ciphertext[..padded_len].chunks(3) {
+        state = poseidon_perm(state);
+        for (k, c) in chunk.iter().enumerate() {
+            message.push(*c - state[k + 1]);
+            state[k + 1] = *c;
+        }
+    }
+
+    state = poseidon_perm(state);
+    if ciphertext[padded_len] != state[1] {
+        return Err(CryptoError::DecryptionFailed(
+            "authentication tag mismatch".to_string(),
+        ));
+    }
+
+    if message[length..].iter().any(|m| !m.is_zero()) {
+        return Err(CryptoError::DecryptionFailed(
+            "padding elements must decrypt to zero".to_string(),
+        ));
+    }
+
+    message.truncate(length);
+    Ok(message.iter().map(fr_to_biguint).collect())
+}
+
+/// Encrypt a 7-element command into a publishable MACI message
+pub fn encrypt_command(shared_key: &EcdhSharedKey, command: &[BigUint]) -> Result<MessageData> {
+    if command.len() != COMMAND_LENGTH {
+        return Err(CryptoError::InvalidCommandLength {
+            expected: COMMAND_LENGTH,
+            actual: command.len(),
+        });
+    }
+
+    let ciphertext = poseidon_encrypt(command, shared_key, &BigUint::from(MESSAGE_NONCE))?;
+    Ok(ciphertext
+        .try_into()
+        .expect("a 7-element command always encrypts to 10 elements"))
+}
+
+/// Decrypt a published MACI message back into its 7-element command
+pub fn decrypt_message(shared_key: &EcdhSharedKey, message: &MessageData) -> Result<Vec<BigUint>> {
+    poseidon_decrypt(
+        message,
+        shared_key,
+        &BigUint::from(MESSAGE_NONCE),
+        COMMAND_LENGTH,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::poseidon;
+    use crate::keys::{gen_ecdh_shared_key, gen_keypair};
+
+    fn sample_command() -> Vec<BigUint> {
+        (1u32..=COMMAND_LENGTH as u32)
+            .map(|i| BigUint::from(i) * BigUint::from(1_000_003u32))
+            .collect()
+    }
+
+    fn shared_key() -> EcdhSharedKey {
+        let voter = gen_keypair(Some(BigUint::from(111u32)));
+        let coordinator = gen_keypair(Some(BigUint::from(222u32)));
+        gen_ecdh_shared_key(&voter.priv_key, &coordinator.pub_key)
+    }
+
+    #[test]
+    fn test_perm_matches_poseidon_hash() {
+        // Poseidon hash of 3 inputs is the first element of perm([0, a, b, c])
+        let inputs = [1u32, 2, 3].map(BigUint::from);
+        let state = poseidon_perm([
+            Fr::zero(),
+            biguint_to_fr(&inputs[0]),
+            biguint_to_fr(&inputs[1]),
+            biguint_to_fr(&inputs[2]),
+        ]);
+        assert_eq!(fr_to_biguint(&state[0]), poseidon(&inputs));
+    }
+
+    #[test]
+    fn test_decrypt_message_recovers_command() {
+        let key = shared_key();
+        let command = sample_command();
+
+        let ciphertext = poseidon_encrypt(&command, &key, &BigUint::from(0u32)).unwrap();
+        assert_eq!(ciphertext.len(), MESSAGE_DATA_LENGTH);
+
+        let message: MessageData = ciphertext.try_into().unwrap();
+        assert_eq!(decrypt_message(&key, &message).unwrap(), command);
+    }
+
+    #[test]
+    fn test_decrypt_message_inverts_encrypt_command() {
+        let key = shared_key();
+        let command = sample_command();
+
+        let message = encrypt_command(&key, &command).unwrap();
+        assert_eq!(decrypt_message(&key, &message).unwrap(), command);
+    }
+
+    #[test]
+    fn test_encrypt_command_rejects_wrong_length() {
+        let command = sample_command();
+        assert_eq!(
+            encrypt_command(&shared_key(), &command[..6]),
+            Err(CryptoError::InvalidCommandLength {
+                expected: COMMAND_LENGTH,
+                actual: 6,
+            })
+        );
+    }
+
+    #[test]
+    fn test_decrypt_message_rejects_wrong_key() {
+        let key = shared_key();
+        let ciphertext = poseidon_encrypt(&sample_command(), &key, &BigUint::from(0u32)).unwrap();
+        let message: MessageData = ciphertext.try_into().unwrap();
+
+        let other = gen_keypair(Some(BigUint::from(333u32)));
+        let wrong_key = gen_ecdh_shared_key(&other.priv_key, &other.pub_key);
+        assert!(matches!(
+            decrypt_message(&wrong_key, &message),
+            Err(CryptoError::DecryptionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_rejects_tampered_ciphertext() {
+        let key = shared_key();
+        let mut ciphertext =
+            poseidon_encrypt(&sample_command(), &key, &BigUint::from(0u32)).unwrap();
+        ciphertext[2] += 1u32;
+
+        assert!(poseidon_decrypt(&ciphertext, &key, &BigUint::from(0u32), COMMAND_LENGTH).is_err());
+    }
+
+    #[test]
+    fn test_nonce_out_of_range() {
+        let nonce = BigUint::from(1u8) << 128;
+        assert!(poseidon_encrypt(&sample_command(), &shared_key(), &nonce).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::hashing::hash2;
 use ark_bn254::Fr;
 use ark_ff::{BigInteger, PrimeField};
 use num_bigint::BigUint;
@@ -23,18 +24,27 @@ pub static SNARK_FIELD_MODULUS: Lazy<Fr> = Lazy::new(|| {
     ])
 });
 
+/// Preimage of the nothing-up-my-sleeve value
+pub const NOTHING_UP_MY_SLEEVE_PREIMAGE: &[u8] = b"Maci";
+
 /// A nothing-up-my-sleeve zero value
 /// Computed as: keccak256("Maci") % SNARK_FIELD_SIZE
 /// Should equal: 8370432830353022751713833565135785980866757267633941821328460903436894336785
-pub static NOTHING_UP_MY_SLEEVE: Lazy<BigUint> = Lazy::new(|| {
+pub static NOTHING_UP_MY_SLEEVE: Lazy<BigUint> = Lazy::new(derive_nothing_up_my_sleeve);
+
+/// Recompute the nothing-up-my-sleeve value from its preimage
+///
+/// Returns `keccak256(NOTHING_UP_MY_SLEEVE_PREIMAGE) % SNARK_FIELD_SIZE`, so the
+/// stored constant can be reproduced independently of this library.
+pub fn derive_nothing_up_my_sleeve() -> BigUint {
     let mut hasher = Keccak::v256();
     let mut output = [0u8; 32];
-    hasher.update(b"Maci");
+    hasher.update(NOTHING_UP_MY_SLEEVE_PREIMAGE);
     hasher.finalize(&mut output);
 
     let hash_value = BigUint::from_bytes_be(&output);
     &hash_value % &*SNARK_FIELD_SIZE
-});
+}
 
 /// Nothing-up-my-sleeve value as an Arkworks Fr element
 pub static NOTHING_UP_MY_SLEEVE_FR: Lazy<Fr> = Lazy::new(|| biguint_to_fr(&NOTHING_UP_MY_SLEEVE));
@@ -49,6 +59,30 @@ pub static PAD_KEY_HASH: Lazy<BigUint> = Lazy::new(|| {
     .expect("Failed to parse PAD_KEY_HASH")
 });
 
+/// Padding public key used for empty message slots
+/// Its hash under `hash2` is `PAD_KEY_HASH`
+pub static PAD_PUB_KEY: Lazy<[BigUint; 2]> = Lazy::new(|| {
+    [
+        BigUint::parse_bytes(
+            b"10457101036533406547632367118273992217979173478358440826365724437999023779287",
+            10,
+        )
+        .expect("Failed to parse PAD_PUB_KEY x"),
+        BigUint::parse_bytes(
+            b"19824078218392094440610104313265183977899662750282163392862422243483260492317",
+            10,
+        )
+        .expect("Failed to parse PAD_PUB_KEY y"),
+    ]
+});
+
+/// Recompute the padding key hash from the padding public key coordinates
+///
+/// Returns `hash2([PAD_PUB_KEY.x, PAD_PUB_KEY.y])`, which should equal `PAD_KEY_HASH`.
+pub fn compute_pad_key_hash() -> BigUint {
+    hash2(&*PAD_PUB_KEY).expect("hash2 accepts exactly two elements")
+}
+
 /// Padding key hash as an Arkworks Fr element
 pub static PAD_KEY_HASH_FR: Lazy<Fr> = Lazy::new(|| biguint_to_fr(&PAD_KEY_HASH));
 
@@ -100,6 +134,17 @@ mod tests {
         assert_eq!(*NOTHING_UP_MY_SLEEVE, expected);
     }
 
+    #[test]
+    fn test_derive_nothing_up_my_sleeve() {
+        let expected = BigUint::parse_bytes(
+            b"8370432830353022751713833565135785980866757267633941821328460903436894336785",
+            10,
+        )
+        .unwrap();
+        assert_eq!(derive_nothing_up_my_sleeve(), expected);
+        assert_eq!(derive_nothing_up_my_sleeve(), *NOTHING_UP_MY_SLEEVE);
+    }
+
     #[test]
     fn test_pad_key_hash() {
         let expected = BigUint::parse_bytes(
@@ -110,6 +155,11 @@ mod tests {
         assert_eq!(*PAD_KEY_HASH, expected);
     }
 
+    #[test]
+    fn test_compute_pad_key_hash() {
+        assert_eq!(compute_pad_key_hash(), *PAD_KEY_HASH);
+    }
+
     #[test]
     fn test_uint32() {
         assert_eq!(*UINT32, BigUint::from(4294967296u64));
@@ -0,0 +1,126 @@
+//! Deactivate tree inclusion proofs for AMACI
+//!
+//! The contract only keeps the deactivate tree root (`DNODES[0]`); the tree
+//! itself is rebuilt off-chain from the processed deactivate messages. It is
+//! a quinary tree of depth `state_tree_depth + 2` with zero leaves, where each
+//! leaf is `poseidon([c1.x, c1.y, c2.x, c2.y, poseidon(shared_key)])`, and
+//! leaves are appended in processing order. Add-key proofs need the path from
+//! a deactivated leaf to that root.
+
+use crate::error::{CryptoError, Result};
+use crate::hashing::poseidon;
+use crate::tree::{biguint_to_node, node_to_biguint, Tree};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// Arity of the deactivate tree
+pub const DEACTIVATE_TREE_DEGREE: usize = 5;
+
+/// Inclusion proof of a deactivated leaf
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeactivateProof {
+    pub leaf: BigUint,
+    pub leaf_idx: usize,
+    /// The four siblings at each level, bottom-up
+    pub path_elements: Vec<Vec<BigUint>>,
+    /// Position of the path node among its siblings at each level, bottom-up
+    pub path_index: Vec<usize>,
+    pub root: BigUint,
+}
+
+impl DeactivateProof {
+    /// Recompute the root from the leaf and its path
+    pub fn compute_root(&self) -> BigUint {
+        let mut node = self.leaf.clone();
+        for (siblings, &position) in self.path_elements.iter().zip(&self.path_index) {
+            let mut children = siblings.clone();
+            children.insert(position, node);
+            node = poseidon(&children);
+        }
+        node
+    }
+}
+
+/// Build the deactivate tree for `leaves` as the operator tracks it
+pub fn deactivate_tree(leaves: &[BigUint], state_tree_depth: usize) -> Tree {
+    let zero = biguint_to_node(&BigUint::from(0u32));
+    let mut tree = Tree::new(DEACTIVATE_TREE_DEGREE, state_tree_depth + 2, zero);
+    let leaf_nodes: Vec<String> = leaves.iter().map(biguint_to_node).collect();
+    tree.init_leaves(&leaf_nodes);
+    tree
+}
+
+/// Generate the inclusion proof of `leaves[leaf_idx]` in the deactivate tree
+pub fn gen_deactivate_proof(
+    leaves: &[BigUint],
+    state_tree_depth: usize,
+    leaf_idx: usize,
+) -> Result<DeactivateProof> {
+    if leaf_idx >= leaves.len() {
+        return Err(CryptoError::LeafIndexOutOfRange { index: leaf_idx });
+    }
+
+    let tree = deactivate_tree(leaves, state_tree_depth);
+    let path_elements = tree
+        .path_element_of(leaf_idx)?
+        .iter()
+        .map(|level| level.iter().map(node_to_biguint).collect())
+        .collect();
+    let path_index = tree
+        .path_idx_of(leaf_idx)?
+        .iter()
+        .map(|idx| idx.parse::<usize>().expect("path index is a usize"))
+        .collect();
+
+    Ok(DeactivateProof {
+        leaf: leaves[leaf_idx].clone(),
+        leaf_idx,
+        path_elements,
+        path_index,
+        root: node_to_biguint(tree.root()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fold the zero-padded leaves level by level
+    fn reference_root(leaves: &[BigUint], depth: usize) -> BigUint {
+        let mut level = leaves.to_vec();
+        level.resize(
+            DEACTIVATE_TREE_DEGREE.pow(depth as u32),
+            BigUint::from(0u32),
+        );
+        for _ in 0..depth {
+            level = level.chunks(DEACTIVATE_TREE_DEGREE).map(poseidon).collect();
+        }
+        level.remove(0)
+    }
+
+    #[test]
+    fn test_gen_deactivate_proof_matches_reference_root() {
+        let state_tree_depth = 1;
+        let leaves: Vec<BigUint> = (1u32..=7)
+            .map(|i| poseidon(&[BigUint::from(i), BigUint::from(i * 10)]))
+            .collect();
+        let expected = reference_root(&leaves, state_tree_depth + 2);
+
+        for leaf_idx in [0, 4, 5, 6] {
+            let proof = gen_deactivate_proof(&leaves, state_tree_depth, leaf_idx).unwrap();
+            assert_eq!(proof.leaf, leaves[leaf_idx]);
+            assert_eq!(proof.path_elements.len(), state_tree_depth + 2);
+            assert_eq!(proof.root, expected);
+            assert_eq!(proof.compute_root(), expected);
+        }
+    }
+
+    #[test]
+    fn test_gen_deactivate_proof_rejects_unprocessed_leaf() {
+        let leaves = vec![BigUint::from(1u32), BigUint::from(2u32)];
+        assert_eq!(
+            gen_deactivate_proof(&leaves, 1, 2).unwrap_err(),
+            CryptoError::LeafIndexOutOfRange { index: 2 }
+        );
+    }
+}
@@ -30,8 +30,8 @@ pub enum CryptoError {
     #[error("Unpacked point is not on curve")]
     PointNotOnCurve,
 
-    #[error("Cannot compute square root - value is either not a quadratic residue or sqrt() is not implemented for this field: {0}")]
-    SquareRootError(String),
+    #[error("Cannot compute square root: value is not a quadratic residue")]
+    NotQuadraticResidue,
 
     // ============ Key Errors ============
     #[error("Invalid key: {0}")]
@@ -69,6 +69,9 @@ pub enum CryptoError {
     #[error("Failed to update leaf: {0}")]
     LeafUpdateFailed(String),
 
+    #[error("Tree error: {count} leaves exceed capacity {capacity}")]
+    TooManyLeaves { count: usize, capacity: usize },
+
     // ============ Rerandomization Errors ============
     #[error("Rerandomization error: {0}")]
     RerandomizationError(String),
@@ -76,10 +79,20 @@ pub enum CryptoError {
     #[error("Invalid point coordinates: {0}")]
     InvalidPointCoordinates(String),
 
+    // ============ Cipher Errors ============
+    #[error("Decryption failed: {0}")]
+    DecryptionFailed(String),
+
+    #[error("Invalid command length: expected {expected} elements, got {actual}")]
+    InvalidCommandLength { expected: usize, actual: usize },
+
     // ============ Serialization Errors ============
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
+    #[error("Value needs {actual} bytes, exceeds {max}")]
+    ValueTooLarge { actual: usize, max: usize },
+
     // ============ Hex Decode Errors ============
     #[error("Hex decode error: {0}")]
     HexDecodeError(String),
@@ -105,7 +118,10 @@ impl From<baby_jubjub::BabyJubjubError> for CryptoError {
             baby_jubjub::BabyJubjubError::DenominatorZero => CryptoError::DenominatorZero,
             baby_jubjub::BabyJubjubError::DenominatorNoInverse => CryptoError::DenominatorNoInverse,
             baby_jubjub::BabyJubjubError::PointNotOnCurve => CryptoError::PointNotOnCurve,
-            baby_jubjub::BabyJubjubError::SquareRootError(msg) => CryptoError::SquareRootError(msg),
+            baby_jubjub::BabyJubjubError::PackedPointsLength(_) => {
+                CryptoError::InvalidPoint(err.to_string())
+            }
+            baby_jubjub::BabyJubjubError::NotQuadraticResidue => CryptoError::NotQuadraticResidue,
         }
     }
 }
@@ -0,0 +1,150 @@
+//! Public inputs for the MACI circuits
+//!
+//! The contracts never take the circuit inputs directly: they rebuild the
+//! input array from their own state and verify the proof against
+//! `compute_input_hash(input)`. Operators have to assemble the exact same
+//! array off-chain, so the layout lives here next to the hash.
+
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// Number of public inputs hashed for a process-messages proof
+pub const PROCESS_INPUTS_LENGTH: usize = 8;
+
+/// On-chain values the process-messages circuit commits to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessInputs {
+    pub num_sign_ups: BigUint,
+    pub max_vote_options: BigUint,
+    /// 0 for 1p1v, 1 for quadratic voting
+    pub circuit_type: BigUint,
+    pub coord_pub_key_hash: BigUint,
+    pub batch_start_hash: BigUint,
+    pub batch_end_hash: BigUint,
+    pub current_state_commitment: BigUint,
+    pub new_state_commitment: BigUint,
+    pub current_deactivate_commitment: BigUint,
+    pub poll_id: BigUint,
+}
+
+/// Pack the vote option count, sign-up count and circuit type
+/// Structure: maxVoteOptions + (numSignUps << 32) + (circuitType << 64)
+pub fn pack_process_vals(
+    num_sign_ups: &BigUint,
+    max_vote_options: &BigUint,
+    circuit_type: &BigUint,
+) -> BigUint {
+    max_vote_options + (num_sign_ups << 32) + (circuit_type << 64)
+}
+
+/// Build the input array for `execute_process_message`
+///
+/// Order matches the contract: packedVals, coordPubKeyHash, batchStartHash,
+/// batchEndHash, currentStateCommitment, newStateCommitment,
+/// currentDeactivateCommitment, pollId.
+pub fn build_process_inputs(inputs: &ProcessInputs) -> [BigUint; PROCESS_INPUTS_LENGTH] {
+    [
+        pack_process_vals(
+            &inputs.num_sign_ups,
+            &inputs.max_vote_options,
+            &inputs.circuit_type,
+        ),
+        inputs.coord_pub_key_hash.clone(),
+        inputs.batch_start_hash.clone(),
+        inputs.batch_end_hash.clone(),
+        inputs.current_state_commitment.clone(),
+        inputs.new_state_commitment.clone(),
+        inputs.current_deactivate_commitment.clone(),
+        inputs.poll_id.clone(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashing::poseidon;
+
+    fn dec(value: &str) -> BigUint {
+        BigUint::parse_bytes(value.as_bytes(), 10).unwrap()
+    }
+
+    // Values from contracts/amaci/src/test/msg_test.json
+    fn fixture_inputs() -> ProcessInputs {
+        let coord_pub_key = [
+            dec("3557592161792765812904087712812111121909518311142005886657252371904276697771"),
+            dec("4363822302427519764561660537570341277214758164895027920046745209970137856681"),
+        ];
+
+        ProcessInputs {
+            num_sign_ups: BigUint::from(2u32),
+            max_vote_options: BigUint::from(5u32),
+            circuit_type: BigUint::from(0u32),
+            coord_pub_key_hash: poseidon(&coord_pub_key),
+            batch_start_hash: BigUint::from(0u32),
+            batch_end_hash: dec(
+                "2806393043795941555460875661453750929773752821171436852821298017638863319962",
+            ),
+            current_state_commitment: dec(
+                "779799324713355896079979908407407728558090458572223573390617290564103869759",
+            ),
+            new_state_commitment: dec(
+                "19102887017139943850405900349357106949526983858984054911125617488021493713002",
+            ),
+            current_deactivate_commitment: BigUint::from(0u32),
+            poll_id: BigUint::from(1u32),
+        }
+    }
+
+    #[test]
+    fn test_build_process_inputs_matches_contract_layout() {
+        let inputs = fixture_inputs();
+        let built = build_process_inputs(&inputs);
+
+        // packedVals from the fixture: 2 sign-ups, 5 vote options, 1p1v
+        assert_eq!(built[0], dec("8589934597"));
+        assert_eq!(built[1], inputs.coord_pub_key_hash);
+        assert_eq!(built[2], inputs.batch_start_hash);
+        assert_eq!(built[3], inputs.batch_end_hash);
+        assert_eq!(built[4], inputs.current_state_commitment);
+        assert_eq!(built[5], inputs.new_state_commitment);
+        assert_eq!(built[6], inputs.current_deactivate_commitment);
+        assert_eq!(built[7], inputs.poll_id);
+    }
+
+    #[test]
+    fn test_pack_process_vals_includes_circuit_type() {
+        let packed = pack_process_vals(
+            &BigUint::from(2u32),
+            &BigUint::from(5u32),
+            &BigUint::from(1u32),
+        );
+        assert_eq!(packed, (BigUint::from(1u32) << 64) + dec("8589934597"));
+    }
+
+    #[test]
+    fn test_pack_process_vals_matches_contract_packing() {
+        use cosmwasm_std::Uint256;
+
+        let max_u32 = u32::MAX as u128;
+        let cases = [
+            (0u128, 0u128, 0u128),
+            (2, 5, 0),
+            (3, 25, 1),
+            (max_u32, max_u32, 1),
+        ];
+        for (num_sign_ups, max_vote_options, circuit_type) in cases {
+            // maci_utils::pack_vals is what the contracts put in the input hash
+            let expected = maci_utils::pack_vals(
+                Uint256::from_u128(num_sign_ups),
+                Uint256::from_u128(circuit_type),
+                Uint256::from_u128(max_vote_options),
+            );
+            let packed = pack_process_vals(
+                &BigUint::from(num_sign_ups),
+                &BigUint::from(max_vote_options),
+                &BigUint::from(circuit_type),
+            );
+            assert_eq!(packed, BigUint::from_bytes_be(&expected.to_be_bytes()));
+        }
+    }
+}
@@ -3,10 +3,11 @@
 //! Adapted for MACI with BigUint compatibility
 //! Uses eddsa-poseidon for key derivation and signing
 
+use crate::deactivate::deactivate_tree;
 use crate::hashing::poseidon;
 use crate::keys::{EcdhSharedKey, PrivKey, PubKey};
 use crate::rerandomize::encrypt_odevity;
-use crate::tree::{biguint_to_node, Tree};
+use crate::tree::Tree;
 use ark_bn254::Fr as Bn254Fr;
 use ark_ff::{BigInteger, PrimeField};
 use baby_jubjub::{base8, gen_random_babyjub_value, mul_point_escalar, EdFr, EdwardsAffine, Fq};
@@ -311,20 +312,12 @@ impl Keypair {
             .collect();
 
         // STEP 2: Generate tree root
-        let degree = 5;
-        let depth = state_tree_depth + 2;
-        let zero = biguint_to_node(&BigUint::from(0u32));
-        let mut tree = Tree::new(degree, depth, zero);
-
         // Hash each deactivate entry to create leaves
         let leaves: Vec<BigUint> = deactivates
             .iter()
             .map(|deactivate| poseidon(deactivate))
             .collect();
-
-        // Convert leaves to tree nodes (String format)
-        let leaf_nodes: Vec<String> = leaves.iter().map(biguint_to_node).collect();
-        tree.init_leaves(&leaf_nodes);
+        let tree = deactivate_tree(&leaves, state_tree_depth);
 
         // Get the root as BigUint
         let root_str = tree.root();
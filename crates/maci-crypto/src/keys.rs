@@ -2,13 +2,16 @@ use crate::constants::SNARK_FIELD_SIZE;
 use crate::error::{CryptoError, Result};
 use ark_ff::{BigInteger, PrimeField};
 use baby_jubjub::{mul_point_escalar, EdFr, EdwardsAffine, Fq};
+use blake::Blake;
 use eddsa_poseidon::{
-    derive_public_key, derive_secret_scalar, pack_public_key, sign_message, unpack_public_key,
-    verify_signature, HashingAlgorithm, Signature,
+    derive_public_key, pack_public_key, sign_message, unpack_public_key, verify_signature,
+    HashingAlgorithm, Signature,
 };
 use num_bigint::BigUint;
+use num_traits::Zero;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 
 /// A public key represented as a pair of BigUint coordinates
 pub type PubKey = [BigUint; 2];
@@ -28,11 +31,19 @@ pub struct Keypair {
 }
 
 /// Generate a random private key (256 bits)
+///
+/// Resamples in the (negligible) case where the key reduces to zero modulo
+/// the SNARK field, since `gen_keypair` would reject it.
 pub fn gen_priv_key() -> PrivKey {
     let mut rng = rand::thread_rng();
-    let mut bytes = [0u8; 32];
-    rng.fill(&mut bytes);
-    BigUint::from_bytes_be(&bytes)
+    loop {
+        let mut bytes = [0u8; 32];
+        rng.fill(&mut bytes);
+        let priv_key = BigUint::from_bytes_be(&bytes);
+        if !(&priv_key % &*SNARK_FIELD_SIZE).is_zero() {
+            return priv_key;
+        }
+    }
 }
 
 /// Generate a random salt (BabyJub-compatible)
@@ -41,24 +52,47 @@ pub fn gen_random_salt() -> BigUint {
 }
 
 /// Format a private key to be compatible with BabyJub curve
-/// Uses eddsa-poseidon's derive_secret_scalar which handles Blake-512 hashing
-/// and proper key derivation
 ///
 /// This matches TypeScript's formatPrivKeyForBabyJub:
 /// `BigInt(deriveSecretScalar(bigInt2Buffer(privKey)))`
 ///
 /// Note: TypeScript's bigInt2Buffer converts to hex string then Buffer,
 /// producing big-endian bytes WITHOUT padding to 32 bytes
+///
+/// The derived scalar is computed without branching on secret bits: pruning
+/// and the right shift work on a fixed 32-byte buffer, and the reduction
+/// modulo the subgroup order uses `EdFr` field arithmetic instead of BigUint
+/// division. Only the final BigUint conversion and the unpadded input
+/// encoding (required for TypeScript compatibility) depend on value length.
 pub fn format_priv_key_for_babyjub(priv_key: &PrivKey) -> BigUint {
     // Convert private key to bytes (big-endian to match TypeScript bigInt2Buffer)
     // bigInt2Buffer: i.toString(16) -> Buffer.from(hex, 'hex') is big-endian
     // Important: DO NOT pad to 32 bytes - bigInt2Buffer doesn't pad
     let priv_key_bytes = priv_key.to_bytes_be();
 
-    // Use eddsa-poseidon's derive_secret_scalar with Blake512
-    // This matches zk-kit's default Blake-1 (Blake512) implementation
-    derive_secret_scalar(&priv_key_bytes, HashingAlgorithm::Blake512)
-        .expect("Failed to derive secret scalar")
+    // Blake512, matching zk-kit's default Blake-1 implementation
+    let mut hasher = Blake::new(512).expect("Failed to create Blake-512 hasher");
+    hasher.update(&priv_key_bytes);
+    let mut hash = [0u8; 64];
+    hasher.finalise(&mut hash);
+
+    // Prune the first 32 bytes (TypeScript pruneBuffer)
+    let mut scalar = [0u8; 32];
+    scalar.copy_from_slice(&hash[..32]);
+    scalar[0] &= 0xf8;
+    scalar[31] &= 0x7f;
+    scalar[31] |= 0x40;
+
+    // Shift the little-endian buffer right by 3 bits
+    let mut shifted = [0u8; 32];
+    for (out, pair) in shifted.iter_mut().zip(scalar.windows(2)) {
+        *out = (pair[0] >> 3) | (pair[1] << 5);
+    }
+    shifted[31] = scalar[31] >> 3;
+
+    // Reduce modulo the subgroup order in the scalar field
+    let s = EdFr::from_le_bytes_mod_order(&shifted);
+    BigUint::from_bytes_le(&s.into_bigint().to_bytes_le())
 }
 
 /// Generate a public key from a private key using eddsa-poseidon
@@ -91,6 +125,17 @@ pub fn gen_pub_key(priv_key: &PrivKey) -> PubKey {
     [x, y]
 }
 
+/// Derive only the x-coordinate of the public key for a private key
+///
+/// Equivalent to `gen_pub_key(priv_key)[0]`, for flows that key a voter by
+/// pubkey x alone.
+pub fn pub_key_x(priv_key: &PrivKey) -> BigUint {
+    let public_point = derive_public_key(&priv_key.to_bytes_be(), HashingAlgorithm::Blake512)
+        .expect("Failed to derive public key");
+
+    BigUint::from_bytes_le(&public_point.x.into_bigint().to_bytes_le())
+}
+
 /// Pack a public key into a single BigUint (lossy compression)
 /// Uses eddsa-poseidon's pack_public_key
 ///
@@ -150,21 +195,53 @@ pub fn unpack_pub_key(packed: &BigUint) -> Result<PubKey> {
 /// const formatedPrivKey = formatPrivKeyForBabyJub(privKey);
 /// const keypair: Keypair = { privKey, pubKey, formatedPrivKey };
 /// ```
+///
+/// A given seed is reduced modulo `SNARK_FIELD_SIZE` before use, so a seed at
+/// or above the field size yields the same keypair as its residue and
+/// `priv_key` always holds the reduced value.
+///
+/// Panics if the given private key is zero modulo the SNARK field; use
+/// `try_gen_keypair` to handle that case.
 pub fn gen_keypair(priv_key: Option<PrivKey>) -> Keypair {
+    try_gen_keypair(priv_key).expect("Failed to generate keypair")
+}
+
+/// Generate a keypair, rejecting a private key that is zero modulo the SNARK field
+pub fn try_gen_keypair(priv_key: Option<PrivKey>) -> Result<Keypair> {
     let priv_key = if let Some(pk) = priv_key {
         &pk % &*SNARK_FIELD_SIZE
     } else {
         &gen_priv_key() % &*SNARK_FIELD_SIZE
     };
 
+    if priv_key.is_zero() {
+        return Err(CryptoError::InvalidKey(
+            "private key must be nonzero".to_string(),
+        ));
+    }
+
     let pub_key = gen_pub_key(&priv_key);
     let formated_priv_key = format_priv_key_for_babyjub(&priv_key);
 
-    Keypair {
+    Ok(Keypair {
         priv_key,
         pub_key,
         formated_priv_key,
-    }
+    })
+}
+
+/// Compare two private keys in constant time
+///
+/// `PrivKey` is a `BigUint` alias, whose `==` returns early on the first differing limb.
+/// Both keys are padded to the same width (at least 32 bytes) before comparing, so only
+/// keys wider than 256 bits leak their length.
+pub fn priv_key_ct_eq(a: &PrivKey, b: &PrivKey) -> Choice {
+    let mut a_bytes = a.to_bytes_le();
+    let mut b_bytes = b.to_bytes_le();
+    let width = a_bytes.len().max(b_bytes.len()).max(32);
+    a_bytes.resize(width, 0);
+    b_bytes.resize(width, 0);
+    a_bytes.as_slice().ct_eq(b_bytes.as_slice())
 }
 
 /// Generate an ECDH shared key from a private key and a public key
@@ -173,6 +250,21 @@ pub fn gen_keypair(priv_key: Option<PrivKey>) -> Keypair {
 /// This matches TypeScript's genEcdhSharedKey:
 /// `mulPointEscalar(pubKey as Point<bigint>, formatPrivKeyForBabyJub(privKey))`
 pub fn gen_ecdh_shared_key(priv_key: &PrivKey, pub_key: &PubKey) -> EcdhSharedKey {
+    let shared_affine = gen_ecdh_shared_point(priv_key, pub_key);
+
+    // Extract coordinates
+    let x_bytes = shared_affine.x.into_bigint().to_bytes_le();
+    let y_bytes = shared_affine.y.into_bigint().to_bytes_le();
+
+    let x = BigUint::from_bytes_le(&x_bytes);
+    let y = BigUint::from_bytes_le(&y_bytes);
+
+    [x, y]
+}
+
+/// Compute the ECDH shared secret as a curve point
+/// `gen_ecdh_shared_key` returns the coordinates of this same point
+pub fn gen_ecdh_shared_point(priv_key: &PrivKey, pub_key: &PubKey) -> EdwardsAffine {
     let formatted = format_priv_key_for_babyjub(priv_key);
 
     // Convert to EdFr (Edwards curve scalar field)
@@ -202,16 +294,7 @@ pub fn gen_ecdh_shared_key(priv_key: &PrivKey, pub_key: &PubKey) -> EcdhSharedKe
     let pub_point_affine = EdwardsAffine::new_unchecked(pub_x_fq, pub_y_fq);
 
     // Use mul_point_escalar from baby_jubjub module
-    let shared_affine = mul_point_escalar(&pub_point_affine, scalar_edfr);
-
-    // Extract coordinates
-    let x_bytes = shared_affine.x.into_bigint().to_bytes_le();
-    let y_bytes = shared_affine.y.into_bigint().to_bytes_le();
-
-    let x = BigUint::from_bytes_le(&x_bytes);
-    let y = BigUint::from_bytes_le(&y_bytes);
-
-    [x, y]
+    mul_point_escalar(&pub_point_affine, scalar_edfr)
 }
 
 /// Sign a message using EdDSA-Poseidon signature scheme
@@ -288,6 +371,23 @@ mod tests {
         assert!(formatted > BigUint::from(0u32));
     }
 
+    #[test]
+    fn test_format_priv_key_matches_derive_secret_scalar() {
+        use eddsa_poseidon::derive_secret_scalar;
+
+        let mut keys = vec![BigUint::from(1u32), BigUint::from(12345u64)];
+        keys.extend((0..32).map(|_| gen_priv_key()));
+
+        for priv_key in keys {
+            let expected =
+                derive_secret_scalar(&priv_key.to_bytes_be(), HashingAlgorithm::Blake512).unwrap();
+            assert_eq!(format_priv_key_for_babyjub(&priv_key), expected);
+        }
+
+        // All-zero keys are rejected by derive_secret_scalar
+        assert!(derive_secret_scalar(&[0], HashingAlgorithm::Blake512).is_err());
+    }
+
     #[test]
     fn test_gen_pub_key() {
         let priv_key = BigUint::from(12345u64);
@@ -313,6 +413,71 @@ mod tests {
         assert_eq!(keypair1.pub_key, keypair2.pub_key);
     }
 
+    #[test]
+    fn test_try_gen_keypair_rejects_zero_seed() {
+        assert_eq!(
+            try_gen_keypair(Some(BigUint::from(0u32))).unwrap_err(),
+            CryptoError::InvalidKey("private key must be nonzero".to_string())
+        );
+        // Seeds are reduced modulo the field first
+        assert!(try_gen_keypair(Some(SNARK_FIELD_SIZE.clone())).is_err());
+
+        let keypair = try_gen_keypair(Some(BigUint::from(12345u64))).unwrap();
+        assert_eq!(
+            keypair.pub_key,
+            gen_keypair(Some(BigUint::from(12345u64))).pub_key
+        );
+    }
+
+    #[test]
+    fn test_priv_key_ct_eq_agrees_with_eq() {
+        let a = gen_priv_key();
+        let b = gen_priv_key();
+        let cases = [
+            (a.clone(), a.clone()),
+            (a.clone(), b.clone()),
+            (BigUint::from(0u32), BigUint::from(0u32)),
+            (BigUint::from(1u32), BigUint::from(256u32)),
+            (BigUint::from(1u32), BigUint::from(1u32) << 300),
+        ];
+        for (x, y) in cases {
+            assert_eq!(bool::from(priv_key_ct_eq(&x, &y)), x == y);
+            assert_eq!(bool::from(priv_key_ct_eq(&y, &x)), x == y);
+        }
+    }
+
+    #[test]
+    fn test_gen_keypair_reduces_large_seeds() {
+        let field_size = &*SNARK_FIELD_SIZE;
+
+        // Zero and the field size share the zero residue, so both are rejected
+        let zero_err = try_gen_keypair(Some(BigUint::from(0u32))).unwrap_err();
+        let field_err = try_gen_keypair(Some(field_size.clone())).unwrap_err();
+        assert_eq!(zero_err, field_err);
+
+        // Seeds past the field size match their residue and are reproducible
+        let one = gen_keypair(Some(BigUint::from(1u32)));
+        let wrapped = gen_keypair(Some(field_size + 1u32));
+        assert_eq!(wrapped.priv_key, BigUint::from(1u32));
+        assert_eq!(wrapped.pub_key, one.pub_key);
+        assert_eq!(wrapped.formated_priv_key, one.formated_priv_key);
+
+        let max_seed = (BigUint::from(1u32) << 256) - 1u32;
+        let large = gen_keypair(Some(max_seed.clone()));
+        assert_eq!(large.priv_key, &max_seed % field_size);
+        assert!(large.priv_key < *field_size);
+        assert_eq!(large.pub_key, gen_keypair(Some(max_seed)).pub_key);
+        assert_ne!(large.pub_key, one.pub_key);
+    }
+
+    #[test]
+    fn test_pub_key_x_matches_gen_pub_key() {
+        for seed in [1u64, 12345, u64::MAX] {
+            let priv_key = BigUint::from(seed);
+            assert_eq!(pub_key_x(&priv_key), gen_pub_key(&priv_key)[0]);
+        }
+    }
+
     #[test]
     fn test_pack_unpack_pub_key() {
         let keypair = gen_keypair(Some(BigUint::from(12345u64)));
@@ -345,6 +510,26 @@ mod tests {
         assert_eq!(shared1, shared2);
     }
 
+    #[test]
+    fn test_ecdh_shared_point() {
+        let keypair1 = gen_keypair(Some(BigUint::from(12345u64)));
+        let keypair2 = gen_keypair(Some(BigUint::from(67890u64)));
+
+        let point1 = gen_ecdh_shared_point(&keypair1.priv_key, &keypair2.pub_key);
+        let point2 = gen_ecdh_shared_point(&keypair2.priv_key, &keypair1.pub_key);
+        assert_eq!(point1, point2);
+
+        let shared = gen_ecdh_shared_key(&keypair1.priv_key, &keypair2.pub_key);
+        assert_eq!(
+            BigUint::from_bytes_le(&point1.x.into_bigint().to_bytes_le()),
+            shared[0]
+        );
+        assert_eq!(
+            BigUint::from_bytes_le(&point1.y.into_bigint().to_bytes_le()),
+            shared[1]
+        );
+    }
+
     #[test]
     fn test_ecdh_deterministic() {
         let keypair1 = gen_keypair(Some(BigUint::from(12345u64)));
@@ -9,7 +9,12 @@
 //! - ECDH shared key derivation
 //! - Message packing/unpacking
 //! - Ciphertext rerandomization
+//! - Poseidon message encryption
 //! - N-ary Merkle trees
+//! - Deactivate tree inclusion proofs for AMACI
+//! - AMACI state leaf hashes
+//! - The message hash chain the contracts store
+//! - Public input layouts for the MACI circuits
 //!
 //! ## Example
 //!
@@ -30,37 +35,60 @@
 //! ```
 
 // Module declarations
+pub mod cipher;
 pub mod constants;
+pub mod deactivate;
 pub mod error;
 pub mod hashing;
+pub mod inputs;
 pub mod keypair;
 pub mod keys;
+pub mod message_chain;
 pub mod pack;
 pub mod rerandomize;
+pub mod state_leaf;
 pub mod tree;
 pub mod utils;
 
 // Re-export commonly used types and functions from baby-jubjub crate
 pub use baby_jubjub::{
     add_point, base8, gen_random_babyjub_value, in_curve, mul_point_escalar, pack_point,
-    unpack_point, BabyJubjubConfig, EdwardsAffine, EdwardsProjective,
+    pack_points, unpack_point, unpack_points, BabyJubjubConfig, EdwardsAffine, EdwardsProjective,
+};
+pub use cipher::{
+    decrypt_message, encrypt_command, poseidon_decrypt, poseidon_encrypt, MessageData,
+    COMMAND_LENGTH, MESSAGE_DATA_LENGTH,
+};
+pub use constants::{
+    compute_pad_key_hash, derive_nothing_up_my_sleeve, NOTHING_UP_MY_SLEEVE,
+    NOTHING_UP_MY_SLEEVE_PREIMAGE, PAD_KEY_HASH, PAD_PUB_KEY, SNARK_FIELD_SIZE, UINT32, UINT96,
+};
+pub use deactivate::{
+    deactivate_tree, gen_deactivate_proof, DeactivateProof, DEACTIVATE_TREE_DEGREE,
 };
-pub use constants::{NOTHING_UP_MY_SLEEVE, PAD_KEY_HASH, SNARK_FIELD_SIZE, UINT32, UINT96};
 pub use hashing::{
     compute_input_hash, hash10, hash12, hash2, hash3, hash4, hash5, hash_lean_imt, hash_left_right,
     hash_n, hash_one, poseidon, poseidon_t3, poseidon_t4, poseidon_t5, poseidon_t6, sha256_hash,
 };
+pub use inputs::{build_process_inputs, pack_process_vals, ProcessInputs, PROCESS_INPUTS_LENGTH};
 pub use keys::{
-    format_priv_key_for_babyjub, gen_ecdh_shared_key, gen_keypair, gen_priv_key, gen_pub_key,
-    gen_random_salt, pack_pub_key, unpack_pub_key, EcdhSharedKey, Keypair, PrivKey, PubKey,
+    format_priv_key_for_babyjub, gen_ecdh_shared_key, gen_ecdh_shared_point, gen_keypair,
+    gen_priv_key, gen_pub_key, gen_random_salt, pack_pub_key, priv_key_ct_eq, pub_key_x,
+    try_gen_keypair, unpack_pub_key, EcdhSharedKey, Keypair, PrivKey, PubKey,
 };
-pub use pack::{pack_element, unpack_element, PackedElement};
+pub use message_chain::{compute_message_hash_chain, hash_message_and_enc_pub_key};
+pub use pack::{pack_command, pack_element, unpack_element, PackedElement};
 pub use rerandomize::{
-    decode_message, decrypt, encode_to_message, encrypt, encrypt_odevity, rerandomize_ciphertext,
+    decode_from_point, decode_message, decrypt, elgamal_decrypt, elgamal_encrypt,
+    encode_to_message, encode_to_point, encrypt, encrypt_odevity, rerandomize_ciphertext,
     Ciphertext, Message,
 };
+pub use state_leaf::{hash_new_key_state_leaf, hash_signup_state_leaf, state_leaf_commitment};
 pub use tree::{biguint_to_node, node_to_biguint, Tree};
-pub use utils::{bigint_to_bytes, bigint_to_hex, bytes_to_bigint, hex_to_bigint};
+pub use utils::{
+    bigint_to_bytes, bigint_to_bytes_32, bigint_to_bytes_32_le, bigint_to_hex, bytes_to_bigint,
+    hex_to_bigint,
+};
 
 // Re-export error types
 pub use error::{CryptoError, Result};
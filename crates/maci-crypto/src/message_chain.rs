@@ -0,0 +1,128 @@
+//! The message hash chain the AMACI contract builds in `publish_message`
+//!
+//! Each message extends the chain as
+//! `hash5(hash5(data[0..5]), hash5(data[5..10]), enc_pub_key.x, enc_pub_key.y, prev)`,
+//! starting from zero. The contract stores the hash after `i` messages under
+//! `MSG_HASHES[i]`, which `GetMsgHash { index: i }` returns.
+
+use crate::cipher::MessageData;
+use crate::hashing::poseidon;
+use crate::keys::PubKey;
+use num_bigint::BigUint;
+
+/// One link of the chain (the contract's `hash_message_and_enc_pub_key`)
+pub fn hash_message_and_enc_pub_key(
+    message: &MessageData,
+    enc_pub_key: &PubKey,
+    prev_hash: &BigUint,
+) -> BigUint {
+    let m_hash = poseidon(&message[..5]);
+    let n_hash = poseidon(&message[5..]);
+    poseidon(&[
+        m_hash,
+        n_hash,
+        enc_pub_key[0].clone(),
+        enc_pub_key[1].clone(),
+        prev_hash.clone(),
+    ])
+}
+
+/// Running chain hashes for `messages` published from an empty chain
+///
+/// Entry `i` is the hash after `i + 1` messages, i.e. `GetMsgHash { index: i + 1 }`.
+pub fn compute_message_hash_chain(messages: &[(MessageData, PubKey)]) -> Vec<BigUint> {
+    let mut prev_hash = BigUint::from(0u32);
+    messages
+        .iter()
+        .map(|(message, enc_pub_key)| {
+            prev_hash = hash_message_and_enc_pub_key(message, enc_pub_key, &prev_hash);
+            prev_hash.clone()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::Uint256;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> BigUint {
+        BigUint::parse_bytes(value.as_bytes(), 10).unwrap()
+    }
+
+    fn to_uint256(value: &BigUint) -> Uint256 {
+        Uint256::from_str(&value.to_string()).unwrap()
+    }
+
+    // The first two publishMessage entries of contracts/amaci/src/test/amaci_test/logs.json
+    fn fixture_messages() -> Vec<(MessageData, PubKey)> {
+        [
+            (
+                [
+                    "4108506414071707876077764872754416884084069067830124606394622137225951228881",
+                    "11758593284611531953761572449126297529615339382369007904876347611087867354585",
+                    "10943560038229027475384844996338120310036530173920221702981340747683946223290",
+                    "15144582931592231724667003491769446928150331804589629284514214786718880838051",
+                    "1473251834473016546276351987473339587999863427209607406885769932469535558299",
+                    "5165296687661288641362621457981500178798684849494574386450681097714726706752",
+                    "13236460988625002930600200978449922471805226230677743380682867921920355266167",
+                    "3060913565465157592283414139081365462032581528956477294583667076564041762274",
+                    "1955500879023896598949479239210921667529214507887324681557978983873072450436",
+                    "13212776588657180654528185950027472367308039232849008029541156031671285067398",
+                ],
+                [
+                    "9159330977504123435105802117017613576238693018072399699796437975868150969255",
+                    "14679692412897032802303712326340821606122389084116501273639958299755444547974",
+                ],
+            ),
+            (
+                [
+                    "13850266323177485269276787242814919132533331431268363863397482142418925462033",
+                    "2906422079885434851203252501337881452052779893347463248915370487902467809621",
+                    "7646936645162456815847523562177925534185206184531941666352693606092464430468",
+                    "7550039366721693178659583177886125574184765422908624170496753732755349454133",
+                    "12832321729723491510612717720276685972689540442164132173319302262596528942729",
+                    "7716581001704121079638254881475266443698421250050785848031387401170763646361",
+                    "12064642044108665525947893047020322953012115442632704286508067651912034132028",
+                    "15775713376603728849064505608455505736526089961571617395051811633060173565568",
+                    "17563760991157186551571608327825078772459583060503937437401085038611447854551",
+                    "2386977777156031543562981082837599295153592596476328729887490849837871888602",
+                ],
+                [
+                    "17070855708645606066161375991312395920063787998405891631884602286435945949540",
+                    "4316452228410096399774340359572816039382020188262894024298916241091268418765",
+                ],
+            ),
+        ]
+        .into_iter()
+        .map(|(message, enc_pub_key)| (message.map(dec), enc_pub_key.map(dec)))
+        .collect()
+    }
+
+    #[test]
+    fn test_message_hash_chain_matches_contract() {
+        let messages = fixture_messages();
+        let chain = compute_message_hash_chain(&messages);
+        assert_eq!(chain.len(), messages.len());
+
+        // hash_message_and_enc_pub_key in contracts/amaci/src/contract.rs, chained from zero
+        let mut expected = Uint256::zero();
+        for ((message, enc_pub_key), hash) in messages.iter().zip(&chain) {
+            let data = message.clone().map(|v| to_uint256(&v));
+            expected = maci_utils::hash5([
+                maci_utils::hash5([data[0], data[1], data[2], data[3], data[4]]),
+                maci_utils::hash5([data[5], data[6], data[7], data[8], data[9]]),
+                to_uint256(&enc_pub_key[0]),
+                to_uint256(&enc_pub_key[1]),
+                expected,
+            ]);
+            assert_eq!(to_uint256(hash), expected);
+        }
+    }
+
+    #[test]
+    fn test_empty_message_hash_chain() {
+        assert!(compute_message_hash_chain(&[]).is_empty());
+    }
+}
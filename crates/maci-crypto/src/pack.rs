@@ -1,4 +1,5 @@
 use crate::constants::{UINT32, UINT96};
+use crate::keys::PubKey;
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +29,28 @@ pub fn pack_element(
     nonce + (state_idx << 32) + (vo_idx << 64) + (new_votes << 96) + (poll_id << 192)
 }
 
+/// The unsigned part of a MACI command: `[packed, new_pub_key.x, new_pub_key.y, salt]`
+///
+/// This is `decrypted[0..4]` in the circuit's `MessageToCommand`. The voter signs
+/// `poseidon([packed, new_pub_key.x, new_pub_key.y])` (the salt is not signed) and appends
+/// `R8.x, R8.y, S` to get the 7-element command for `encrypt_command`.
+pub fn pack_command(
+    state_idx: &BigUint,
+    vote_option: &BigUint,
+    new_vote_weight: &BigUint,
+    nonce: &BigUint,
+    poll_id: &BigUint,
+    new_pub_key: &PubKey,
+    salt: &BigUint,
+) -> [BigUint; 4] {
+    [
+        pack_element(nonce, state_idx, vote_option, new_vote_weight, poll_id),
+        new_pub_key[0].clone(),
+        new_pub_key[1].clone(),
+        salt.clone(),
+    ]
+}
+
 /// Unpack a BigUint back into its component fields
 pub fn unpack_element(packed: &BigUint) -> PackedElement {
     let nonce = packed % &*UINT32;
@@ -103,6 +126,43 @@ mod tests {
         assert_eq!(unpacked.poll_id, poll_id);
     }
 
+    #[test]
+    fn test_pack_command() {
+        let new_pub_key = [BigUint::from(11u32), BigUint::from(22u32)];
+        let salt = BigUint::from(33u32);
+
+        let command = pack_command(
+            &BigUint::from(5u32),
+            &BigUint::from(10u32),
+            &BigUint::from(100u32),
+            &BigUint::from(1u32),
+            &BigUint::from(0u32),
+            &new_pub_key,
+            &salt,
+        );
+
+        // pack_basic in e2e/crypto-test/test-vectors-rust.json
+        assert_eq!(
+            command[0],
+            BigUint::parse_bytes(b"640000000a0000000500000001", 16).unwrap()
+        );
+        assert_eq!(
+            command[1..],
+            [new_pub_key[0].clone(), new_pub_key[1].clone(), salt]
+        );
+
+        let command = pack_command(
+            &BigUint::from(5u32),
+            &BigUint::from(10u32),
+            &BigUint::from(100u32),
+            &BigUint::from(1u32),
+            &BigUint::from(7u32),
+            &new_pub_key,
+            &BigUint::from(0u32),
+        );
+        assert_eq!(unpack_element(&command[0]).poll_id, BigUint::from(7u32));
+    }
+
     #[test]
     fn test_pack_max_values() {
         // Max values for each field based on bit sizes
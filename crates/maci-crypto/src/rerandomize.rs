@@ -1,7 +1,8 @@
-use crate::error::Result;
-use crate::keys::{gen_keypair, PubKey};
+use crate::constants::SNARK_FIELD_SIZE;
+use crate::error::{CryptoError, Result};
+use crate::keys::{format_priv_key_for_babyjub, gen_keypair, PrivKey, PubKey};
 use ark_ec::CurveGroup;
-use ark_ff::{BigInteger, PrimeField};
+use ark_ff::{BigInteger, Field, PrimeField};
 use baby_jubjub::{gen_random_babyjub_value, EdFr, EdwardsAffine, EdwardsProjective, Fq};
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
@@ -14,13 +15,60 @@ pub struct Message {
 }
 
 /// A ciphertext consisting of two curve points and an x-increment
+///
+/// On the wire, `c1` and `c2` are serialized as packed points (32-byte hex strings)
+/// so ciphertexts stay compact when transported between mixnet nodes.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Ciphertext {
+    #[serde(with = "packed_point")]
     pub c1: [BigUint; 2],
+    #[serde(with = "packed_point")]
     pub c2: [BigUint; 2],
     pub x_increment: BigUint,
 }
 
+/// Serde helpers encoding a curve point as its 32-byte packed form in hex
+mod packed_point {
+    use super::biguint_to_edwards_point;
+    use ark_ec::CurveGroup;
+    use ark_ff::{BigInteger, PrimeField};
+    use baby_jubjub::{pack_point, unpack_point};
+    use num_bigint::BigUint;
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(coords: &[BigUint; 2], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let point = biguint_to_edwards_point(coords)
+            .map_err(serde::ser::Error::custom)?
+            .into_affine();
+
+        let mut bytes = pack_point(&point).to_bytes_le();
+        bytes.resize(32, 0);
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[BigUint; 2], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex_str = String::deserialize(deserializer)?;
+        let bytes = hex::decode(&hex_str).map_err(D::Error::custom)?;
+        if bytes.len() != 32 {
+            return Err(D::Error::custom(format!(
+                "packed point must be 32 bytes, got {}",
+                bytes.len()
+            )));
+        }
+
+        let point = unpack_point(&BigUint::from_bytes_le(&bytes)).map_err(D::Error::custom)?;
+        let x = BigUint::from_bytes_le(&point.x.into_bigint().to_bytes_le());
+        let y = BigUint::from_bytes_le(&point.y.into_bigint().to_bytes_le());
+        Ok([x, y])
+    }
+}
+
 /// Convert BigUint coordinates to an Edwards curve point
 fn biguint_to_edwards_point(coords: &[BigUint; 2]) -> Result<EdwardsProjective> {
     let x_bytes = coords[0].to_bytes_le();
@@ -322,6 +370,138 @@ pub fn decrypt(formatted_priv_key: &BigUint, ciphertext: &Ciphertext) -> Result<
     Ok(decode_message(&message))
 }
 
+/// Convert a BigUint to a Baby Jubjub scalar (reduced modulo the subgroup order)
+fn biguint_to_scalar(value: &BigUint) -> EdFr {
+    let bytes = value.to_bytes_le();
+    let mut padded = vec![0u8; 32];
+    let len = bytes.len().min(32);
+    padded[..len].copy_from_slice(&bytes[..len]);
+    EdFr::from_le_bytes_mod_order(&padded)
+}
+
+/// Encrypts a curve point with plain ElGamal
+///
+/// Algorithm:
+/// - c1 = Base8 * r
+/// - c2 = plaintextPoint + pubKey * r
+///
+/// Unlike `encrypt`, no message encoding is applied, so the returned
+/// ciphertext always has a zero x-increment.
+///
+/// # Arguments
+/// * `pub_key` - The public key to encrypt with
+/// * `plaintext_point` - The curve point to encrypt
+/// * `r` - The ephemeral random value
+///
+/// # Returns
+/// A Ciphertext containing c1 and c2
+pub fn elgamal_encrypt(
+    pub_key: &PubKey,
+    plaintext_point: &EdwardsAffine,
+    r: &BigUint,
+) -> Ciphertext {
+    let scalar = biguint_to_scalar(r);
+
+    let c1_point = get_base8() * scalar;
+
+    let pub_key_point =
+        biguint_to_edwards_point(pub_key).expect("BigUint coordinates always map to a point");
+    let c2_point = EdwardsProjective::from(*plaintext_point) + pub_key_point * scalar;
+
+    Ciphertext {
+        c1: edwards_point_to_biguint(&c1_point),
+        c2: edwards_point_to_biguint(&c2_point),
+        x_increment: BigUint::from(0u32),
+    }
+}
+
+/// Decrypts a plain ElGamal ciphertext back to the encrypted curve point
+///
+/// Computes `c2 - c1 * formatPrivKeyForBabyJub(privKey)`. The x-increment is
+/// ignored, so this also recovers the message point of ciphertexts produced
+/// by `encrypt` and `encrypt_odevity`.
+///
+/// # Arguments
+/// * `priv_key` - The raw private key matching the encryption public key
+/// * `ct` - The ciphertext to decrypt
+///
+/// # Returns
+/// The decrypted curve point
+pub fn elgamal_decrypt(priv_key: &PrivKey, ct: &Ciphertext) -> EdwardsAffine {
+    let scalar = biguint_to_scalar(&format_priv_key_for_babyjub(priv_key));
+
+    let c1_point =
+        biguint_to_edwards_point(&ct.c1).expect("BigUint coordinates always map to a point");
+    let c2_point =
+        biguint_to_edwards_point(&ct.c2).expect("BigUint coordinates always map to a point");
+
+    (c2_point - c1_point * scalar).into_affine()
+}
+
+/// Number of x-coordinate candidates tried per message by `encode_to_point`
+const POINT_ENCODING_WINDOW: u32 = 256;
+
+/// Maps a message to a Baby Jubjub curve point (try-and-increment embedding)
+///
+/// Candidate x-coordinates `m * 256 + i` are tried for `i` in `0..256` until
+/// `y^2 = (1 - a*x^2) / (1 - d*x^2)` has a square root. Each candidate succeeds
+/// with probability ~1/2, so failure is negligible.
+///
+/// The largest encodable message is `floor((p - 1) / 256) - 1` where `p` is
+/// the SNARK field size, i.e. roughly 2^246. The resulting point is on the
+/// curve but not necessarily in the prime-order subgroup.
+///
+/// # Arguments
+/// * `m` - The message to encode
+///
+/// # Returns
+/// A curve point whose x-coordinate encodes `m`
+pub fn encode_to_point(m: &BigUint) -> Result<EdwardsAffine> {
+    let window = BigUint::from(POINT_ENCODING_WINDOW);
+    let base = m * &window;
+    if &base + &window > *SNARK_FIELD_SIZE {
+        return Err(CryptoError::InvalidFieldElement(format!(
+            "message {} is too large to encode as a point",
+            m
+        )));
+    }
+
+    let a = Fq::from(168700u64);
+    let d = Fq::from(168696u64);
+
+    for i in 0..POINT_ENCODING_WINDOW {
+        let x_value = &base + BigUint::from(i);
+        let x = Fq::from_le_bytes_mod_order(&x_value.to_bytes_le());
+        let x2 = x * x;
+
+        let denominator = Fq::ONE - d * x2;
+        let Some(denominator_inv) = denominator.inverse() else {
+            continue;
+        };
+
+        if let Some(y) = ((Fq::ONE - a * x2) * denominator_inv).sqrt() {
+            return Ok(EdwardsAffine::new_unchecked(x, y));
+        }
+    }
+
+    Err(CryptoError::invalid_point(format!(
+        "no curve point found for message {}",
+        m
+    )))
+}
+
+/// Recovers the message embedded by `encode_to_point`
+///
+/// # Arguments
+/// * `point` - A point produced by `encode_to_point`
+///
+/// # Returns
+/// The encoded message
+pub fn decode_from_point(point: &EdwardsAffine) -> BigUint {
+    let x = BigUint::from_bytes_le(&point.x.into_bigint().to_bytes_le());
+    x / BigUint::from(POINT_ENCODING_WINDOW)
+}
+
 /// Rerandomize a ciphertext
 ///
 /// Given a ciphertext (c1, c2) and a public key, this function produces
@@ -691,6 +871,117 @@ mod tests {
         assert!(rerandomized1.c1 != rerandomized2.c1 || rerandomized1.c2 != rerandomized2.c2);
     }
 
+    #[test]
+    fn test_elgamal_encrypt_decrypt() {
+        let keypair = gen_keypair(Some(BigUint::from(12345u64)));
+        let plaintext_point =
+            baby_jubjub::mul_point_escalar(&baby_jubjub::base8(), EdFr::from(42u64));
+
+        let ciphertext =
+            elgamal_encrypt(&keypair.pub_key, &plaintext_point, &BigUint::from(54321u64));
+        assert_eq!(ciphertext.x_increment, BigUint::from(0u32));
+
+        let decrypted = elgamal_decrypt(&keypair.priv_key, &ciphertext);
+        assert_eq!(decrypted, plaintext_point);
+
+        let rerandomized =
+            rerandomize_ciphertext(&keypair.pub_key, &ciphertext, Some(BigUint::from(22222u64)))
+                .expect("Rerandomization failed");
+        assert_ne!(rerandomized.c1, ciphertext.c1);
+        assert_eq!(
+            elgamal_decrypt(&keypair.priv_key, &rerandomized),
+            plaintext_point
+        );
+    }
+
+    #[test]
+    fn test_elgamal_decrypt_recovers_message_point() {
+        let keypair = gen_keypair(Some(BigUint::from(12345u64)));
+
+        let ciphertext = encrypt(
+            &BigUint::from(999u32),
+            &keypair.pub_key,
+            Some(BigUint::from(54321u64)),
+        )
+        .expect("Encryption failed");
+        let point = elgamal_decrypt(&keypair.priv_key, &ciphertext);
+        let coords = edwards_point_to_biguint(&EdwardsProjective::from(point));
+
+        assert!(point.is_on_curve());
+        assert_eq!(&coords[0] - &ciphertext.x_increment, BigUint::from(999u32));
+    }
+
+    #[test]
+    fn test_encode_decode_point_roundtrip() {
+        let messages = [0u64, 1, 2, 42, 255, 256, 12345, u64::MAX];
+
+        for m in messages {
+            let m = BigUint::from(m);
+            let point = encode_to_point(&m).expect("Encoding failed");
+            assert!(point.is_on_curve());
+            assert_eq!(decode_from_point(&point), m);
+        }
+    }
+
+    #[test]
+    fn test_encode_to_point_max_message() {
+        let window = BigUint::from(POINT_ENCODING_WINDOW);
+        let max = (&*SNARK_FIELD_SIZE - 1u32) / &window - 1u32;
+
+        let point = encode_to_point(&max).expect("Encoding failed");
+        assert_eq!(decode_from_point(&point), max);
+
+        assert!(encode_to_point(&(&*SNARK_FIELD_SIZE / &window)).is_err());
+    }
+
+    #[test]
+    fn test_encode_to_point_elgamal_roundtrip() {
+        let keypair = gen_keypair(Some(BigUint::from(12345u64)));
+        let m = BigUint::from(31337u32);
+
+        let point = encode_to_point(&m).expect("Encoding failed");
+        let ciphertext = elgamal_encrypt(&keypair.pub_key, &point, &BigUint::from(54321u64));
+        let decrypted = elgamal_decrypt(&keypair.priv_key, &ciphertext);
+
+        assert_eq!(decode_from_point(&decrypted), m);
+    }
+
+    #[test]
+    fn test_ciphertext_serde_roundtrip() {
+        let keypair = gen_keypair(Some(BigUint::from(12345u64)));
+        let plaintext = BigUint::from(777u32);
+
+        let ciphertext = encrypt(&plaintext, &keypair.pub_key, Some(BigUint::from(33333u64)))
+            .expect("Encryption failed");
+        let rerandomized =
+            rerandomize_ciphertext(&keypair.pub_key, &ciphertext, Some(BigUint::from(44444u64)))
+                .expect("Rerandomization failed");
+
+        for ct in [ciphertext, rerandomized] {
+            let json = serde_json::to_value(&ct).expect("Serialization failed");
+            assert_eq!(json["c1"].as_str().unwrap().len(), 64);
+            assert_eq!(json["c2"].as_str().unwrap().len(), 64);
+
+            let recovered: Ciphertext =
+                serde_json::from_value(json).expect("Deserialization failed");
+            assert_eq!(recovered, ct);
+
+            let decrypted =
+                decrypt(&keypair.formated_priv_key, &recovered).expect("Decryption failed");
+            assert_eq!(decrypted, plaintext);
+        }
+    }
+
+    #[test]
+    fn test_ciphertext_deserialize_rejects_short_point() {
+        let json = serde_json::json!({
+            "c1": "00",
+            "c2": "00",
+            "x_increment": [0],
+        });
+        assert!(serde_json::from_value::<Ciphertext>(json).is_err());
+    }
+
     #[test]
     fn test_biguint_edwards_conversion() {
         let keypair = gen_keypair(Some(BigUint::from(12345u64)));
@@ -0,0 +1,184 @@
+//! State leaf hashes as the AMACI contract enqueues them
+//!
+//! AMACI leaves commit to the voter's key and balance plus a deactivate
+//! ciphertext: `hash2(hash5(x, y, balance, vote_option_root, nonce), hash5(c))`.
+//! On sign-up the vote option root and nonce are zero, and `c` is either empty
+//! (a regular sign-up) or the rerandomized ciphertext `d` of an add-new-key.
+//!
+//! The circuits fix these layouts, so there is no explicit domain tag: the
+//! plain and AMACI leaves are told apart by their Poseidon widths instead.
+
+use crate::hashing::poseidon;
+use crate::keys::PubKey;
+use num_bigint::BigUint;
+
+/// Plain MACI state leaf, as the circuits recompute it for a voter:
+/// `hash5(x, y, voice_credit, vote_option_root, nonce)` (the maci contract's
+/// `StateLeaf::hash_state_leaf`)
+pub fn state_leaf_commitment(
+    pub_key: &PubKey,
+    voice_credit: &BigUint,
+    vote_option_root: &BigUint,
+    nonce: &BigUint,
+) -> BigUint {
+    poseidon(&[
+        pub_key[0].clone(),
+        pub_key[1].clone(),
+        voice_credit.clone(),
+        vote_option_root.clone(),
+        nonce.clone(),
+    ])
+}
+
+/// Leaf for a regular sign-up (the contract's `hash_decativate_state_leaf`)
+pub fn hash_signup_state_leaf(pub_key: &PubKey, balance: &BigUint) -> BigUint {
+    let zero = BigUint::from(0u32);
+    hash_new_key_state_leaf(
+        pub_key,
+        balance,
+        &[zero.clone(), zero.clone(), zero.clone(), zero],
+    )
+}
+
+/// Leaf for AddNewKey, carrying the rerandomized deactivate ciphertext `d`
+pub fn hash_new_key_state_leaf(pub_key: &PubKey, balance: &BigUint, d: &[BigUint; 4]) -> BigUint {
+    let zero = BigUint::from(0u32);
+    let leaf = state_leaf_commitment(pub_key, balance, &zero, &zero);
+    let ciphertext = poseidon(&[d[0].clone(), d[1].clone(), d[2].clone(), d[3].clone(), zero]);
+    poseidon(&[leaf, ciphertext])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::Uint256;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> BigUint {
+        BigUint::parse_bytes(value.as_bytes(), 10).unwrap()
+    }
+
+    fn to_uint256(value: &BigUint) -> Uint256 {
+        Uint256::from_str(&value.to_string()).unwrap()
+    }
+
+    // setStateLeaf entry 0 of contracts/amaci/src/test/amaci_test/logs.json
+    fn fixture_pub_key() -> PubKey {
+        [
+            dec("17399497775960102380565502463631688556400670056228730095262963787053279532078"),
+            dec("18877551448216649826981805566855898133402281419471365959781518735588655366005"),
+        ]
+    }
+
+    #[test]
+    fn test_signup_leaf_matches_contract() {
+        let pub_key = fixture_pub_key();
+        let balance = BigUint::from(100u32);
+
+        // StateLeaf::hash_decativate_state_leaf in contracts/amaci/src/state.rs
+        let x = to_uint256(&pub_key[0]);
+        let y = to_uint256(&pub_key[1]);
+        let zero = Uint256::zero();
+        let expected = maci_utils::hash2([
+            maci_utils::hash5([x, y, Uint256::from_u128(100), zero, zero]),
+            maci_utils::uint256_from_hex_string(
+                "2066be41bebe6caf7e079360abe14fbf9118c62eabc42e2fe75e342b160a95bc",
+            ),
+        ]);
+
+        assert_eq!(
+            to_uint256(&hash_signup_state_leaf(&pub_key, &balance)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_new_key_leaf_matches_contract() {
+        let pub_key = fixture_pub_key();
+        let balance = BigUint::from(100u32);
+        let d = [
+            BigUint::from(1u32),
+            BigUint::from(2u32),
+            BigUint::from(3u32),
+            BigUint::from(4u32),
+        ];
+
+        // StateLeaf::hash_new_key_state_leaf in contracts/amaci/src/state.rs
+        let x = to_uint256(&pub_key[0]);
+        let y = to_uint256(&pub_key[1]);
+        let d_u = d.clone().map(|v| to_uint256(&v));
+        let zero = Uint256::zero();
+        let expected = maci_utils::hash2([
+            maci_utils::hash5([x, y, Uint256::from_u128(100), zero, zero]),
+            maci_utils::hash5([d_u[0], d_u[1], d_u[2], d_u[3], zero]),
+        ]);
+
+        assert_eq!(
+            to_uint256(&hash_new_key_state_leaf(&pub_key, &balance, &d)),
+            expected
+        );
+        assert_ne!(
+            hash_new_key_state_leaf(&pub_key, &balance, &d),
+            hash_signup_state_leaf(&pub_key, &balance)
+        );
+    }
+
+    #[test]
+    fn test_state_leaf_matches_contract() {
+        let pub_key = fixture_pub_key();
+        let root = BigUint::from(7u32);
+        let nonce = BigUint::from(3u32);
+
+        // StateLeaf::hash_state_leaf
+        let expected = maci_utils::hash5([
+            to_uint256(&pub_key[0]),
+            to_uint256(&pub_key[1]),
+            Uint256::from_u128(90),
+            to_uint256(&root),
+            to_uint256(&nonce),
+        ]);
+
+        assert_eq!(
+            to_uint256(&state_leaf_commitment(
+                &pub_key,
+                &BigUint::from(90u32),
+                &root,
+                &nonce
+            )),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_state_leaf_commitment() {
+        // Poseidon-5 of five zeros, as circomlib computes it (the contracts'
+        // empty deactivate ciphertext hash)
+        let zero = BigUint::from(0u32);
+        assert_eq!(
+            state_leaf_commitment(&[zero.clone(), zero.clone()], &zero, &zero, &zero),
+            BigUint::parse_bytes(
+                b"2066be41bebe6caf7e079360abe14fbf9118c62eabc42e2fe75e342b160a95bc",
+                16
+            )
+            .unwrap()
+        );
+
+        // setStateLeaf entry 0 of contracts/maci/src/test/maci_test/logs.json
+        let pub_key = [
+            dec("8446677751716569713622015905729882243875224951572887602730835165068040887285"),
+            dec("12484654491029393893324568717198080229359788322121893494118068510674758553628"),
+        ];
+        let balance = BigUint::from(100u32);
+        let expected = maci_utils::hash5([
+            to_uint256(&pub_key[0]),
+            to_uint256(&pub_key[1]),
+            Uint256::from_u128(100),
+            Uint256::zero(),
+            Uint256::zero(),
+        ]);
+        assert_eq!(
+            to_uint256(&state_leaf_commitment(&pub_key, &balance, &zero, &zero)),
+            expected
+        );
+    }
+}
@@ -3,6 +3,7 @@ use crate::hashing::poseidon;
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use zk_kit_imt::imt::{IMTNode, IMT};
 
 // Use full path for Result to avoid conflict with serde::Result
@@ -50,6 +51,8 @@ pub struct Tree {
     zero: IMTNode,
     /// Cached root value
     cached_root: RefCell<IMTNode>,
+    /// Initialized leaves, kept in sync with the IMT
+    leaves: Vec<IMTNode>,
     /// Internal zkkit IMT instance (non-serializable)
     imt: RefCell<Option<IMT>>,
 }
@@ -128,10 +131,105 @@ impl Tree {
             nodes_count,
             zero,
             cached_root,
+            leaves: vec![],
             imt: RefCell::new(imt),
         }
     }
 
+    /// Create a tree already holding `leaves`, which must fit in its `degree^depth` slots
+    pub fn with_leaves(
+        degree: usize,
+        depth: usize,
+        zero: IMTNode,
+        leaves: &[IMTNode],
+    ) -> CryptoResult<Self> {
+        let mut tree = Tree::new(degree, depth, zero);
+        if leaves.len() > tree.leaves_count {
+            return Err(CryptoError::TooManyLeaves {
+                count: leaves.len(),
+                capacity: tree.leaves_count,
+            });
+        }
+        tree.init_leaves(leaves);
+        Ok(tree)
+    }
+
+    /// Every node that differs from its level's zero hash, as `(index, value)` in the flat
+    /// layout the contracts store: the root at 0 and the children of `i` at
+    /// `i * degree + 1..=i * degree + degree`, so leaves start at `leaves_idx_0`
+    pub fn to_nodes(&self) -> Vec<(usize, IMTNode)> {
+        let zero_hashes = Tree::compute_zero_hashes(self.degree, self.depth, self.zero.clone());
+        let mut nodes = vec![];
+        let mut level = self.leaves.clone();
+
+        for height in 0..=self.depth {
+            let level_idx_0 =
+                (self.degree.pow((self.depth - height) as u32) - 1) / (self.degree - 1);
+            nodes.extend(
+                level
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, node)| **node != zero_hashes[height])
+                    .map(|(i, node)| (level_idx_0 + i, node.clone())),
+            );
+
+            if height < self.depth {
+                level = level
+                    .chunks(self.degree)
+                    .map(|children| {
+                        let mut children = children.to_vec();
+                        children.resize(self.degree, zero_hashes[height].clone());
+                        hash_function(children)
+                    })
+                    .collect();
+            }
+        }
+
+        nodes.sort_unstable_by_key(|(index, _)| *index);
+        nodes
+    }
+
+    /// Rebuild a tree from [`Tree::to_nodes`] output
+    ///
+    /// The leaves define the tree; any internal nodes given must match the ones they hash to.
+    /// Zero leaves are not exported, so trailing zero leaves come back as unset.
+    pub fn from_nodes(
+        degree: usize,
+        depth: usize,
+        zero: IMTNode,
+        nodes: &[(usize, IMTNode)],
+    ) -> CryptoResult<Self> {
+        let leaves_idx_0 = (degree.pow(depth as u32) - 1) / (degree - 1);
+        let nodes_count = (degree.pow((depth + 1) as u32) - 1) / (degree - 1);
+
+        let mut leaves = vec![];
+        for (index, node) in nodes {
+            if *index >= nodes_count {
+                return Err(CryptoError::tree_error(format!(
+                    "node index {index} out of range"
+                )));
+            }
+            if let Some(leaf_idx) = index.checked_sub(leaves_idx_0) {
+                if leaves.len() <= leaf_idx {
+                    leaves.resize(leaf_idx + 1, zero.clone());
+                }
+                leaves[leaf_idx] = node.clone();
+            }
+        }
+
+        let tree = Tree::with_leaves(degree, depth, zero, &leaves)?;
+        let rebuilt: HashMap<usize, IMTNode> = tree.to_nodes().into_iter().collect();
+        for (index, node) in nodes.iter().filter(|(index, _)| *index < leaves_idx_0) {
+            if rebuilt.get(index) != Some(node) {
+                return Err(CryptoError::tree_error(format!(
+                    "node {index} does not match the leaves"
+                )));
+            }
+        }
+
+        Ok(tree)
+    }
+
     /// Update cached root from IMT (internal helper)
     fn sync_root(&self) {
         // Borrow IMT mutably in a limited scope
@@ -174,6 +272,11 @@ impl Tree {
         )
         .ok();
 
+        self.leaves = if new_imt.is_some() {
+            leaves.to_vec()
+        } else {
+            vec![]
+        };
         *self.imt.borrow_mut() = new_imt;
         self.sync_root();
     }
@@ -184,18 +287,17 @@ impl Tree {
             return Err(CryptoError::LeafIndexOutOfRange { index: leaf_idx });
         }
 
-        // Get all leaves and return the requested one
-        let leaves = self.leaves();
-        Ok(leaves[leaf_idx].clone())
+        // Leaves past the initialized ones are still zero
+        Ok(self
+            .leaves
+            .get(leaf_idx)
+            .cloned()
+            .unwrap_or_else(|| self.zero.clone()))
     }
 
-    /// Get all leaves
-    pub fn leaves(&self) -> Vec<IMTNode> {
-        if let Some(ref imt) = *self.imt.borrow() {
-            imt.leaves().to_vec()
-        } else {
-            vec![self.zero.clone(); self.leaves_count]
-        }
+    /// Get all initialized leaves
+    pub fn leaves(&self) -> &[IMTNode] {
+        &self.leaves
     }
 
     /// Update a leaf at the given index
@@ -207,18 +309,46 @@ impl Tree {
         {
             let mut imt_borrow = self.imt.borrow_mut();
             if let Some(ref mut imt) = *imt_borrow {
-                imt.update(leaf_idx, leaf)
+                imt.update(leaf_idx, leaf.clone())
                     .map_err(|e| CryptoError::LeafUpdateFailed(e.to_string()))?;
             } else {
                 return Err(CryptoError::IMTNotInitialized);
             }
         }
 
+        if let Some(slot) = self.leaves.get_mut(leaf_idx) {
+            *slot = leaf;
+        }
+
         // Update root after releasing the borrow
         self.sync_root();
         Ok(())
     }
 
+    /// Compute the root as if `leaf_idx` were set to `value`, leaving the tree unchanged
+    pub fn root_with_leaf(&self, leaf_idx: usize, value: &str) -> CryptoResult<IMTNode> {
+        if leaf_idx >= self.leaves_count {
+            return Err(CryptoError::LeafIndexOutOfRange { index: leaf_idx });
+        }
+
+        let mut leaves = self.leaves.clone();
+        if leaf_idx >= leaves.len() {
+            leaves.resize(leaf_idx + 1, self.zero.clone());
+        }
+        leaves[leaf_idx] = value.to_string();
+
+        let mut imt = IMT::new(
+            hash_function,
+            self.depth,
+            self.zero.clone(),
+            self.degree,
+            leaves,
+        )
+        .map_err(|e| CryptoError::TreeError(e.to_string()))?;
+
+        imt.root().ok_or(CryptoError::IMTNotInitialized)
+    }
+
     /// Get path indices for a leaf
     pub fn path_idx_of(&self, leaf_idx: usize) -> CryptoResult<Vec<IMTNode>> {
         if leaf_idx >= self.leaves_count {
@@ -285,8 +415,7 @@ impl Tree {
         let mut sub_tree = Tree::new(self.degree, self.depth, self.zero.clone());
 
         if length > 0 {
-            let leaves = self.leaves();
-            let sub_leaves: Vec<IMTNode> = leaves.into_iter().take(length).collect();
+            let sub_leaves: Vec<IMTNode> = self.leaves().iter().take(length).cloned().collect();
             sub_tree.init_leaves(&sub_leaves);
         }
 
@@ -437,6 +566,87 @@ mod tests {
         assert_eq!(leaves[1], "2".to_string());
     }
 
+    #[test]
+    fn test_root_with_leaf() {
+        let mut tree = Tree::new(5, 2, "0".to_string());
+        tree.init_leaves(&["1".to_string(), "2".to_string()]);
+        let root_before = tree.root().clone();
+
+        let peeked = tree.root_with_leaf(1, "42").unwrap();
+        assert_eq!(tree.root(), &root_before);
+        assert_eq!(tree.leaves()[1], "2".to_string());
+
+        tree.update_leaf(1, "42".to_string()).unwrap();
+        assert_eq!(tree.root(), &peeked);
+
+        assert!(tree.root_with_leaf(25, "1").is_err());
+    }
+
+    #[test]
+    fn test_leaves_slice() {
+        let mut tree = Tree::new(5, 2, "0".to_string());
+        assert!(tree.leaves().is_empty());
+
+        tree.init_leaves(&["1".to_string(), "2".to_string()]);
+        assert_eq!(tree.leaves(), &["1".to_string(), "2".to_string()][..]);
+
+        tree.update_leaf(1, "3".to_string()).unwrap();
+        assert_eq!(tree.leaves(), &["1".to_string(), "3".to_string()][..]);
+        assert_eq!(tree.leaf(4).unwrap(), "0".to_string());
+    }
+
+    #[test]
+    fn test_tree_with_leaves() {
+        let leaves: Vec<IMTNode> = (1..=4).map(|i| i.to_string()).collect();
+        let tree = Tree::with_leaves(2, 2, "0".to_string(), &leaves).unwrap();
+
+        let mut expected = Tree::new(2, 2, "0".to_string());
+        expected.init_leaves(&leaves);
+        assert_eq!(tree.leaves(), leaves.as_slice());
+        assert_eq!(tree.root(), expected.root());
+
+        let leaves: Vec<IMTNode> = (1..=5).map(|i| i.to_string()).collect();
+        assert_eq!(
+            Tree::with_leaves(2, 2, "0".to_string(), &leaves).err(),
+            Some(CryptoError::TooManyLeaves {
+                count: 5,
+                capacity: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_tree_nodes_round_trip() {
+        let leaves: Vec<IMTNode> = (1..=7).map(|i| (i * 11).to_string()).collect();
+        let tree = Tree::with_leaves(5, 2, "0".to_string(), &leaves).unwrap();
+
+        let nodes = tree.to_nodes();
+        assert_eq!(nodes[0], (0, tree.root().clone()));
+        assert_eq!(
+            nodes.last().unwrap(),
+            &(tree.leaves_idx_0 + 6, "77".to_string())
+        );
+        // root, two populated subtrees and seven leaves
+        assert_eq!(nodes.len(), 1 + 2 + 7);
+
+        let restored = Tree::from_nodes(5, 2, "0".to_string(), &nodes).unwrap();
+        assert_eq!(restored.root(), tree.root());
+        assert_eq!(restored.leaves(), tree.leaves());
+
+        // Internal nodes that disagree with the leaves are rejected
+        let mut tampered = nodes.clone();
+        tampered[1].1 = "1".to_string();
+        assert!(Tree::from_nodes(5, 2, "0".to_string(), &tampered).is_err());
+        assert!(Tree::from_nodes(5, 2, "0".to_string(), &[(31, "1".to_string())]).is_err());
+
+        let empty = Tree::new(5, 2, "0".to_string());
+        assert!(empty.to_nodes().is_empty());
+        assert_eq!(
+            Tree::from_nodes(5, 2, "0".to_string(), &[]).unwrap().root(),
+            empty.root()
+        );
+    }
+
     #[test]
     fn test_biguint_conversion() {
         let value = BigUint::from(12345u32);
@@ -1,3 +1,4 @@
+use crate::error::{CryptoError, Result};
 use num_bigint::BigUint;
 use serde_json::Value;
 
@@ -6,6 +7,27 @@ pub fn bigint_to_bytes(value: &BigUint) -> Vec<u8> {
     value.to_bytes_be()
 }
 
+/// Convert a BigUint to exactly 32 bytes (big-endian, zero-padded)
+pub fn bigint_to_bytes_32(value: &BigUint) -> Result<[u8; 32]> {
+    let bytes = value.to_bytes_be();
+    if bytes.len() > 32 {
+        return Err(CryptoError::ValueTooLarge {
+            actual: bytes.len(),
+            max: 32,
+        });
+    }
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(out)
+}
+
+/// Convert a BigUint to exactly 32 bytes (little-endian, zero-padded)
+pub fn bigint_to_bytes_32_le(value: &BigUint) -> Result<[u8; 32]> {
+    let mut out = bigint_to_bytes_32(value)?;
+    out.reverse();
+    Ok(out)
+}
+
 /// Convert a byte array (big-endian) to a BigUint
 pub fn bytes_to_bigint(bytes: &[u8]) -> BigUint {
     BigUint::from_bytes_be(bytes)
@@ -16,9 +38,12 @@ pub fn bigint_to_hex(value: &BigUint) -> String {
     hex::encode(bigint_to_bytes(value))
 }
 
-/// Convert hex string to BigUint
-pub fn hex_to_bigint(hex_str: &str) -> Result<BigUint, hex::FromHexError> {
-    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+/// Convert hex string (optionally `0x`/`0X` prefixed) to BigUint
+pub fn hex_to_bigint(hex_str: &str) -> Result<BigUint> {
+    let hex_str = hex_str
+        .strip_prefix("0x")
+        .or_else(|| hex_str.strip_prefix("0X"))
+        .unwrap_or(hex_str);
     let bytes = hex::decode(hex_str)?;
     Ok(bytes_to_bigint(&bytes))
 }
@@ -79,6 +104,36 @@ mod tests {
         assert_eq!(value, restored);
     }
 
+    #[test]
+    fn test_bigint_to_bytes_32() {
+        let value = BigUint::from(0x0102u64);
+        let be = bigint_to_bytes_32(&value).unwrap();
+        assert_eq!(be[30..], [0x01, 0x02]);
+        assert!(be[..30].iter().all(|b| *b == 0));
+
+        let le = bigint_to_bytes_32_le(&value).unwrap();
+        assert_eq!(le[..2], [0x02, 0x01]);
+        assert!(le[2..].iter().all(|b| *b == 0));
+        assert_eq!(BigUint::from_bytes_le(&le), value);
+
+        assert_eq!(bigint_to_bytes_32(&BigUint::from(0u32)).unwrap(), [0u8; 32]);
+
+        // 2^256 - 1 is the largest value that fits
+        let max = (BigUint::from(1u32) << 256) - 1u32;
+        assert_eq!(bigint_to_bytes_32(&max).unwrap(), [0xff; 32]);
+        assert_eq!(bigint_to_bytes_32_le(&max).unwrap(), [0xff; 32]);
+
+        let too_large = BigUint::from(1u32) << 256;
+        assert_eq!(
+            bigint_to_bytes_32(&too_large),
+            Err(CryptoError::ValueTooLarge {
+                actual: 33,
+                max: 32
+            })
+        );
+        assert!(bigint_to_bytes_32_le(&too_large).is_err());
+    }
+
     #[test]
     fn test_bigint_to_hex() {
         let value = BigUint::from(255u64);
@@ -100,6 +155,22 @@ mod tests {
         assert_eq!(value, BigUint::from(255u64));
     }
 
+    #[test]
+    fn test_hex_upper_prefix() {
+        assert_eq!(hex_to_bigint("0XFF").unwrap(), BigUint::from(255u64));
+        assert_eq!(hex_to_bigint("0x0100").unwrap(), BigUint::from(256u64));
+    }
+
+    #[test]
+    fn test_hex_to_bigint_rejects_malformed_input() {
+        for input in ["0xzz", "0x0g", "xff", "0x0x01", "abc"] {
+            assert!(
+                matches!(hex_to_bigint(input), Err(CryptoError::HexDecodeError(_))),
+                "{input} should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn test_stringizing() {
         let json = serde_json::json!({
@@ -122,4 +193,3 @@ mod tests {
         assert_eq!(destringized["arr"][0], 1);
     }
 }
-
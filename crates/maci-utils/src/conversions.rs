@@ -1,4 +1,15 @@
 use cosmwasm_std::Uint256;
+use thiserror::Error;
+
+/// Errors from the checked hex conversions
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    #[error("Invalid hex string: {0}")]
+    InvalidHex(String),
+
+    #[error("Hex value is {0} bytes, wider than 32")]
+    TooLong(usize),
+}
 
 /// Convert hex string to Uint256 (OLD implementation - for reference and testing)
 /// Uses string formatting for padding
@@ -68,6 +79,19 @@ pub fn hex_to_decimal(hex_bytes: &str) -> [u8; 32] {
     array
 }
 
+/// Convert hex string to a right-aligned 32-byte array, rejecting non-hex
+/// input and values wider than 32 bytes instead of zeroing or truncating them
+pub fn hex_to_decimal_checked(hex_bytes: &str) -> Result<[u8; 32], ConversionError> {
+    let bytes = hex::decode(hex_bytes).map_err(|e| ConversionError::InvalidHex(e.to_string()))?;
+    if bytes.len() > 32 {
+        return Err(ConversionError::TooLong(bytes.len()));
+    }
+
+    let mut array: [u8; 32] = [0; 32];
+    array[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(array)
+}
+
 /// Convert hex string to Uint256
 pub fn hex_to_uint256(hex_bytes: &str) -> Uint256 {
     let bytes = hex::decode(hex_bytes).expect("Invalid hex string");
@@ -182,6 +206,41 @@ mod tests {
         let _result = hex_to_decimal("xyz");
     }
 
+    #[test]
+    fn test_hex_to_decimal_checked_valid() {
+        let full = "2066be41bebe6caf7e079360abe14fbf9118c62eabc42e2fe75e342b160a95bc";
+        assert_eq!(hex_to_decimal_checked(full).unwrap(), hex_to_decimal(full));
+
+        // Short input is right-aligned like uint256_from_hex_string
+        let short = hex_to_decimal_checked("0100").unwrap();
+        assert_eq!(Uint256::from_be_bytes(short), Uint256::from_u128(256));
+
+        assert_eq!(hex_to_decimal_checked("").unwrap(), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_hex_to_decimal_checked_too_long() {
+        let hex = "ff".repeat(33);
+        assert_eq!(
+            hex_to_decimal_checked(&hex),
+            Err(ConversionError::TooLong(33))
+        );
+    }
+
+    #[test]
+    fn test_hex_to_decimal_checked_non_hex() {
+        for hex in ["zzzz", "0xff", "abc"] {
+            assert!(
+                matches!(
+                    hex_to_decimal_checked(hex),
+                    Err(ConversionError::InvalidHex(_))
+                ),
+                "Expected InvalidHex for: {}",
+                hex
+            );
+        }
+    }
+
     #[test]
     fn test_uint256_from_hex_string_single_byte() {
         let hex = "01";
@@ -239,6 +298,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_round_trip_high_bit_values() {
+        // Values whose leading hex digits are zero or whose top bit is set
+        for shift in [240u32, 247, 248, 255] {
+            let value = Uint256::one() << shift;
+            let hex = uint256_to_hex(value);
+            assert_eq!(hex.len(), 64);
+            assert_eq!(
+                uint256_from_hex_string(&hex),
+                value,
+                "Round trip failed for 2^{}",
+                shift
+            );
+
+            // The same value written without its leading zeros, as JS does
+            let trimmed = hex.trim_start_matches('0');
+            assert_eq!(
+                uint256_from_hex_string(trimmed),
+                value,
+                "Trimmed hex failed for 2^{}",
+                shift
+            );
+        }
+
+        assert_eq!(
+            uint256_to_hex(Uint256::one() << 248),
+            format!("01{}", "0".repeat(62))
+        );
+        assert_eq!(
+            uint256_to_hex(Uint256::one() << 255),
+            format!("80{}", "0".repeat(62))
+        );
+    }
+
     #[test]
     fn test_hex_to_uint256_case_insensitive() {
         let lower = hex_to_uint256("ff");
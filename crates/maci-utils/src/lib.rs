@@ -8,6 +8,9 @@ Shared utility functions for MACI contracts.
 - **Poseidon Hashing**: ZK-friendly hash functions (hash2, hash5, etc.)
 - **Type Conversions**: Uint256 ↔ Hex ↔ Fr conversions
 - **SHA256 Utilities**: Standard hashing for non-ZK contexts
+- **Circuit Inputs**: `packedVals` packing for the process circuit
+- **Shared Types**: the `PubKey` used by every contract
+- **snarkjs Proofs**: snarkjs proof JSON ↔ the hex encoding the contracts verify
 - **Performance Optimized**: Cached Poseidon instance for better gas efficiency
 
 ## Usage
@@ -23,14 +26,26 @@ let result = hash2(data);
 
 mod babyjubjub;
 mod conversions;
+mod packed_vals;
 mod poseidon;
+mod pubkey;
 mod sha256_utils;
+mod snarkjs;
 
 // Re-export main types and functions
 pub use babyjubjub::is_on_babyjubjub_curve;
-pub use conversions::{hex_to_decimal, hex_to_uint256, uint256_from_hex_string, uint256_to_hex};
-pub use poseidon::{hash, hash2, hash5, hash_uint256, uint256_to_fr, Fr};
-pub use sha256_utils::{encode_packed, hash_256_uint256_list};
+pub use conversions::{
+    hex_to_decimal, hex_to_decimal_checked, hex_to_uint256, uint256_from_hex_string,
+    uint256_to_hex, ConversionError,
+};
+pub use packed_vals::{pack_vals, unpack_vals};
+pub use poseidon::{hash, hash10, hash12, hash2, hash5, hash_uint256, uint256_to_fr, Fr};
+pub use pubkey::PubKey;
+pub use sha256_utils::{address_to_uint256, encode_packed, hash_256_uint256_list};
+pub use snarkjs::{
+    groth16_proof_from_snarkjs, groth16_proof_to_snarkjs, plonk_proof_from_snarkjs, PlonkProofHex,
+    SnarkjsError, SnarkjsGroth16Proof, SnarkjsPlonkProof,
+};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,72 @@
+use cosmwasm_std::Uint256;
+
+/// Pack the process circuit's `packedVals` public input.
+///
+/// Layout (low to high bits): `max_vote_options` in bits 0-31, `num_sign_ups`
+/// in bits 32-63 and `circuit_type` from bit 64 up, i.e.
+/// `(num_sign_ups << 32) + (circuit_type << 64) + max_vote_options`.
+/// With circuit_type 0 (1p1v) the top term vanishes.
+pub fn pack_vals(
+    num_sign_ups: Uint256,
+    circuit_type: Uint256,
+    max_vote_options: Uint256,
+) -> Uint256 {
+    (num_sign_ups << 32) + (circuit_type << 64) + max_vote_options
+}
+
+/// Split a `packedVals` value back into `(num_sign_ups, circuit_type, max_vote_options)`.
+///
+/// Inverse of [`pack_vals`] as long as `num_sign_ups` and `max_vote_options`
+/// each fit in 32 bits, which the circuits require.
+pub fn unpack_vals(packed: Uint256) -> (Uint256, Uint256, Uint256) {
+    let circuit_type = packed >> 64;
+    let low_64 = packed - (circuit_type << 64);
+    let num_sign_ups = low_64 >> 32;
+    let max_vote_options = low_64 - (num_sign_ups << 32);
+    (num_sign_ups, circuit_type, max_vote_options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_vals_matches_contract_layout() {
+        let num_sign_ups = Uint256::from_u128(3);
+        let max_vote_options = Uint256::from_u128(5);
+
+        // 1p1v leaves the circuit_type bits empty
+        assert_eq!(
+            pack_vals(num_sign_ups, Uint256::zero(), max_vote_options),
+            Uint256::from_u128((3 << 32) + 5)
+        );
+        // qv sets bit 64
+        assert_eq!(
+            pack_vals(num_sign_ups, Uint256::one(), max_vote_options),
+            Uint256::from_u128((1 << 64) + (3 << 32) + 5)
+        );
+    }
+
+    #[test]
+    fn test_pack_unpack_round_trip() {
+        let max_u32 = Uint256::from_u128(u32::MAX as u128);
+        let cases = [
+            (Uint256::zero(), Uint256::zero(), Uint256::zero()),
+            (
+                Uint256::from_u128(3),
+                Uint256::zero(),
+                Uint256::from_u128(5),
+            ),
+            (Uint256::from_u128(3), Uint256::one(), Uint256::from_u128(5)),
+            (max_u32, Uint256::one(), max_u32),
+        ];
+
+        for (num_sign_ups, circuit_type, max_vote_options) in cases {
+            let packed = pack_vals(num_sign_ups, circuit_type, max_vote_options);
+            assert_eq!(
+                unpack_vals(packed),
+                (num_sign_ups, circuit_type, max_vote_options)
+            );
+        }
+    }
+}
@@ -130,6 +130,26 @@ pub fn hash5(data: [Uint256; 5]) -> Uint256 {
     hash_width_5(&fr_array)
 }
 
+/// Hash 10 Uint256 values as hash2 of two hash5 halves, matching maci-crypto's hash10
+pub fn hash10(data: [Uint256; 10]) -> Uint256 {
+    let left = hash5([data[0], data[1], data[2], data[3], data[4]]);
+    let right = hash5([data[5], data[6], data[7], data[8], data[9]]);
+    hash2([left, right])
+}
+
+/// Hash 12 Uint256 values as a width-4 hash over two hash5 halves and the
+/// trailing two elements, matching maci-crypto's hash12
+pub fn hash12(data: [Uint256; 12]) -> Uint256 {
+    let left = hash5([data[0], data[1], data[2], data[3], data[4]]);
+    let right = hash5([data[5], data[6], data[7], data[8], data[9]]);
+    hash(vec![
+        uint256_to_fr(&left),
+        uint256_to_fr(&right),
+        uint256_to_fr(&data[10]),
+        uint256_to_fr(&data[11]),
+    ])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,6 +190,32 @@ mod tests {
         assert_eq!(result1, result2);
     }
 
+    #[test]
+    fn test_hash10_consistency() {
+        let data: [Uint256; 10] = core::array::from_fn(|i| Uint256::from_u128(i as u128 + 1));
+        let result1 = hash10(data);
+        let result2 = hash10(data);
+        assert_eq!(result1, result2);
+        assert_ne!(result1, Uint256::zero());
+
+        let mut modified = data;
+        modified[9] = Uint256::from_u128(11);
+        assert_ne!(result1, hash10(modified));
+    }
+
+    #[test]
+    fn test_hash12_consistency() {
+        let data: [Uint256; 12] = core::array::from_fn(|i| Uint256::from_u128(i as u128 + 1));
+        let result1 = hash12(data);
+        let result2 = hash12(data);
+        assert_eq!(result1, result2);
+        assert_ne!(result1, Uint256::zero());
+
+        let mut modified = data;
+        modified[11] = Uint256::from_u128(13);
+        assert_ne!(result1, hash12(modified));
+    }
+
     #[test]
     fn test_hash2_with_zero() {
         let data = [Uint256::zero(), Uint256::zero()];
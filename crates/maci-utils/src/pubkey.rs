@@ -0,0 +1,90 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint256;
+
+/// BabyJubJub public key, shared by every MACI contract.
+///
+/// Serializes as `{"x": "<decimal>", "y": "<decimal>"}`, the same encoding the
+/// per-contract definitions used, so existing storage and messages stay valid.
+#[cw_serde]
+pub struct PubKey {
+    pub x: Uint256,
+    pub y: Uint256,
+}
+
+impl PubKey {
+    pub fn new(x: Uint256, y: Uint256) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<(Uint256, Uint256)> for PubKey {
+    fn from((x, y): (Uint256, Uint256)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<PubKey> for (Uint256, Uint256) {
+    fn from(pubkey: PubKey) -> Self {
+        (pubkey.x, pubkey.y)
+    }
+}
+
+impl From<[Uint256; 2]> for PubKey {
+    fn from([x, y]: [Uint256; 2]) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<PubKey> for [Uint256; 2] {
+    fn from(pubkey: PubKey) -> Self {
+        [pubkey.x, pubkey.y]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    /// The definition each contract carried before this type was shared
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct LegacyPubKey {
+        x: Uint256,
+        y: Uint256,
+    }
+
+    fn sample() -> LegacyPubKey {
+        LegacyPubKey {
+            x: Uint256::from_u128(0x0a1b2c3d4e5f),
+            y: Uint256::MAX,
+        }
+    }
+
+    #[test]
+    fn test_legacy_pubkey_round_trips_through_json() {
+        let legacy = sample();
+        let legacy_json = serde_json::to_string(&legacy).unwrap();
+
+        let shared: PubKey = serde_json::from_str(&legacy_json).unwrap();
+        assert_eq!(shared, PubKey::new(legacy.x, legacy.y));
+        assert_eq!(serde_json::to_string(&shared).unwrap(), legacy_json);
+
+        let back: LegacyPubKey =
+            serde_json::from_str(&serde_json::to_string(&shared).unwrap()).unwrap();
+        assert_eq!(back, legacy);
+    }
+
+    #[test]
+    fn test_pubkey_tuple_and_array_conversions() {
+        let legacy = sample();
+        let shared = PubKey::from((legacy.x, legacy.y));
+
+        assert_eq!(
+            <(Uint256, Uint256)>::from(shared.clone()),
+            (legacy.x, legacy.y)
+        );
+        assert_eq!(<[Uint256; 2]>::from(shared.clone()), [legacy.x, legacy.y]);
+        assert_eq!(PubKey::from([legacy.x, legacy.y]), shared);
+    }
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::Uint256;
+use cosmwasm_std::{Addr, Uint256};
 use sha2::{Digest, Sha256};
 
 /// Hash a list of Uint256 values using SHA256
@@ -16,6 +16,17 @@ pub fn hash_256_uint256_list(arrays: &[Uint256]) -> String {
     hex::encode(hash_result)
 }
 
+/// Convert an address to Uint256 by hashing it with SHA256
+///
+/// The 32-byte digest is read as a little-endian integer, i.e. digest byte 0
+/// is the least significant byte of the result. Circuits and off-chain
+/// certificate signers rely on this exact byte order.
+pub fn address_to_uint256(address: &Addr) -> Uint256 {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::digest(address.as_bytes()));
+    Uint256::from_le_bytes(hash)
+}
+
 /// Pack multiple 32-byte arrays into a single byte vector
 pub fn encode_packed(arrays: &[&[u8; 32]]) -> Vec<u8> {
     let total_length = arrays.len() * 32;
@@ -218,4 +229,41 @@ mod tests {
         assert_eq!(result.len(), 64);
         assert_eq!(result.capacity(), 64);
     }
+
+    #[test]
+    fn test_address_to_uint256_matches_reversed_digest() {
+        // Reference implementation: reverse the digest and read it big-endian
+        fn reversed_digest(address: &Addr) -> Uint256 {
+            let hash = Sha256::digest(address.as_bytes());
+            let mut bytes = [0u8; 32];
+            for (i, &byte) in hash.iter().enumerate() {
+                bytes[31 - i] = byte;
+            }
+            Uint256::from_be_bytes(bytes)
+        }
+
+        for address in [
+            "",
+            "contract0",
+            "dora1qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqs",
+            "dora14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9s7enn5t",
+        ] {
+            let addr = Addr::unchecked(address);
+            assert_eq!(address_to_uint256(&addr), reversed_digest(&addr));
+        }
+    }
+
+    #[test]
+    fn test_address_to_uint256_vector() {
+        use std::str::FromStr;
+
+        let addr = Addr::unchecked("contract0");
+        assert_eq!(
+            address_to_uint256(&addr),
+            Uint256::from_str(
+                "51788793381365401356776017899576520467898468617578197738183646369208722835043"
+            )
+            .unwrap()
+        );
+    }
 }
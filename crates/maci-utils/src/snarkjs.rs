@@ -0,0 +1,473 @@
+//! snarkjs proof JSON <-> the hex encoding the contracts verify
+//!
+//! Mirrors the SDK's `adaptToUncompressed`: a G1 point is `x || y` and a G2
+//! point is `x.c1 || x.c0 || y.c1 || y.c0`, each coordinate a 32-byte
+//! big-endian word. snarkjs writes coordinates as decimal strings in
+//! normalized projective form (`z = 1`) and orders each Fq2 as `[c0, c1]`.
+
+use cosmwasm_std::Uint256;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Errors converting between snarkjs JSON and contract hex
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SnarkjsError {
+    #[error("Invalid snarkjs JSON: {0}")]
+    Json(String),
+
+    #[error("Unsupported {field}: expected {expected}, got {actual}")]
+    Unsupported {
+        field: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Malformed point {field}")]
+    MalformedPoint { field: String },
+
+    #[error("Invalid coordinate in {field}: {value}")]
+    InvalidCoordinate { field: String, value: String },
+}
+
+/// A Groth16 proof as written by `snarkjs groth16 prove`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SnarkjsGroth16Proof {
+    pub pi_a: Vec<String>,
+    pub pi_b: Vec<Vec<String>>,
+    pub pi_c: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+}
+
+/// A PlonK proof as written by `snarkjs plonk prove`. snarkjs releases before
+/// 0.5 also emitted `eval_t` and `eval_r`; later ones leave the verifier to
+/// recompute them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SnarkjsPlonkProof {
+    #[serde(rename = "A")]
+    pub a: Vec<String>,
+    #[serde(rename = "B")]
+    pub b: Vec<String>,
+    #[serde(rename = "C")]
+    pub c: Vec<String>,
+    #[serde(rename = "Z")]
+    pub z: Vec<String>,
+    #[serde(rename = "T1")]
+    pub t1: Vec<String>,
+    #[serde(rename = "T2")]
+    pub t2: Vec<String>,
+    #[serde(rename = "T3")]
+    pub t3: Vec<String>,
+    #[serde(rename = "Wxi")]
+    pub wxi: Vec<String>,
+    #[serde(rename = "Wxiw")]
+    pub wxiw: Vec<String>,
+    pub eval_a: String,
+    pub eval_b: String,
+    pub eval_c: String,
+    pub eval_s1: String,
+    pub eval_s2: String,
+    pub eval_zw: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eval_t: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eval_r: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+}
+
+/// Contract hex for the fields a snarkjs PlonK proof carries. G1 points are
+/// `x || y`, field elements a single 32-byte big-endian word.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlonkProofHex {
+    pub wire_commitments: Vec<String>,
+    pub grand_product_commitment: String,
+    pub quotient_poly_commitments: Vec<String>,
+    pub wire_values_at_z: Vec<String>,
+    pub grand_product_at_z_omega: String,
+    pub quotient_polynomial_at_z: String,
+    pub linearization_polynomial_at_z: String,
+    pub permutation_polynomials_at_z: Vec<String>,
+    pub opening_at_z_proof: String,
+    pub opening_at_z_omega_proof: String,
+}
+
+fn check_label(field: &str, value: &Option<String>, expected: &str) -> Result<(), SnarkjsError> {
+    match value {
+        Some(actual) if actual != expected => Err(SnarkjsError::Unsupported {
+            field: field.to_string(),
+            expected: expected.to_string(),
+            actual: actual.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn decimal_to_hex(field: &str, value: &str) -> Result<String, SnarkjsError> {
+    let word = Uint256::from_str(value).map_err(|_| SnarkjsError::InvalidCoordinate {
+        field: field.to_string(),
+        value: value.to_string(),
+    })?;
+    Ok(hex::encode(word.to_be_bytes()))
+}
+
+/// Split contract hex into 32-byte words, expecting exactly `words` of them
+fn hex_to_decimals(field: &str, value: &str, words: usize) -> Result<Vec<String>, SnarkjsError> {
+    let malformed = || SnarkjsError::MalformedPoint {
+        field: field.to_string(),
+    };
+    let bytes = hex::decode(value).map_err(|_| malformed())?;
+    if bytes.len() != words * 32 {
+        return Err(malformed());
+    }
+    Ok(bytes
+        .chunks(32)
+        .map(|chunk| {
+            let mut word = [0u8; 32];
+            word.copy_from_slice(chunk);
+            Uint256::from_be_bytes(word).to_string()
+        })
+        .collect())
+}
+
+fn g1_to_hex(field: &str, point: &[String]) -> Result<String, SnarkjsError> {
+    match point {
+        [x, y, z] if z == "1" => Ok(decimal_to_hex(field, x)? + &decimal_to_hex(field, y)?),
+        _ => Err(SnarkjsError::MalformedPoint {
+            field: field.to_string(),
+        }),
+    }
+}
+
+fn g2_to_hex(field: &str, point: &[Vec<String>]) -> Result<String, SnarkjsError> {
+    match point {
+        [x, y, z] if x.len() == 2 && y.len() == 2 && z == &["1", "0"] => {
+            let mut hex = String::with_capacity(256);
+            for coordinate in [&x[1], &x[0], &y[1], &y[0]] {
+                hex.push_str(&decimal_to_hex(field, coordinate)?);
+            }
+            Ok(hex)
+        }
+        _ => Err(SnarkjsError::MalformedPoint {
+            field: field.to_string(),
+        }),
+    }
+}
+
+/// Contract hex `(a, b, c)` for a snarkjs Groth16 proof JSON
+pub fn groth16_proof_from_snarkjs(json: &str) -> Result<(String, String, String), SnarkjsError> {
+    let proof: SnarkjsGroth16Proof =
+        serde_json::from_str(json).map_err(|e| SnarkjsError::Json(e.to_string()))?;
+    check_label("protocol", &proof.protocol, "groth16")?;
+    check_label("curve", &proof.curve, "bn128")?;
+
+    Ok((
+        g1_to_hex("pi_a", &proof.pi_a)?,
+        g2_to_hex("pi_b", &proof.pi_b)?,
+        g1_to_hex("pi_c", &proof.pi_c)?,
+    ))
+}
+
+/// Contract hex for a snarkjs PlonK proof JSON. `eval_t` and `eval_r` map to
+/// zero when the proof omits them.
+pub fn plonk_proof_from_snarkjs(json: &str) -> Result<PlonkProofHex, SnarkjsError> {
+    let proof: SnarkjsPlonkProof =
+        serde_json::from_str(json).map_err(|e| SnarkjsError::Json(e.to_string()))?;
+    check_label("protocol", &proof.protocol, "plonk")?;
+    check_label("curve", &proof.curve, "bn128")?;
+
+    let optional = |field: &str, value: &Option<String>| match value {
+        Some(value) => decimal_to_hex(field, value),
+        None => Ok(hex::encode([0u8; 32])),
+    };
+
+    Ok(PlonkProofHex {
+        wire_commitments: vec![
+            g1_to_hex("A", &proof.a)?,
+            g1_to_hex("B", &proof.b)?,
+            g1_to_hex("C", &proof.c)?,
+        ],
+        grand_product_commitment: g1_to_hex("Z", &proof.z)?,
+        quotient_poly_commitments: vec![
+            g1_to_hex("T1", &proof.t1)?,
+            g1_to_hex("T2", &proof.t2)?,
+            g1_to_hex("T3", &proof.t3)?,
+        ],
+        wire_values_at_z: vec![
+            decimal_to_hex("eval_a", &proof.eval_a)?,
+            decimal_to_hex("eval_b", &proof.eval_b)?,
+            decimal_to_hex("eval_c", &proof.eval_c)?,
+        ],
+        grand_product_at_z_omega: decimal_to_hex("eval_zw", &proof.eval_zw)?,
+        quotient_polynomial_at_z: optional("eval_t", &proof.eval_t)?,
+        linearization_polynomial_at_z: optional("eval_r", &proof.eval_r)?,
+        permutation_polynomials_at_z: vec![
+            decimal_to_hex("eval_s1", &proof.eval_s1)?,
+            decimal_to_hex("eval_s2", &proof.eval_s2)?,
+        ],
+        opening_at_z_proof: g1_to_hex("Wxi", &proof.wxi)?,
+        opening_at_z_omega_proof: g1_to_hex("Wxiw", &proof.wxiw)?,
+    })
+}
+
+/// snarkjs Groth16 proof JSON for contract hex `(a, b, c)`
+pub fn groth16_proof_to_snarkjs(a: &str, b: &str, c: &str) -> Result<String, SnarkjsError> {
+    let g1 = |field: &str, value: &str| -> Result<Vec<String>, SnarkjsError> {
+        let mut point = hex_to_decimals(field, value, 2)?;
+        point.push("1".to_string());
+        Ok(point)
+    };
+    let b = hex_to_decimals("pi_b", b, 4)?;
+
+    let proof = SnarkjsGroth16Proof {
+        pi_a: g1("pi_a", a)?,
+        pi_b: vec![
+            vec![b[1].clone(), b[0].clone()],
+            vec![b[3].clone(), b[2].clone()],
+            vec!["1".to_string(), "0".to_string()],
+        ],
+        pi_c: g1("pi_c", c)?,
+        protocol: Some("groth16".to_string()),
+        curve: Some("bn128".to_string()),
+    };
+    serde_json::to_string(&proof).map_err(|e| SnarkjsError::Json(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // proofDeactivate in contracts/amaci/src/test/amaci_test/logs.json, as snarkjs emitted it
+    const SNARKJS_PROOF: &str = r#"{
+        "pi_a": [
+            "3137634515860586619931999996714233423621154136182558139697201007484249806238",
+            "3779264547722000370130589414525119902545498162139394276356880873817683911716",
+            "1"
+        ],
+        "pi_b": [
+            [
+                "15687269743182912395170928397292663158146880265200240655697140735420192742848",
+                "9834017064230883224263111745077542337090048078075510586975081832608015883104"
+            ],
+            [
+                "19957668016134374313267933429128253794458479666899977732635736747931267091455",
+                "9845152844476407833765447744675844897482266424306794431778231005092100483943"
+            ],
+            ["1", "0"]
+        ],
+        "pi_c": [
+            "19717218952441698692227920041223776774532557189790353630661568177077261618463",
+            "16279336746408107270670873904623209430291521368099406892777600151654289390",
+            "1"
+        ],
+        "protocol": "groth16",
+        "curve": "bn128"
+    }"#;
+
+    const HEX_A: &str = "06efd69472c9d7ba6763a84a55e44680f0b6d3e76612e0d18e4b75d86e0b959e085afce90e0ae29e1f996d9e806148d0682f3241f56be72f24831bc7008de424";
+    const HEX_B: &str = "15bddb3dce5cbbe15882789d7ad7825b411c7566cb72acbc30ce5c1b21f09b6022aeae163066b944cd4b2402f924f64f0696a17604ba6a4e9a05a8de428b5dc015c428b6da15e44639c7a619c39faf15839c21c6e24d430c6d3c28d5773cab672c1fa3c99696676a0b2e7c76521d6ffb22d9d25695040bd4b92a6d467bb9cbff";
+    const HEX_C: &str = "2b978cea20e3a2b342f4c9f1dd20380345445c2d5538da0e991e936ddfa6cd1f000936ba28ee7a64ebfd1a00ff9f954ed9e7a67074aac4af2bb5b5ef25d4cfee";
+
+    #[test]
+    fn test_groth16_from_snarkjs_matches_sdk_encoding() {
+        let (a, b, c) = groth16_proof_from_snarkjs(SNARKJS_PROOF).unwrap();
+        assert_eq!(a, HEX_A);
+        assert_eq!(b, HEX_B);
+        assert_eq!(c, HEX_C);
+    }
+
+    #[test]
+    fn test_groth16_snarkjs_round_trip() {
+        let json = groth16_proof_to_snarkjs(HEX_A, HEX_B, HEX_C).unwrap();
+        let expected: SnarkjsGroth16Proof = serde_json::from_str(SNARKJS_PROOF).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SnarkjsGroth16Proof>(&json).unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            groth16_proof_from_snarkjs(&json).unwrap(),
+            (HEX_A.to_string(), HEX_B.to_string(), HEX_C.to_string())
+        );
+    }
+
+    // A `snarkjs plonk prove` proof; commitments are 2G..10G on BN254
+    const SNARKJS_PLONK_PROOF: &str = r#"{
+        "A": [
+            "1368015179489954701390400359078579693043519447331113978918064868415326638035",
+            "9918110051302171585080402603319702774565515993150576347155970296011118125764",
+            "1"
+        ],
+        "B": [
+            "3353031288059533942658390886683067124040920775575537747144343083137631628272",
+            "19321533766552368860946552437480515441416830039777911637913418824951667761761",
+            "1"
+        ],
+        "C": [
+            "3010198690406615200373504922352659861758983907867017329644089018310584441462",
+            "4027184618003122424972590350825261965929648733675738730716654005365300998076",
+            "1"
+        ],
+        "Z": [
+            "10744596414106452074759370245733544594153395043370666422502510773307029471145",
+            "848677436511517736191562425154572367705380862894644942948681172815252343932",
+            "1"
+        ],
+        "T1": [
+            "4503322228978077916651710446042370109107355802721800704639343137502100212473",
+            "6132642251294427119375180147349983541569387941788025780665104001559216576968",
+            "1"
+        ],
+        "T2": [
+            "10415861484417082502655338383609494480414113902179649885744799961447382638712",
+            "10196215078179488638353184030336251401353352596818396260819493263908881608606",
+            "1"
+        ],
+        "T3": [
+            "3932705576657793550893430333273221375907985235130430286685735064194643946083",
+            "18813763293032256545937756946359266117037834559191913266454084342712532869153",
+            "1"
+        ],
+        "Wxi": [
+            "1624070059937464756887933993293429854168590106605707304006200119738501412969",
+            "3269329550605213075043232856820720631601935657990457502777101397807070461336",
+            "1"
+        ],
+        "Wxiw": [
+            "4444740815889402603535294170722302758225367627362056425101568584910268024244",
+            "10537263096529483164618820017164668921386457028564663708352735080900270541420",
+            "1"
+        ],
+        "eval_a": "11",
+        "eval_b": "12",
+        "eval_c": "13",
+        "eval_s1": "14",
+        "eval_s2": "15",
+        "eval_zw": "16",
+        "protocol": "plonk",
+        "curve": "bn128"
+    }"#;
+
+    fn word(value: u8) -> String {
+        format!("{:064x}", value)
+    }
+
+    #[test]
+    fn test_plonk_from_snarkjs_maps_proof_fields() {
+        let proof = plonk_proof_from_snarkjs(SNARKJS_PLONK_PROOF).unwrap();
+        assert_eq!(
+            proof.wire_commitments,
+            vec![
+                "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd315ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+                "0769bf9ac56bea3ff40232bcb1b6bd159315d84715b8e679f2d355961915abf02ab799bee0489429554fdb7c8d086475319e63b40b9c5b57cdf1ff3dd9fe2261",
+                "06a7b64af8f414bcbeef455b1da5208c9b592b83ee6599824caa6d2ee9141a7608e74e438cee31ac104ce59b94e45fe98a97d8f8a6e75664ce88ef5a41e72fbc",
+            ]
+        );
+        assert_eq!(
+            proof.grand_product_commitment,
+            "17c139df0efee0f766bc0204762b774362e4ded88953a39ce849a8a7fa163fa901e0559bacb160664764a357af8a9fe70baa9258e0b959273ffc5718c6d4cc7c"
+        );
+        assert_eq!(
+            proof.quotient_poly_commitments,
+            vec![
+                "09f4ca411a3f52f4e0792fd9e792779856719215d3b32a762afe3d5b8c684af90d8ef3d795acd4b35d4366ab22e4ad335273aa59429e26929d0f64583474d9c8",
+                "17072b2ed3bb8d759a5325f477629386cb6fc6ecb801bd76983a6b86abffe078168ada6cd130dd52017bb54bfa19377aadfe3bf05d18f41b77809f7f60d4af9e",
+                "08b1d51d23480c10f472f5e93b9cfea88238c121fe155af7043937882c306a63299836713dad3fa34e337aa412466015c366af8ec50b9d7bd05aa74642822021",
+            ]
+        );
+        assert_eq!(proof.wire_values_at_z, vec![word(11), word(12), word(13)]);
+        assert_eq!(proof.permutation_polynomials_at_z, vec![word(14), word(15)]);
+        assert_eq!(proof.grand_product_at_z_omega, word(16));
+        assert_eq!(proof.quotient_polynomial_at_z, word(0));
+        assert_eq!(proof.linearization_polynomial_at_z, word(0));
+        assert_eq!(
+            proof.opening_at_z_proof,
+            "039730ea8dff1254c0fee9c0ea777d29a9c710b7e616683f194f18c43b43b869073a5ffcc6fc7a28c30723d6e58ce577356982d65b833a5a5c15bf9024b43d98"
+        );
+        assert_eq!(
+            proof.opening_at_z_omega_proof,
+            "09d3a257b99f1ad804a9e2354ea71c72da7fa518f4ca7904c6951d924b4045b4174be12ae3fd899d55d3e487fa103f951a24ca0f670ecae802209b2518ccca6c"
+        );
+
+        // Proofs from snarkjs before 0.5 still carry t(z) and r(z)
+        let mut legacy: SnarkjsPlonkProof = serde_json::from_str(SNARKJS_PLONK_PROOF).unwrap();
+        legacy.eval_t = Some("17".to_string());
+        legacy.eval_r = Some("18".to_string());
+        let proof = plonk_proof_from_snarkjs(&serde_json::to_string(&legacy).unwrap()).unwrap();
+        assert_eq!(proof.quotient_polynomial_at_z, word(17));
+        assert_eq!(proof.linearization_polynomial_at_z, word(18));
+    }
+
+    #[test]
+    fn test_plonk_from_snarkjs_rejects_malformed_input() {
+        assert!(matches!(
+            plonk_proof_from_snarkjs(SNARKJS_PROOF),
+            Err(SnarkjsError::Json(_))
+        ));
+
+        let mut proof: SnarkjsPlonkProof = serde_json::from_str(SNARKJS_PLONK_PROOF).unwrap();
+        proof.protocol = Some("groth16".to_string());
+        assert!(matches!(
+            plonk_proof_from_snarkjs(&serde_json::to_string(&proof).unwrap()),
+            Err(SnarkjsError::Unsupported { .. })
+        ));
+
+        let mut proof: SnarkjsPlonkProof = serde_json::from_str(SNARKJS_PLONK_PROOF).unwrap();
+        proof.z[2] = "0".to_string();
+        assert_eq!(
+            plonk_proof_from_snarkjs(&serde_json::to_string(&proof).unwrap()),
+            Err(SnarkjsError::MalformedPoint {
+                field: "Z".to_string()
+            })
+        );
+
+        let mut proof: SnarkjsPlonkProof = serde_json::from_str(SNARKJS_PLONK_PROOF).unwrap();
+        proof.eval_s1 = "0x0e".to_string();
+        assert!(matches!(
+            plonk_proof_from_snarkjs(&serde_json::to_string(&proof).unwrap()),
+            Err(SnarkjsError::InvalidCoordinate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_groth16_from_snarkjs_rejects_malformed_input() {
+        let mut proof: SnarkjsGroth16Proof = serde_json::from_str(SNARKJS_PROOF).unwrap();
+        proof.protocol = Some("plonk".to_string());
+        assert!(matches!(
+            groth16_proof_from_snarkjs(&serde_json::to_string(&proof).unwrap()),
+            Err(SnarkjsError::Unsupported { .. })
+        ));
+
+        let mut proof: SnarkjsGroth16Proof = serde_json::from_str(SNARKJS_PROOF).unwrap();
+        proof.pi_a[2] = "0".to_string();
+        assert_eq!(
+            groth16_proof_from_snarkjs(&serde_json::to_string(&proof).unwrap()),
+            Err(SnarkjsError::MalformedPoint {
+                field: "pi_a".to_string()
+            })
+        );
+
+        let mut proof: SnarkjsGroth16Proof = serde_json::from_str(SNARKJS_PROOF).unwrap();
+        proof.pi_c[0] = "0x12".to_string();
+        assert!(matches!(
+            groth16_proof_from_snarkjs(&serde_json::to_string(&proof).unwrap()),
+            Err(SnarkjsError::InvalidCoordinate { .. })
+        ));
+
+        assert!(matches!(
+            groth16_proof_from_snarkjs("{"),
+            Err(SnarkjsError::Json(_))
+        ));
+        assert_eq!(
+            groth16_proof_to_snarkjs(&HEX_A[2..], HEX_B, HEX_C),
+            Err(SnarkjsError::MalformedPoint {
+                field: "pi_a".to_string()
+            })
+        );
+    }
+}
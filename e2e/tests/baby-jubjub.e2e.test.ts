@@ -8,6 +8,7 @@
  * - Point addition
  * - Scalar multiplication
  * - Pack/unpack points
+ * - Rejection of malformed packed points
  * - Point validation (inCurve)
  */
 
@@ -47,12 +48,22 @@ interface PackUnpackData {
   packed: string;
 }
 
+interface InvalidPackUnpackData {
+  packed: string;
+  reason: string;
+}
+
 interface InCurveData {
   point: PointJson;
   on_curve: boolean;
 }
 
-type BabyJubjubData = AddPointData | MulPointEscalarData | PackUnpackData | InCurveData;
+type BabyJubjubData =
+  | AddPointData
+  | MulPointEscalarData
+  | PackUnpackData
+  | InvalidPackUnpackData
+  | InCurveData;
 
 interface BabyJubjubTestVector {
   name: string;
@@ -163,6 +174,22 @@ describe('Baby Jubjub E2E Tests', function () {
     });
   });
 
+  describe('3b. Invalid Packed Points', function () {
+    it('should reject the same malformed points as Rust', function () {
+      const invalidVectors = testVectors.filter((v) => v.vector_type === 'invalidPackUnpack');
+      expect(invalidVectors).to.not.be.empty;
+
+      invalidVectors.forEach((vector) => {
+        const data = vector.data as InvalidPackUnpackData;
+        console.log(`\n  Testing: ${vector.name}`);
+        console.log(`  ${vector.description}`);
+
+        expect(unpackPoint(BigInt(data.packed))).to.be.null;
+        console.log(`  ✓ Rejected: ${data.reason}`);
+      });
+    });
+  });
+
   describe('4. Point Validation (inCurve)', function () {
     it('should match Rust inCurve implementation', function () {
       const inCurveVectors = testVectors.filter((v) => v.vector_type === 'inCurve');
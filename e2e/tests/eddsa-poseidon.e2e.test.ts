@@ -53,6 +53,7 @@ interface DerivePublicKeyData {
   private_key_bytes: string;
   secret_scalar: string;
   public_key: PointJson;
+  hashing_algorithm: string;
 }
 
 interface SignVerifyData {
@@ -62,6 +63,7 @@ interface SignVerifyData {
   public_key: PointJson;
   signature: SignatureJson;
   valid: boolean;
+  hashing_algorithm: string;
 }
 
 interface PackSignatureData {
@@ -75,6 +77,7 @@ interface SdkKeysData {
   formatted_priv_key: string;
   pub_key: PointJson;
   packed_pub_key: string;
+  hashing_algorithm: string;
 }
 
 interface KeypairModuleData {
@@ -83,6 +86,7 @@ interface KeypairModuleData {
   secret_scalar: string;
   pub_key: PointJson;
   commitment: string;
+  hashing_algorithm: string;
 }
 
 type EdDSAData =